@@ -3,6 +3,7 @@ use scrypto::prelude::*;
 blueprint! {
     struct Account {
         vaults: LazyMap<ResourceAddress, Vault>,
+        deposit_mode: DepositMode,
     }
 
     impl Account {
@@ -16,9 +17,18 @@ blueprint! {
                 .method("balance", rule!(allow_all))
                 .method("deposit", rule!(allow_all))
                 .method("deposit_batch", rule!(allow_all))
+                .method("try_deposit_or_refund", rule!(allow_all))
+                .method("try_deposit_batch_or_refund", rule!(allow_all))
+                .method("deposit_mode", rule!(allow_all))
                 .default(withdraw_rule);
 
-            Self { vaults }.instantiate().add_access_check(access_rules).globalize()
+            Self {
+                vaults,
+                deposit_mode: DepositMode::AcceptAll,
+            }
+            .instantiate()
+            .add_access_check(access_rules)
+            .globalize()
         }
 
         pub fn new(withdraw_rule: AccessRule) -> ComponentAddress {
@@ -36,8 +46,55 @@ blueprint! {
                 .unwrap_or_default()
         }
 
+        /// Returns which resources this account accepts deposits of.
+        pub fn deposit_mode(&self) -> DepositMode {
+            self.deposit_mode.clone()
+        }
+
+        /// Changes which resources this account accepts deposits of.
+        pub fn set_deposit_mode(&mut self, deposit_mode: DepositMode) {
+            self.deposit_mode = deposit_mode;
+        }
+
         /// Deposits resource into this account.
+        ///
+        /// A runtime error is raised if the deposit mode doesn't accept the resource.
         pub fn deposit(&mut self, bucket: Bucket) {
+            let resource_address = bucket.resource_address();
+            if !self.deposit_mode.accepts(&resource_address) {
+                panic!("Deposits of {} are not accepted", resource_address);
+            }
+            self.put(bucket);
+        }
+
+        /// Deposit a batch of buckets into this account
+        pub fn deposit_batch(&mut self, buckets: Vec<Bucket>) {
+            for bucket in buckets {
+                self.deposit(bucket);
+            }
+        }
+
+        /// Deposits resource into this account, or returns it if the deposit mode doesn't
+        /// accept the resource.
+        pub fn try_deposit_or_refund(&mut self, bucket: Bucket) -> Option<Bucket> {
+            if self.deposit_mode.accepts(&bucket.resource_address()) {
+                self.put(bucket);
+                None
+            } else {
+                Some(bucket)
+            }
+        }
+
+        /// Deposits a batch of buckets into this account, and returns those of resources
+        /// which the deposit mode doesn't accept.
+        pub fn try_deposit_batch_or_refund(&mut self, buckets: Vec<Bucket>) -> Vec<Bucket> {
+            buckets
+                .into_iter()
+                .filter_map(|bucket| self.try_deposit_or_refund(bucket))
+                .collect()
+        }
+
+        fn put(&mut self, bucket: Bucket) {
             let resource_address = bucket.resource_address();
             match self.vaults.get(&resource_address) {
                 Some(mut v) => {
@@ -50,10 +107,14 @@ blueprint! {
             }
         }
 
-        /// Deposit a batch of buckets into this account
-        pub fn deposit_batch(&mut self, buckets: Vec<Bucket>) {
-            for bucket in buckets {
-                self.deposit(bucket);
+        /// Locks some amount of XRD in this account as transaction fee.
+        pub fn lock_fee(&mut self, amount: Decimal) {
+            let vault = self.vaults.get(&RADIX_TOKEN);
+            match vault {
+                Some(mut vault) => vault.lock_fee(amount),
+                None => {
+                    panic!("No XRD in account");
+                }
             }
         }
 
@@ -183,6 +183,31 @@ impl ComponentObjects {
             .map(|(lazy_map_id, lazy_map)| (lazy_map_id, lazy_map.get(key).map(|v| v.to_vec())))
     }
 
+    pub fn remove_lazy_map_entry(&mut self, lazy_map_id: &LazyMapId, key: &[u8]) {
+        if self.borrowed_vault.is_some() {
+            panic!("Should not be taking while value is being borrowed");
+        }
+
+        let (_, lazy_map) = self.get_lazy_map_mut(lazy_map_id).unwrap();
+        lazy_map.remove(key);
+    }
+
+    pub fn get_lazy_map_entries(
+        &mut self,
+        lazy_map_id: &LazyMapId,
+    ) -> Option<BTreeMap<Vec<u8>, Vec<u8>>> {
+        if self.borrowed_vault.is_some() {
+            panic!("Should not be taking while value is being borrowed");
+        }
+
+        self.get_lazy_map_mut(lazy_map_id).map(|(_, lazy_map)| {
+            lazy_map
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        })
+    }
+
     fn get_lazy_map_mut(
         &mut self,
         lazy_map_id: &LazyMapId,
@@ -1,8 +1,39 @@
+use scrypto::constants::*;
 use scrypto::crypto::hash;
 use scrypto::engine::types::*;
 use scrypto::rust::ops::Range;
 
 pub const ECDSA_TOKEN_BUCKET_ID: BucketId = 0;
+pub const ED25519_TOKEN_BUCKET_ID: BucketId = 1;
+pub const SYSTEM_TOKEN_BUCKET_ID: BucketId = 2;
+
+/// The first byte of the address of a native component, which no other component uses.
+const NATIVE_COMPONENT_PREFIX: u8 = 0xff;
+
+/// The packages of native components.
+const NATIVE_PACKAGES: [PackageAddress; 3] =
+    [POOL_PACKAGE, ACCESS_CONTROLLER_PACKAGE, VALIDATOR_PACKAGE];
+
+/// Returns whether components of the given package are native.
+pub fn is_native_package(package_address: &PackageAddress) -> bool {
+    NATIVE_PACKAGES.contains(package_address)
+}
+
+/// Returns whether a component is native, which its address tells without loading it.
+pub fn is_native_component(component_address: &ComponentAddress) -> bool {
+    *component_address == EPOCH_MANAGER || native_component_package(component_address).is_some()
+}
+
+/// Returns the package of a native component, which its address tells without loading it.
+pub fn native_component_package(component_address: &ComponentAddress) -> Option<PackageAddress> {
+    if component_address.0[0] != NATIVE_COMPONENT_PREFIX {
+        return None;
+    }
+    NATIVE_PACKAGES
+        .iter()
+        .find(|package_address| package_address.0[25] == component_address.0[1])
+        .cloned()
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IdSpace {
@@ -60,7 +91,26 @@ impl IdAllocator {
     ) -> Result<ComponentAddress, IdAllocatorError> {
         let mut data = transaction_hash.to_vec();
         data.extend(self.next()?.to_le_bytes());
-        Ok(ComponentAddress(hash(data).lower_26_bytes()))
+        let mut address = hash(data).lower_26_bytes();
+        if address[0] == NATIVE_COMPONENT_PREFIX {
+            address[0] = 0;
+        }
+        Ok(ComponentAddress(address))
+    }
+
+    /// Creates a new address for a component of a native package.
+    ///
+    /// The address starts with the native component prefix, followed by the last byte of
+    /// the package address.
+    pub fn new_native_component_address(
+        &mut self,
+        transaction_hash: Hash,
+        package_address: PackageAddress,
+    ) -> Result<ComponentAddress, IdAllocatorError> {
+        let mut component_address = self.new_component_address(transaction_hash)?;
+        component_address.0[0] = NATIVE_COMPONENT_PREFIX;
+        component_address.0[1] = package_address.0[25];
+        Ok(component_address)
     }
 
     /// Creates a new resource address.
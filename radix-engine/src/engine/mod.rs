@@ -9,5 +9,8 @@ pub use component_objects::*;
 pub use id_allocator::*;
 pub use id_validator::*;
 pub use process::{Process, SNodeState, SystemApi};
-pub use track::{CommitReceipt, Track};
-pub use wasm_env::{EnvModuleResolver, ENGINE_FUNCTION_INDEX, ENGINE_FUNCTION_NAME};
+pub use track::{CommitReceipt, RoyaltyRecipient, Track, TrackSavepoint};
+pub use wasm_env::{
+    EnvModuleResolver, ENGINE_FUNCTION_INDEX, ENGINE_FUNCTION_NAME, GAS_FUNCTION_INDEX,
+    GAS_FUNCTION_NAME,
+};
@@ -95,7 +95,43 @@ pub trait SystemApi {
 
     fn create_resource(&mut self, resource_manager: ResourceManager) -> ResourceAddress;
 
+    fn create_vault(&mut self, container: ResourceContainer) -> VaultId;
+
+    fn get_epoch(&mut self) -> u64;
+
+    fn create_component(&mut self, component: Component) -> Result<ComponentAddress, RuntimeError>;
+
+    fn borrow_global_mut_component(
+        &mut self,
+        component_address: ComponentAddress,
+    ) -> Result<Component, RuntimeError>;
+
+    fn return_borrowed_global_component(
+        &mut self,
+        component_address: ComponentAddress,
+        component: Component,
+    );
+
+    fn borrow_global_mut_vault(
+        &mut self,
+        component_address: &ComponentAddress,
+        vault_id: &VaultId,
+    ) -> Vault;
+
+    fn return_borrowed_global_vault(
+        &mut self,
+        component_address: &ComponentAddress,
+        vault_id: &VaultId,
+        vault: Vault,
+    );
+
+    fn get_package(&mut self, package_address: &PackageAddress) -> Option<&Package>;
+
+    fn get_package_mut(&mut self, package_address: &PackageAddress) -> Option<&mut Package>;
+
     fn create_package(&mut self, package: Package) -> PackageAddress;
+
+    fn lock_fee(&mut self, vault_id: VaultId, fee: ResourceContainer) -> Result<(), RuntimeError>;
 }
 
 pub enum SNodeState {
@@ -106,6 +142,15 @@ pub enum SNodeState {
     Scrypto(ScryptoActorInfo, Option<Component>),
     ResourceStatic,
     ResourceRef(ResourceAddress, ResourceManager),
+    ComponentRef(ComponentAddress, Component),
+    ComponentMetadata(HashMap<String, String>),
+    PoolStatic,
+    PoolRef(ComponentAddress, Pool),
+    AccessControllerStatic,
+    AccessControllerRef(ComponentAddress, Component, AccessController),
+    ValidatorStatic,
+    ValidatorRef(ComponentAddress, Component, Validator),
+    EpochManagerRef(EpochManager),
     BucketRef(BucketId, Bucket),
     Bucket(Bucket),
     ProofRef(ProofId, Proof),
@@ -291,7 +336,15 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     ));
                 }
 
+                let code_len = package.code().len() as u32;
                 let (module, memory) = package.load_module().unwrap();
+                let instantiation_cost = self
+                    .track
+                    .fee_table()
+                    .wasm_instantiation_per_byte()
+                    .saturating_mul(code_len);
+                self.track
+                    .consume_cost_units(instantiation_cost, "wasm_instantiation")?;
 
                 let (interpreter_state, args) = if let Some(component) = component_state {
                     let component_address = actor.component_address().unwrap().clone();
@@ -356,6 +409,31 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
 
                 Ok(return_value)
             }
+            SNodeState::ComponentRef(_, component) => component
+                .main(function.as_str(), args)
+                .map_err(RuntimeError::ComponentError),
+            SNodeState::ComponentMetadata(metadata) => Ok(ScryptoValue::from_value(metadata)),
+            SNodeState::PoolStatic => {
+                Pool::static_main(function.as_str(), args, self).map_err(RuntimeError::PoolError)
+            }
+            SNodeState::PoolRef(pool_address, pool) => pool
+                .main(*pool_address, function.as_str(), args, self)
+                .map_err(RuntimeError::PoolError),
+            SNodeState::AccessControllerStatic => {
+                AccessController::static_main(function.as_str(), args, self)
+                    .map_err(RuntimeError::AccessControllerError)
+            }
+            SNodeState::AccessControllerRef(controller_address, _, controller) => controller
+                .main(*controller_address, function.as_str(), args, self)
+                .map_err(RuntimeError::AccessControllerError),
+            SNodeState::ValidatorStatic => Validator::static_main(function.as_str(), args, self)
+                .map_err(RuntimeError::ValidatorError),
+            SNodeState::ValidatorRef(validator_address, _, validator) => validator
+                .main(*validator_address, function.as_str(), args, self)
+                .map_err(RuntimeError::ValidatorError),
+            SNodeState::EpochManagerRef(epoch_manager) => epoch_manager
+                .main(function.as_str(), args, self)
+                .map_err(RuntimeError::EpochManagerError),
             SNodeState::BucketRef(bucket_id, bucket) => bucket
                 .main(*bucket_id, function.as_str(), args, self)
                 .map_err(RuntimeError::BucketError),
@@ -404,9 +482,63 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         function: String,
         args: Vec<ScryptoValue>,
     ) -> Result<ScryptoValue, RuntimeError> {
+        // Native components are called like any other component, and told apart by address
+        if let SNodeRef::Scrypto(ScryptoActor::Component(component_address)) = &snode_ref {
+            if *component_address == EPOCH_MANAGER {
+                return self.invoke_snode(SNodeRef::EpochManagerRef, function, args);
+            }
+            let package_address = native_component_package(component_address);
+            if package_address == Some(POOL_PACKAGE) {
+                return self.invoke_snode(SNodeRef::PoolRef(*component_address), function, args);
+            } else if package_address == Some(ACCESS_CONTROLLER_PACKAGE) {
+                return self.invoke_snode(
+                    SNodeRef::AccessControllerRef(*component_address),
+                    function,
+                    args,
+                );
+            } else if package_address == Some(VALIDATOR_PACKAGE) {
+                return self.invoke_snode(
+                    SNodeRef::ValidatorRef(*component_address),
+                    function,
+                    args,
+                );
+            }
+        }
+        // Validators are created like any other blueprint
+        if let SNodeRef::Scrypto(ScryptoActor::Blueprint(package_address, _)) = &snode_ref {
+            if *package_address == VALIDATOR_PACKAGE {
+                return self.invoke_snode(SNodeRef::ValidatorStatic, function, args);
+            }
+        }
+
+        // Costing
+        let invoke_cost = self.track.fee_table().invoke_snode();
+        self.track.consume_cost_units(invoke_cost, "invoke_snode")?;
+
         // Authorization and state load
         let (mut snode, method_auths) = match &snode_ref {
-            SNodeRef::PackageStatic => Ok((SNodeState::PackageStatic, vec![])),
+            SNodeRef::PackageStatic => {
+                // Functions of the package owner are authorized against the upgrade rule
+                let mut method_auths = vec![];
+                if matches!(
+                    function.as_str(),
+                    "upgrade" | "set_royalty_config" | "claim_royalty" | "set_metadata"
+                ) {
+                    let package_address = args
+                        .get(0)
+                        .and_then(|arg| scrypto_decode::<PackageAddress>(&arg.raw).ok());
+                    if let Some(package_address) = package_address {
+                        if let Some(rule) = self
+                            .track
+                            .get_package(&package_address)
+                            .and_then(|package| package.upgrade_rule())
+                        {
+                            method_auths.push(convert(&Type::Unit, &Value::Unit, rule));
+                        }
+                    }
+                }
+                Ok((SNodeState::PackageStatic, method_auths))
+            }
             SNodeRef::AuthZoneRef => {
                 if let Some(auth_zone) = self.auth_zone.take() {
                     Ok((SNodeState::AuthZone(auth_zone), vec![]))
@@ -471,6 +603,34 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     }
                 }
             }
+            SNodeRef::MigrateComponent(component_address, package_address) => {
+                let component = self
+                    .track
+                    .borrow_global_mut_component(component_address.clone())?;
+                match self.check_migration(*component_address, &component, *package_address) {
+                    Ok(method_auths) => {
+                        let blueprint_name = component.blueprint_name().to_string();
+                        let export_name = format!("{}_migrate", blueprint_name);
+                        Ok((
+                            SNodeState::Scrypto(
+                                ScryptoActorInfo::component(
+                                    package_address.clone(),
+                                    blueprint_name,
+                                    export_name,
+                                    component_address.clone(),
+                                ),
+                                Some(component),
+                            ),
+                            method_auths,
+                        ))
+                    }
+                    Err(error) => {
+                        self.track
+                            .return_borrowed_global_component(*component_address, component);
+                        Err(error)
+                    }
+                }
+            }
             SNodeRef::ResourceStatic => Ok((SNodeState::ResourceStatic, vec![])),
             SNodeRef::ResourceRef(resource_address) => {
                 let resource_manager: ResourceManager = self
@@ -482,6 +642,107 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     vec![method_auth],
                 ))
             }
+            SNodeRef::ComponentRef(component_address) => {
+                // Reads don't borrow the component, so that it can read its own metadata
+                if function == "get_metadata" {
+                    let metadata = self.get_component_metadata(*component_address)?;
+                    Ok((SNodeState::ComponentMetadata(metadata), vec![]))
+                } else {
+                    let component = self
+                        .track
+                        .borrow_global_mut_component(component_address.clone())?;
+                    let method_auths = component.native_authorization(&function);
+                    Ok((
+                        SNodeState::ComponentRef(component_address.clone(), component),
+                        method_auths,
+                    ))
+                }
+            }
+            SNodeRef::PoolStatic => Ok((SNodeState::PoolStatic, vec![])),
+            SNodeRef::PoolRef(pool_address) => {
+                let component = self
+                    .track
+                    .get_component(*pool_address)
+                    .ok_or(RuntimeError::ComponentNotFound(*pool_address))?;
+                if component.package_address() != POOL_PACKAGE {
+                    return Err(RuntimeError::PoolError(PoolError::NotAPool(*pool_address)));
+                }
+                let pool: Pool = scrypto_decode(component.state())
+                    .map_err(|e| RuntimeError::PoolError(PoolError::InvalidRequestData(e)))?;
+                let method_auths = pool.authorization(&function);
+                Ok((SNodeState::PoolRef(*pool_address, pool), method_auths))
+            }
+            SNodeRef::AccessControllerStatic => Ok((SNodeState::AccessControllerStatic, vec![])),
+            SNodeRef::AccessControllerRef(controller_address) => {
+                let component = self
+                    .track
+                    .borrow_global_mut_component(*controller_address)?;
+                let controller = if component.package_address() == ACCESS_CONTROLLER_PACKAGE {
+                    scrypto_decode::<AccessController>(component.state()).map_err(|e| {
+                        AccessControllerError::InvalidRequestData(e)
+                    })
+                } else {
+                    Err(AccessControllerError::NotAnAccessController(*controller_address))
+                };
+                match controller {
+                    Ok(controller) => {
+                        let method_auths = controller.authorization(&function);
+                        Ok((
+                            SNodeState::AccessControllerRef(
+                                *controller_address,
+                                component,
+                                controller,
+                            ),
+                            method_auths,
+                        ))
+                    }
+                    Err(error) => {
+                        self.track
+                            .return_borrowed_global_component(*controller_address, component);
+                        Err(RuntimeError::AccessControllerError(error))
+                    }
+                }
+            }
+            SNodeRef::ValidatorStatic => Ok((SNodeState::ValidatorStatic, vec![])),
+            SNodeRef::ValidatorRef(validator_address) => {
+                let component = self
+                    .track
+                    .borrow_global_mut_component(*validator_address)?;
+                let validator = if component.package_address() == VALIDATOR_PACKAGE
+                    && *validator_address != EPOCH_MANAGER
+                {
+                    scrypto_decode::<Validator>(component.state())
+                        .map_err(ValidatorError::InvalidRequestData)
+                } else {
+                    Err(ValidatorError::NotAValidator(*validator_address))
+                };
+                match validator {
+                    Ok(validator) => {
+                        let method_auths = validator.authorization(&function);
+                        Ok((
+                            SNodeState::ValidatorRef(*validator_address, component, validator),
+                            method_auths,
+                        ))
+                    }
+                    Err(error) => {
+                        self.track
+                            .return_borrowed_global_component(*validator_address, component);
+                        Err(RuntimeError::ValidatorError(error))
+                    }
+                }
+            }
+            SNodeRef::EpochManagerRef => {
+                let component = self
+                    .track
+                    .get_component(EPOCH_MANAGER)
+                    .ok_or(RuntimeError::ComponentNotFound(EPOCH_MANAGER))?;
+                let epoch_manager: EpochManager =
+                    scrypto_decode(component.state()).map_err(|e| {
+                        RuntimeError::EpochManagerError(EpochManagerError::InvalidRequestData(e))
+                    })?;
+                let method_auths = epoch_manager.authorization(&function);
+                Ok((SNodeState::EpochManagerRef(epoch_manager), method_auths))
+            }
             SNodeRef::Bucket(bucket_id) => {
                 let bucket = self
                     .buckets
@@ -542,6 +803,30 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     vec![method_auth.clone()],
                 ))
             }
+            SNodeRef::ComponentVaultRef(component_address, vault_id) => {
+                // Only the issuer methods may be called on vaults of other components
+                if !matches!(
+                    function.as_str(),
+                    "recall_vault" | "freeze_vault" | "unfreeze_vault"
+                ) {
+                    return Err(RuntimeError::IllegalSystemCall);
+                }
+                if !self.track.vault_exists(component_address, vault_id) {
+                    return Err(RuntimeError::VaultNotFound(*vault_id));
+                }
+
+                let vault = self.track.borrow_vault_mut(component_address, vault_id);
+                let resource_address = vault.resource_address();
+                let method_auth = self
+                    .track
+                    .get_resource_manager(&resource_address)
+                    .unwrap()
+                    .get_auth(&function, &args);
+                Ok((
+                    SNodeState::VaultRef(vault_id.clone(), Some(*component_address), vault),
+                    vec![method_auth.clone()],
+                ))
+            }
         }?;
 
         // Authorization check
@@ -553,7 +838,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
 
             match &snode {
                 // Resource auth check includes caller
-                SNodeState::ResourceRef(_, _) | SNodeState::VaultRef(_, _, _) | SNodeState::BucketRef(_, _) | SNodeState::Bucket(_) => {
+                SNodeState::ResourceRef(_, _) | SNodeState::ComponentRef(_, _) | SNodeState::PoolRef(_, _) | SNodeState::AccessControllerRef(_, _, _) | SNodeState::ValidatorRef(_, _, _) | SNodeState::VaultRef(_, _, _) | SNodeState::BucketRef(_, _) | SNodeState::Bucket(_) => {
                     if let Some(auth_zone) = self.caller_auth_zone {
                         auth_zones.push(auth_zone);
                     }
@@ -562,9 +847,10 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                 _ => { }
             };
 
+            let clock = self.track.clock();
             for method_auth in method_auths {
                 method_auth
-                    .check(&auth_zones)
+                    .check(&auth_zones, clock)
                     .map_err(|error| RuntimeError::AuthorizationError {
                         function: function.clone(),
                         authorization: method_auth,
@@ -573,6 +859,11 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             }
         }
 
+        // Royalties
+        if let SNodeState::Scrypto(actor, component_state) = &snode {
+            self.charge_royalties(actor, component_state.as_ref(), &function)?;
+        }
+
         // Execution
         let result = match snode {
             SNodeState::Proof(proof) => {
@@ -631,10 +922,13 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     }
                     SNodeState::Scrypto(actor, component_state) => {
                         if let Some(component_address) = actor.component_address() {
-                            self.track.return_borrowed_global_component(
-                                component_address,
-                                component_state.unwrap(),
-                            );
+                            let mut component = component_state.unwrap();
+                            // A migrated component now belongs to the package which migrated it
+                            if component.package_address() != *actor.package_address() {
+                                component.set_package_address(actor.package_address().clone());
+                            }
+                            self.track
+                                .return_borrowed_global_component(component_address, component);
                         }
                     }
                     SNodeState::ResourceRef(resource_address, resource_manager) => {
@@ -643,6 +937,18 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                             resource_manager,
                         );
                     }
+                    SNodeState::ComponentRef(component_address, component) => {
+                        self.track
+                            .return_borrowed_global_component(component_address, component);
+                    }
+                    SNodeState::AccessControllerRef(address, mut component, controller) => {
+                        component.set_state(scrypto_encode(&controller));
+                        self.track.return_borrowed_global_component(address, component);
+                    }
+                    SNodeState::ValidatorRef(address, mut component, validator) => {
+                        component.set_state(scrypto_encode(&validator));
+                        self.track.return_borrowed_global_component(address, component);
+                    }
                     SNodeState::BucketRef(bucket_id, bucket) => {
                         self.buckets.insert(bucket_id, bucket);
                     }
@@ -666,6 +972,163 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(result)
     }
 
+    /// Returns the metadata of a component, which is either the one running in this process
+    /// or one not borrowed by any other.
+    fn get_component_metadata(
+        &mut self,
+        component_address: ComponentAddress,
+    ) -> Result<HashMap<String, String>, RuntimeError> {
+        if let Some(WasmProcess {
+            interpreter_state:
+                InterpreterState::Component {
+                    component_address: actor_address,
+                    component,
+                    ..
+                },
+            ..
+        }) = &self.wasm_process_state
+        {
+            if *actor_address == component_address {
+                return Ok(component.metadata().clone());
+            }
+        }
+
+        let component = self.track.get_unborrowed_component(component_address)?;
+        Ok(component.metadata().clone())
+    }
+
+    /// Charges the royalties of the package, and of the component if any, for a call.
+    fn charge_royalties(
+        &mut self,
+        actor: &ScryptoActorInfo,
+        component: Option<&Component>,
+        function: &str,
+    ) -> Result<(), RuntimeError> {
+        let package_address = actor.package_address().clone();
+        let package_royalty = self
+            .track
+            .get_package(&package_address)
+            .ok_or(RuntimeError::PackageNotFound(package_address))?
+            .royalty_config(actor.blueprint_name())
+            .map(|royalty_config| royalty_config.get(function))
+            .unwrap_or(Decimal::zero());
+        self.track
+            .charge_royalty(RoyaltyRecipient::Package(package_address), package_royalty);
+
+        if let (Some(component), Some(component_address)) = (component, actor.component_address()) {
+            let component_royalty = component.royalty_config().get(function);
+            self.track.charge_royalty(
+                RoyaltyRecipient::Component(component_address),
+                component_royalty,
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that a component can be migrated to the given package, and returns the
+    /// authorization of the migration.
+    ///
+    /// The package must be a later version of the component's package, and the component
+    /// must have opted into migrations when instantiated.
+    fn check_migration(
+        &mut self,
+        component_address: ComponentAddress,
+        component: &Component,
+        package_address: PackageAddress,
+    ) -> Result<Vec<MethodAuthorization>, RuntimeError> {
+        let migration_auth = component
+            .migration_authorization()
+            .ok_or(RuntimeError::MigrationNotAllowed(component_address))?;
+
+        let current_package_address = component.package_address();
+        let blueprint_name = component.blueprint_name();
+        let package = self
+            .track
+            .get_package(&package_address)
+            .ok_or(RuntimeError::PackageNotFound(package_address))?;
+        if !package.contains_blueprint(blueprint_name) {
+            return Err(RuntimeError::BlueprintNotFound(
+                package_address,
+                blueprint_name.to_string(),
+            ));
+        }
+        let mut predecessor = package.predecessor();
+        loop {
+            match predecessor {
+                Some(address) if address == current_package_address => break,
+                Some(address) => {
+                    predecessor = self
+                        .track
+                        .get_package(&address)
+                        .ok_or(RuntimeError::PackageNotFound(address))?
+                        .predecessor();
+                }
+                None => {
+                    return Err(RuntimeError::NotAnUpgrade(
+                        package_address,
+                        current_package_address,
+                    ))
+                }
+            }
+        }
+
+        Ok(vec![migration_auth])
+    }
+
+    /// Invokes a blueprint or component, rolling back the state updates made by the callee
+    /// if it fails.
+    ///
+    /// The inner result is the outcome of the invocation; the outer one is for errors which
+    /// can't be recovered from, such as running out of cost units.
+    pub fn try_invoke_snode(
+        &mut self,
+        snode_ref: SNodeRef,
+        function: String,
+        args: Vec<ScryptoValue>,
+    ) -> Result<Result<ScryptoValue, RuntimeError>, RuntimeError> {
+        if !matches!(snode_ref, SNodeRef::Scrypto(_)) {
+            return Err(RuntimeError::IllegalTryCall);
+        }
+
+        // Keep a copy of the moving buckets, to give them back on failure
+        let mut bucket_snapshots = HashMap::new();
+        for arg in &args {
+            if !arg.proof_ids.is_empty() {
+                return Err(RuntimeError::ProofNotAllowed);
+            }
+            for bucket_id in arg.bucket_ids.keys() {
+                let bucket = self
+                    .buckets
+                    .get(bucket_id)
+                    .ok_or(RuntimeError::BucketNotFound(*bucket_id))?;
+                bucket_snapshots.insert(*bucket_id, bucket.snapshot());
+            }
+        }
+
+        let savepoint = self.track.savepoint();
+        match self.invoke_snode(snode_ref, function, args) {
+            Ok(result) => {
+                self.track.release_savepoint(savepoint);
+                Ok(Ok(result))
+            }
+            Err(error @ RuntimeError::CostingError(_))
+            | Err(error @ RuntimeError::WasmGasExhausted(_)) => {
+                self.track.release_savepoint(savepoint);
+                Err(error)
+            }
+            Err(error) => {
+                re_debug!(self, "Rolling back failed call: {:?}", error);
+                self.track.rollback(savepoint)?;
+                for (bucket_id, container) in bucket_snapshots {
+                    if !self.buckets.contains_key(&bucket_id) {
+                        self.buckets.insert(bucket_id, Bucket::new(container));
+                    }
+                }
+                Ok(Err(error))
+            }
+        }
+    }
+
     /// Calls the ABI generator of a blueprint.
     // TODO: Remove
     pub fn call_abi(
@@ -847,14 +1310,16 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
 
     /// Send a byte array to wasm instance.
     fn send_bytes(&mut self, bytes: &[u8]) -> Result<i32, RuntimeError> {
-        let wasm_process = self.wasm_process_state.as_ref().unwrap();
-        let result = wasm_process.vm.module.invoke_export(
+        // Instrumented code calls back into the engine for gas, so the process is the externals
+        let module = self.wasm_process_state.as_ref().unwrap().vm.module.clone();
+        let result = module.invoke_export(
             "scrypto_alloc",
             &[RuntimeValue::I32((bytes.len()) as i32)],
-            &mut NopExternals,
+            self,
         );
 
         if let Ok(Some(RuntimeValue::I32(ptr))) = result {
+            let wasm_process = self.wasm_process_state.as_ref().unwrap();
             if wasm_process.vm.memory.set((ptr + 4) as u32, bytes).is_ok() {
                 return Ok(ptr);
             }
@@ -893,11 +1358,20 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         args: RuntimeArgs,
         handler: fn(&mut Self, input: I) -> Result<O, RuntimeError>,
     ) -> Result<Option<RuntimeValue>, Trap> {
-        let wasm_process = self.wasm_process_state.as_mut().unwrap();
         let op: u32 = args.nth_checked(0)?;
         let input_ptr: u32 = args.nth_checked(1)?;
         let input_len: u32 = args.nth_checked(2)?;
         // SECURITY: bill before allocating memory
+        let fee_table = self.track.fee_table();
+        let engine_call_cost = fee_table.wasm_engine_call().saturating_add(
+            fee_table
+                .wasm_engine_call_per_byte()
+                .saturating_mul(input_len),
+        );
+        self.track
+            .consume_cost_units(engine_call_cost, "wasm_engine_call")
+            .map_err(Trap::from)?;
+        let wasm_process = self.wasm_process_state.as_mut().unwrap();
         let mut input_bytes = vec![0u8; input_len as usize];
         wasm_process
             .vm
@@ -929,6 +1403,19 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(Some(RuntimeValue::I32(output_ptr)))
     }
 
+    /// Handles a gas charge from instrumented code.
+    fn handle_gas(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>, Trap> {
+        let gas: u32 = args.nth_checked(0)?;
+        let cost_units = self.track.fee_table().wasm_gas().saturating_mul(gas);
+        self.track
+            .consume_cost_units(cost_units, "wasm_gas")
+            .map_err(|e| match e {
+                RuntimeError::CostingError(e) => RuntimeError::WasmGasExhausted(e),
+                e => e,
+            })?;
+        Ok(None)
+    }
+
     //============================
     // SYSTEM CALL HANDLERS START
     //============================
@@ -950,7 +1437,10 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             input.blueprint_name,
             input.access_rules_list,
             input.state,
-        );
+        )
+        .with_royalty_config(input.royalty_config)
+        .with_metadata(input.metadata, input.metadata_update_rule)
+        .with_migration_rule(input.migration_rule);
         let component_address = self.track.create_component(component);
         self.track
             .insert_objects_into_component(new_objects, component_address);
@@ -1024,6 +1514,29 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(PutComponentStateOutput {})
     }
 
+    fn handle_claim_component_royalty(
+        &mut self,
+        input: ClaimComponentRoyaltyInput,
+    ) -> Result<ClaimComponentRoyaltyOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_mut()
+            .ok_or(RuntimeError::IllegalSystemCall)?;
+        let royalty = match &mut wasm_process.interpreter_state {
+            InterpreterState::Component {
+                component_address,
+                component,
+                ..
+            } if *component_address == input.component_address => component
+                .take_royalty()
+                .map_err(RuntimeError::VaultError),
+            _ => Err(RuntimeError::RoyaltyNotClaimable(input.component_address)),
+        }?;
+
+        let bucket_id = self.create_bucket(royalty)?;
+        Ok(ClaimComponentRoyaltyOutput { bucket_id })
+    }
+
     fn handle_create_lazy_map(
         &mut self,
         _input: CreateLazyMapInput,
@@ -1151,6 +1664,142 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(PutLazyMapEntryOutput {})
     }
 
+    fn handle_remove_lazy_map_entry(
+        &mut self,
+        input: RemoveLazyMapEntryInput,
+    ) -> Result<RemoveLazyMapEntryOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_ref()
+            .ok_or(RuntimeError::IllegalSystemCall)?;
+        let (old_value, lazy_map_state) = match self
+            .owned_snodes
+            .get_lazy_map_entry(&input.lazy_map_id, &input.key)
+        {
+            None => match &wasm_process.interpreter_state {
+                InterpreterState::Component {
+                    component_address,
+                    ..
+                } => {
+                    if !self.snode_refs
+                            .lazy_map_ids
+                            .contains(&input.lazy_map_id)
+                    {
+                        return Err(RuntimeError::LazyMapNotFound(input.lazy_map_id));
+                    }
+                    let old_value = self.track.get_lazy_map_entry(
+                        *component_address,
+                        &input.lazy_map_id,
+                        &input.key,
+                    );
+                    Ok((
+                        old_value,
+                        Committed {
+                            component_address: *component_address,
+                        },
+                    ))
+                }
+                _ => Err(RuntimeError::LazyMapNotFound(input.lazy_map_id)),
+            },
+            Some((root, value)) => Ok((value, Uncommitted { root })),
+        }?;
+
+        let old_value = match old_value {
+            None => return Ok(RemoveLazyMapEntryOutput { value: None }),
+            Some(v) => v,
+        };
+
+        // Vaults and lazy maps can't be dropped along with the entry
+        let old_entry_object_refs = Self::process_entry_data(&old_value).unwrap();
+        ComponentObjectRefs::new().remove(&old_entry_object_refs)?;
+
+        match lazy_map_state {
+            Uncommitted { .. } => {
+                self.owned_snodes
+                    .remove_lazy_map_entry(&input.lazy_map_id, &input.key);
+            }
+            Committed { component_address } => {
+                self.track.remove_lazy_map_entry(
+                    component_address,
+                    input.lazy_map_id,
+                    input.key,
+                );
+            }
+        }
+
+        Ok(RemoveLazyMapEntryOutput {
+            value: Some(old_value),
+        })
+    }
+
+    fn handle_get_lazy_map_entries(
+        &mut self,
+        input: GetLazyMapEntriesInput,
+    ) -> Result<GetLazyMapEntriesOutput, RuntimeError> {
+        let entries: Vec<(Vec<u8>, Vec<u8>)> =
+            match self.owned_snodes.get_lazy_map_entries(&input.lazy_map_id) {
+                Some(entries) => entries
+                    .into_iter()
+                    .filter(|(key, _)| match &input.start_after {
+                        Some(start_after) => key > start_after,
+                        None => true,
+                    })
+                    .take(input.limit as usize)
+                    .collect(),
+                None => {
+                    let component_address = self.get_lazy_map_component(&input.lazy_map_id)?;
+                    self.track.get_lazy_map_entries(
+                        component_address,
+                        &input.lazy_map_id,
+                        input.start_after,
+                        input.limit as usize,
+                    )
+                }
+            };
+
+        for (_, value) in &entries {
+            let map_entry_objects = Self::process_entry_data(value).unwrap();
+            self.snode_refs.extend(map_entry_objects);
+        }
+
+        Ok(GetLazyMapEntriesOutput { entries })
+    }
+
+    fn handle_get_lazy_map_len(
+        &mut self,
+        input: GetLazyMapLenInput,
+    ) -> Result<GetLazyMapLenOutput, RuntimeError> {
+        let len = match self.owned_snodes.get_lazy_map_entries(&input.lazy_map_id) {
+            Some(entries) => entries.len() as u32,
+            None => {
+                let component_address = self.get_lazy_map_component(&input.lazy_map_id)?;
+                self.track
+                    .get_lazy_map_len(component_address, &input.lazy_map_id)
+            }
+        };
+        Ok(GetLazyMapLenOutput { len })
+    }
+
+    /// Returns the component which stores a committed lazy map accessible by this process.
+    fn get_lazy_map_component(
+        &self,
+        lazy_map_id: &LazyMapId,
+    ) -> Result<ComponentAddress, RuntimeError> {
+        if !self.snode_refs.lazy_map_ids.contains(lazy_map_id) {
+            return Err(RuntimeError::LazyMapNotFound(*lazy_map_id));
+        }
+
+        match &self.wasm_process_state {
+            Some(WasmProcess {
+                interpreter_state: InterpreterState::Component {
+                    component_address, ..
+                },
+                ..
+            }) => Ok(*component_address),
+            _ => Err(RuntimeError::LazyMapNotFound(*lazy_map_id)),
+        }
+    }
+
     fn handle_create_vault(
         &mut self,
         input: CreateEmptyVaultInput,
@@ -1190,12 +1839,55 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(InvokeSNodeOutput { rtn: result.raw })
     }
 
+    fn handle_try_invoke_snode(
+        &mut self,
+        input: TryInvokeSNodeInput,
+    ) -> Result<TryInvokeSNodeOutput, RuntimeError> {
+        let mut validated_args = Vec::new();
+        for arg in input.args {
+            validated_args.push(
+                ScryptoValue::from_slice(&arg).map_err(RuntimeError::ParseScryptoValueError)?,
+            );
+        }
+
+        let result = self.try_invoke_snode(input.snode_ref, input.function, validated_args)?;
+        Ok(TryInvokeSNodeOutput {
+            rtn: result.map(|rtn| rtn.raw).map_err(|error| error.to_string()),
+        })
+    }
+
     fn handle_emit_log(&mut self, input: EmitLogInput) -> Result<EmitLogOutput, RuntimeError> {
         self.track.add_log(input.level, input.message);
 
         Ok(EmitLogOutput {})
     }
 
+    fn handle_emit_event(
+        &mut self,
+        input: EmitEventInput,
+    ) -> Result<EmitEventOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_ref()
+            .ok_or(RuntimeError::InterpreterNotStarted)?;
+        let data =
+            ScryptoValue::from_slice(&input.data).map_err(RuntimeError::ParseScryptoValueError)?;
+        if !data.bucket_ids.is_empty() {
+            return Err(RuntimeError::BucketNotAllowed);
+        }
+        if !data.proof_ids.is_empty() {
+            return Err(RuntimeError::ProofNotAllowed);
+        }
+
+        self.track.add_event(Event {
+            actor: wasm_process.vm.actor.clone(),
+            name: input.name,
+            data,
+        });
+
+        Ok(EmitEventOutput {})
+    }
+
     fn handle_get_call_data(
         &mut self,
         _input: GetCallDataInput,
@@ -1234,6 +1926,15 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         })
     }
 
+    fn handle_get_current_time(
+        &mut self,
+        _input: GetCurrentTimeInput,
+    ) -> Result<GetCurrentTimeOutput, RuntimeError> {
+        Ok(GetCurrentTimeOutput {
+            current_time: self.track.current_time(),
+        })
+    }
+
     fn handle_generate_uuid(
         &mut self,
         _input: GenerateUuidInput,
@@ -1262,7 +1963,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         let mut simulated_auth_zone = AuthZone::new_with_proofs(proofs);
 
         let method_authorization = convert(&Type::Unit, &Value::Unit, &input.access_rule);
-        let is_authorized = method_authorization.check(&[&simulated_auth_zone]).is_ok();
+        let is_authorized = method_authorization
+            .check(&[&simulated_auth_zone], self.track.clock())
+            .is_ok();
         simulated_auth_zone.main("clear", Vec::new(), self).map_err(RuntimeError::AuthZoneError)?;
 
         return Ok(CheckAccessRuleOutput{
@@ -1348,9 +2051,74 @@ impl<'r, 'l, L: SubstateStore> SystemApi for Process<'r, 'l, L> {
         self.track.create_resource_manager(resource_manager)
     }
 
+    fn create_vault(&mut self, container: ResourceContainer) -> VaultId {
+        let vault_id = self.track.new_vault_id();
+        self.owned_snodes.vaults.insert(vault_id, Vault::new(container));
+        vault_id
+    }
+
+    fn get_epoch(&mut self) -> u64 {
+        self.track.current_epoch()
+    }
+
+    fn create_component(&mut self, component: Component) -> Result<ComponentAddress, RuntimeError> {
+        let data = Self::process_entry_data(component.state())?;
+        let new_objects = self.owned_snodes.take(data)?;
+        let component_address = self.track.create_component(component);
+        self.track
+            .insert_objects_into_component(new_objects, component_address);
+        Ok(component_address)
+    }
+
+    fn borrow_global_mut_component(
+        &mut self,
+        component_address: ComponentAddress,
+    ) -> Result<Component, RuntimeError> {
+        self.track.borrow_global_mut_component(component_address)
+    }
+
+    fn return_borrowed_global_component(
+        &mut self,
+        component_address: ComponentAddress,
+        component: Component,
+    ) {
+        self.track
+            .return_borrowed_global_component(component_address, component)
+    }
+
+    fn borrow_global_mut_vault(
+        &mut self,
+        component_address: &ComponentAddress,
+        vault_id: &VaultId,
+    ) -> Vault {
+        self.track.borrow_vault_mut(component_address, vault_id)
+    }
+
+    fn return_borrowed_global_vault(
+        &mut self,
+        component_address: &ComponentAddress,
+        vault_id: &VaultId,
+        vault: Vault,
+    ) {
+        self.track
+            .return_borrowed_vault(component_address, vault_id, vault)
+    }
+
+    fn get_package(&mut self, package_address: &PackageAddress) -> Option<&Package> {
+        self.track.get_package(package_address)
+    }
+
+    fn get_package_mut(&mut self, package_address: &PackageAddress) -> Option<&mut Package> {
+        self.track.get_package_mut(package_address)
+    }
+
     fn create_package(&mut self, package: Package) -> PackageAddress {
         self.track.create_package(package)
     }
+
+    fn lock_fee(&mut self, vault_id: VaultId, fee: ResourceContainer) -> Result<(), RuntimeError> {
+        self.track.lock_fee(vault_id, fee)
+    }
 }
 
 impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
@@ -1367,19 +2135,30 @@ impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
                     GET_COMPONENT_INFO => self.handle(args, Self::handle_get_component_info),
                     GET_COMPONENT_STATE => self.handle(args, Self::handle_get_component_state),
                     PUT_COMPONENT_STATE => self.handle(args, Self::handle_put_component_state),
+                    CLAIM_COMPONENT_ROYALTY => {
+                        self.handle(args, Self::handle_claim_component_royalty)
+                    }
 
                     CREATE_LAZY_MAP => self.handle(args, Self::handle_create_lazy_map),
                     GET_LAZY_MAP_ENTRY => self.handle(args, Self::handle_get_lazy_map_entry),
                     PUT_LAZY_MAP_ENTRY => self.handle(args, Self::handle_put_lazy_map_entry),
+                    REMOVE_LAZY_MAP_ENTRY => {
+                        self.handle(args, Self::handle_remove_lazy_map_entry)
+                    }
+                    GET_LAZY_MAP_ENTRIES => self.handle(args, Self::handle_get_lazy_map_entries),
+                    GET_LAZY_MAP_LEN => self.handle(args, Self::handle_get_lazy_map_len),
 
                     CREATE_EMPTY_VAULT => self.handle(args, Self::handle_create_vault),
 
                     INVOKE_SNODE => self.handle(args, Self::handle_invoke_snode),
+                    TRY_INVOKE_SNODE => self.handle(args, Self::handle_try_invoke_snode),
 
                     EMIT_LOG => self.handle(args, Self::handle_emit_log),
+                    EMIT_EVENT => self.handle(args, Self::handle_emit_event),
                     GET_CALL_DATA => self.handle(args, Self::handle_get_call_data),
                     GET_TRANSACTION_HASH => self.handle(args, Self::handle_get_transaction_hash),
                     GET_CURRENT_EPOCH => self.handle(args, Self::handle_get_current_epoch),
+                    GET_CURRENT_TIME => self.handle(args, Self::handle_get_current_time),
                     GENERATE_UUID => self.handle(args, Self::handle_generate_uuid),
                     GET_ACTOR => self.handle(args, Self::handle_get_actor),
 
@@ -1388,6 +2167,7 @@ impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
                     _ => Err(RuntimeError::InvalidRequestCode(operation).into()),
                 }
             }
+            GAS_FUNCTION_INDEX => self.handle_gas(args),
             _ => Err(RuntimeError::HostFunctionNotFound(index).into()),
         }
     }
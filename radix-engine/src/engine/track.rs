@@ -1,12 +1,15 @@
 use indexmap::IndexMap;
 use scrypto::constants::*;
 use scrypto::engine::types::*;
+use scrypto::resource::DIVISIBILITY_MAXIMUM;
 use scrypto::rust::collections::*;
 use scrypto::rust::string::String;
+use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 
 use crate::engine::*;
 use crate::errors::RuntimeError;
+use crate::fee::*;
 use crate::ledger::*;
 use crate::model::*;
 
@@ -32,11 +35,60 @@ impl CommitReceipt {
     }
 }
 
+/// The receiver of a royalty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoyaltyRecipient {
+    Package(PackageAddress),
+    Component(ComponentAddress),
+}
+
+/// The child key under which the number of entries of a lazy map is stored.
+///
+/// It is encoded as a tuple, so it never shares a prefix with the entries of the map.
+fn lazy_map_len_key(lazy_map_id: &LazyMapId) -> (LazyMapId, ()) {
+    (*lazy_map_id, ())
+}
+
+#[derive(Clone)]
 struct SubstateUpdate<T> {
     prev_id: Option<(Hash, u32)>,
     value: T,
 }
 
+/// The state of an entry before it was changed, which is restored on rollback.
+enum JournalEntry {
+    Package(PackageAddress, Option<SubstateUpdate<Package>>),
+    Component(ComponentAddress, Option<SubstateUpdate<Component>>),
+    BorrowedComponent(ComponentAddress, Option<Option<(Hash, u32)>>),
+    ResourceManager(ResourceAddress, Option<SubstateUpdate<ResourceManager>>),
+    BorrowedResourceManager(ResourceAddress, Option<Option<(Hash, u32)>>),
+    Vault(
+        (ComponentAddress, VaultId),
+        Option<SubstateUpdate<VaultSnapshot>>,
+    ),
+    BorrowedVault((ComponentAddress, VaultId), Option<Option<(Hash, u32)>>),
+    NonFungible(
+        NonFungibleAddress,
+        Option<SubstateUpdate<Option<NonFungible>>>,
+    ),
+    LazyMapEntry(
+        (ComponentAddress, LazyMapId, Vec<u8>),
+        Option<SubstateUpdate<Option<Vec<u8>>>>,
+    ),
+    LazyMapLen((ComponentAddress, LazyMapId), Option<SubstateUpdate<u32>>),
+}
+
+/// Records the current state of an entry in the journal, if a savepoint is open.
+macro_rules! journal {
+    ($track:expr, $map:ident, $variant:ident, $key:expr) => {
+        if $track.open_savepoints > 0 {
+            let key = $key;
+            let prev = $track.$map.get(&key).cloned();
+            $track.journal.push(JournalEntry::$variant(key, prev));
+        }
+    };
+}
+
 /// An abstraction of transaction execution state.
 ///
 /// It acts as the facade of ledger state and keeps track of all temporary state updates,
@@ -47,9 +99,16 @@ struct SubstateUpdate<T> {
 pub struct Track<'s, S: SubstateStore> {
     substate_store: &'s mut S,
     transaction_hash: Hash,
-    transaction_signers: Vec<EcdsaPublicKey>,
+    transaction_signers: Vec<PublicKey>,
     id_allocator: IdAllocator,
     logs: Vec<(Level, String)>,
+    events: Vec<Event>,
+
+    fee_table: FeeTable,
+    cost_unit_counter: CostUnitCounter,
+    cost_unit_price: Decimal,
+    locked_fees: Vec<(ComponentAddress, VaultId, ResourceContainer)>,
+    royalties: Vec<(RoyaltyRecipient, Decimal)>,
 
     packages: IndexMap<PackageAddress, SubstateUpdate<Package>>,
 
@@ -64,27 +123,63 @@ pub struct Track<'s, S: SubstateStore> {
 
     non_fungibles: HashMap<NonFungibleAddress, SubstateUpdate<Option<NonFungible>>>,
 
-    lazy_map_entries: HashMap<(ComponentAddress, LazyMapId, Vec<u8>), SubstateUpdate<Vec<u8>>>,
+    /// Lazy map entries, where `None` marks a removed entry
+    lazy_map_entries:
+        HashMap<(ComponentAddress, LazyMapId, Vec<u8>), SubstateUpdate<Option<Vec<u8>>>>,
+    lazy_map_lens: HashMap<(ComponentAddress, LazyMapId), SubstateUpdate<u32>>,
+
+    /// The number of savepoints which haven't been rolled back or released yet
+    open_savepoints: usize,
+    /// The previous state of the entries changed while a savepoint is open, in change order
+    journal: Vec<JournalEntry>,
+}
+
+/// The state of a track at some point of a transaction, which can be rolled back to.
+///
+/// Cost units consumed, logs and allocated ids are never rolled back.
+pub struct TrackSavepoint {
+    events_len: usize,
+    locked_fees_len: usize,
+    royalties_len: usize,
+    journal_len: usize,
 }
 
 impl<'s, S: SubstateStore> Track<'s, S> {
     pub fn new(
         substate_store: &'s mut S,
         transaction_hash: Hash,
-        transaction_signers: Vec<EcdsaPublicKey>,
+        transaction_signers: Vec<PublicKey>,
+        cost_unit_limit: u32,
+        cost_unit_price: Decimal,
     ) -> Self {
+        let fee_table = FeeTable::new();
+        // Nothing needs to be paid back when cost units are free, so the loan covers the limit.
+        let system_loan = if cost_unit_price.is_zero() {
+            cost_unit_limit
+        } else {
+            fee_table.system_loan()
+        };
         Self {
             substate_store,
             transaction_hash,
             transaction_signers,
             id_allocator: IdAllocator::new(IdSpace::Application),
             logs: Vec::new(),
+            events: Vec::new(),
+            fee_table,
+            cost_unit_counter: CostUnitCounter::new(cost_unit_limit, system_loan),
+            cost_unit_price,
+            locked_fees: Vec::new(),
+            royalties: Vec::new(),
             packages: IndexMap::new(),
             components: IndexMap::new(),
             borrowed_components: HashMap::new(),
             resource_managers: IndexMap::new(),
             borrowed_resource_managers: HashMap::new(),
             lazy_map_entries: HashMap::new(),
+            lazy_map_lens: HashMap::new(),
+            open_savepoints: 0,
+            journal: Vec::new(),
             vaults: HashMap::new(),
             borrowed_vaults: HashMap::new(),
             non_fungibles: HashMap::new(),
@@ -93,12 +188,15 @@ impl<'s, S: SubstateStore> Track<'s, S> {
 
     /// Start a process.
     pub fn start_process<'r>(&'r mut self, verbose: bool) -> Process<'r, 's, S> {
-        let signers: BTreeSet<NonFungibleId> = self
-            .transaction_signers
-            .clone()
-            .into_iter()
-            .map(|public_key| NonFungibleId::from_bytes(public_key.to_vec()))
-            .collect();
+        let mut ecdsa_signers = BTreeSet::new();
+        let mut ed25519_signers = BTreeSet::new();
+        for public_key in &self.transaction_signers {
+            let id = NonFungibleId::from_bytes(public_key.to_vec());
+            match public_key {
+                PublicKey::EcdsaP256(_) => ecdsa_signers.insert(id),
+                PublicKey::Ed25519(_) => ed25519_signers.insert(id),
+            };
+        }
 
         // With the latest change, proof amount can't be zero, thus a virtual proof is created
         // only if there are signers.
@@ -108,13 +206,27 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         //
         // TODO: possible to update static check to reject them early?
         let mut initial_auth_zone_proofs = Vec::new();
-        if !signers.is_empty() {
+        if !ecdsa_signers.is_empty() {
             // Proofs can't be zero amount
-            let mut ecdsa_bucket =
-                Bucket::new(ResourceContainer::new_non_fungible(ECDSA_TOKEN, signers));
+            let mut ecdsa_bucket = Bucket::new(ResourceContainer::new_non_fungible(
+                ECDSA_TOKEN,
+                NonFungibleIdType::Bytes,
+                ecdsa_signers,
+            ));
             let ecdsa_proof = ecdsa_bucket.create_proof(ECDSA_TOKEN_BUCKET_ID).unwrap();
             initial_auth_zone_proofs.push(ecdsa_proof);
         }
+        if !ed25519_signers.is_empty() {
+            let mut ed25519_bucket = Bucket::new(ResourceContainer::new_non_fungible(
+                ED25519_TOKEN,
+                NonFungibleIdType::Bytes,
+                ed25519_signers,
+            ));
+            let ed25519_proof = ed25519_bucket
+                .create_proof(ED25519_TOKEN_BUCKET_ID)
+                .unwrap();
+            initial_auth_zone_proofs.push(ed25519_proof);
+        }
 
         Process::new(
             0,
@@ -127,6 +239,28 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         )
     }
 
+    /// Start a process of a system routine, such as an epoch change.
+    ///
+    /// The auth zone holds a proof of the system token instead of the signer proofs.
+    pub fn start_system_process<'r>(&'r mut self, verbose: bool) -> Process<'r, 's, S> {
+        let mut system_bucket = Bucket::new(ResourceContainer::new_fungible(
+            SYSTEM_TOKEN,
+            0,
+            Decimal::one(),
+        ));
+        let system_proof = system_bucket.create_proof(SYSTEM_TOKEN_BUCKET_ID).unwrap();
+
+        Process::new(
+            0,
+            verbose,
+            self,
+            Some(AuthZone::new_with_proofs(vec![system_proof])),
+            Some(Worktop::new()),
+            HashMap::new(),
+            HashMap::new(),
+        )
+    }
+
     /// Returns the transaction hash.
     pub fn transaction_hash(&self) -> Hash {
         self.transaction_hash
@@ -137,6 +271,19 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         self.substate_store.get_epoch()
     }
 
+    /// Returns the ledger time, in seconds since the Unix epoch.
+    pub fn current_time(&self) -> u64 {
+        self.substate_store.get_current_time()
+    }
+
+    /// Returns the clock which time-based access rules are checked against.
+    pub fn clock(&self) -> LedgerClock {
+        LedgerClock {
+            epoch: self.current_epoch(),
+            timestamp: self.current_time(),
+        }
+    }
+
     /// Returns the logs collected so far.
     pub fn logs(&self) -> &Vec<(Level, String)> {
         &self.logs
@@ -180,6 +327,389 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         self.logs.push((level, message));
     }
 
+    /// Returns the events emitted so far, in order.
+    pub fn events(&self) -> &Vec<Event> {
+        &self.events
+    }
+
+    /// Adds an event.
+    pub fn add_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Returns the fee table used for metering.
+    pub fn fee_table(&self) -> &FeeTable {
+        &self.fee_table
+    }
+
+    /// Consumes cost units, failing if the limit or the balance is exceeded.
+    pub fn consume_cost_units(&mut self, n: u32, reason: &str) -> Result<(), RuntimeError> {
+        self.cost_unit_counter
+            .consume(n, reason)
+            .map_err(RuntimeError::CostingError)
+    }
+
+    /// Records cost units for substate access.
+    ///
+    /// Substate accessors don't fail on costing; an exceeded limit or balance is reported by
+    /// the next call to `consume_cost_units()` or `check_fee_payment()`.
+    fn charge(&mut self, n: u32, reason: &str) {
+        let _ = self.cost_unit_counter.consume(n, reason);
+    }
+
+    fn charge_read(&mut self) {
+        self.charge(self.fee_table.read_substate(), "read_substate");
+    }
+
+    fn charge_write(&mut self, size: usize) {
+        let n = self.fee_table.write_substate().saturating_add(
+            self.fee_table
+                .write_substate_per_byte()
+                .saturating_mul(size as u32),
+        );
+        self.charge(n, "write_substate");
+    }
+
+    /// Locks XRD taken from a vault for fee payment, crediting the cost units it pays for.
+    ///
+    /// The vault must be a borrowed vault of a global component, so that the fee can be
+    /// charged from committed state should the transaction fail.
+    pub fn lock_fee(&mut self, vault_id: VaultId, fee: ResourceContainer) -> Result<(), RuntimeError> {
+        let component_address = self
+            .borrowed_vaults
+            .keys()
+            .find(|(_, vid)| *vid == vault_id)
+            .map(|(component_address, _)| *component_address)
+            .ok_or(RuntimeError::LockFeeFromLocalVault(vault_id))?;
+
+        if !self.cost_unit_price.is_zero() {
+            let units = (fee.total_amount() / self.cost_unit_price).floor().0 / Decimal::one().0;
+            self.cost_unit_counter
+                .credit(u32::try_from(units).unwrap_or(u32::MAX));
+        }
+        self.locked_fees.push((component_address, vault_id, fee));
+        Ok(())
+    }
+
+    /// Creates a savepoint of the state updates so far.
+    ///
+    /// Until it is rolled back or released, the previous state of every entry changed is
+    /// journaled, so that only what is changed has to be copied.
+    pub fn savepoint(&mut self) -> TrackSavepoint {
+        self.open_savepoints += 1;
+        TrackSavepoint {
+            events_len: self.events.len(),
+            locked_fees_len: self.locked_fees.len(),
+            royalties_len: self.royalties.len(),
+            journal_len: self.journal.len(),
+        }
+    }
+
+    /// Keeps the state updates made since the given savepoint.
+    pub fn release_savepoint(&mut self, _savepoint: TrackSavepoint) {
+        self.open_savepoints -= 1;
+        if self.open_savepoints == 0 {
+            self.journal.clear();
+        }
+    }
+
+    /// Discards the state updates made since the given savepoint.
+    ///
+    /// Vaults are restored in place, so that proofs created before the savepoint remain valid.
+    pub fn rollback(&mut self, savepoint: TrackSavepoint) -> Result<(), RuntimeError> {
+        if self.locked_fees.len() != savepoint.locked_fees_len {
+            return Err(RuntimeError::CantRollbackFeeLock);
+        }
+
+        self.events.truncate(savepoint.events_len);
+        self.royalties.truncate(savepoint.royalties_len);
+
+        // Vaults taken out of the track are kept, so that they can be restored in place
+        let mut removed_vaults = HashMap::new();
+        while self.journal.len() > savepoint.journal_len {
+            match self.journal.pop().unwrap() {
+                JournalEntry::Package(address, prev) => match prev {
+                    Some(update) => {
+                        self.packages.insert(address, update);
+                    }
+                    None => {
+                        self.packages.shift_remove(&address);
+                    }
+                },
+                JournalEntry::Component(address, prev) => match prev {
+                    Some(update) => {
+                        self.components.insert(address, update);
+                    }
+                    None => {
+                        self.components.shift_remove(&address);
+                    }
+                },
+                JournalEntry::BorrowedComponent(address, prev) => match prev {
+                    Some(prev_id) => {
+                        self.borrowed_components.insert(address, prev_id);
+                    }
+                    None => {
+                        self.borrowed_components.remove(&address);
+                    }
+                },
+                JournalEntry::ResourceManager(address, prev) => match prev {
+                    Some(update) => {
+                        self.resource_managers.insert(address, update);
+                    }
+                    None => {
+                        self.resource_managers.shift_remove(&address);
+                    }
+                },
+                JournalEntry::BorrowedResourceManager(address, prev) => match prev {
+                    Some(prev_id) => {
+                        self.borrowed_resource_managers.insert(address, prev_id);
+                    }
+                    None => {
+                        self.borrowed_resource_managers.remove(&address);
+                    }
+                },
+                JournalEntry::Vault(id, prev) => match prev {
+                    Some(SubstateUpdate { prev_id, value }) => {
+                        let vault = match self.vaults.remove(&id) {
+                            Some(update) => Some(update.value),
+                            None => removed_vaults.remove(&id),
+                        };
+                        let vault = match vault {
+                            Some(mut vault) => {
+                                vault.restore(value);
+                                vault
+                            }
+                            None => Vault::from_snapshot(value),
+                        };
+                        self.vaults.insert(
+                            id,
+                            SubstateUpdate {
+                                prev_id,
+                                value: vault,
+                            },
+                        );
+                    }
+                    None => {
+                        if let Some(update) = self.vaults.remove(&id) {
+                            removed_vaults.insert(id, update.value);
+                        }
+                    }
+                },
+                JournalEntry::BorrowedVault(id, prev) => match prev {
+                    Some(prev_id) => {
+                        self.borrowed_vaults.insert(id, prev_id);
+                    }
+                    None => {
+                        self.borrowed_vaults.remove(&id);
+                    }
+                },
+                JournalEntry::NonFungible(address, prev) => match prev {
+                    Some(update) => {
+                        self.non_fungibles.insert(address, update);
+                    }
+                    None => {
+                        self.non_fungibles.remove(&address);
+                    }
+                },
+                JournalEntry::LazyMapEntry(id, prev) => match prev {
+                    Some(update) => {
+                        self.lazy_map_entries.insert(id, update);
+                    }
+                    None => {
+                        self.lazy_map_entries.remove(&id);
+                    }
+                },
+                JournalEntry::LazyMapLen(id, prev) => match prev {
+                    Some(update) => {
+                        self.lazy_map_lens.insert(id, update);
+                    }
+                    None => {
+                        self.lazy_map_lens.remove(&id);
+                    }
+                },
+            }
+        }
+
+        self.release_savepoint(savepoint);
+        Ok(())
+    }
+
+    /// Records the current state of a vault in the journal, if a savepoint is open.
+    fn journal_vault(&mut self, id: (ComponentAddress, VaultId)) {
+        if self.open_savepoints > 0 {
+            let prev = self.vaults.get(&id).map(|update| SubstateUpdate {
+                prev_id: update.prev_id,
+                value: update.value.snapshot(),
+            });
+            self.journal.push(JournalEntry::Vault(id, prev));
+        }
+    }
+
+    /// Returns the vaults and amounts fees have been locked from so far.
+    pub fn locked_fees(&self) -> Vec<(ComponentAddress, VaultId, Decimal)> {
+        self.locked_fees
+            .iter()
+            .map(|(component_address, vault_id, fee)| {
+                (*component_address, *vault_id, fee.total_amount())
+            })
+            .collect()
+    }
+
+    /// Charges a royalty, which is paid out of the locked fees when the transaction
+    /// succeeds.
+    pub fn charge_royalty(&mut self, recipient: RoyaltyRecipient, amount: Decimal) {
+        if amount.is_positive() {
+            self.royalties.push((recipient, amount));
+        }
+    }
+
+    /// Returns the royalties charged so far.
+    pub fn royalty_due(&self) -> Decimal {
+        self.royalties.iter().map(|(_, amount)| *amount).sum()
+    }
+
+    /// Checks that the cost units consumed are within the limit and paid for by locked fees,
+    /// and that the locked fees also cover the royalties charged.
+    pub fn check_fee_payment(&self) -> Result<(), RuntimeError> {
+        self.cost_unit_counter
+            .check()
+            .map_err(RuntimeError::CostingError)?;
+        if !self.cost_unit_price.is_zero() {
+            self.cost_unit_counter
+                .check_loan_repaid()
+                .map_err(RuntimeError::CostingError)?;
+        }
+        let royalty_due = self.royalty_due();
+        if royalty_due.is_positive() {
+            let locked: Decimal = self
+                .locked_fees
+                .iter()
+                .map(|(_, _, fee)| fee.total_amount())
+                .sum();
+            if locked < self.fee_due() + royalty_due {
+                return Err(RuntimeError::RoyaltyNotPaid(royalty_due));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the fee due for the cost units consumed so far.
+    pub fn fee_due(&self) -> Decimal {
+        self.cost_unit_price * self.cost_unit_counter.consumed()
+    }
+
+    /// Pays the royalties and burns the fee due out of the locked fees, and refunds the rest
+    /// to the vaults they were locked from.
+    pub fn settle_fees(&mut self) -> FeeSummary {
+        let royalty_due = self.royalty_due();
+        let fee_due = self.fee_due();
+        let mut royalty_remaining = royalty_due;
+        let mut royalty =
+            ResourceContainer::new_fungible(RADIX_TOKEN, DIVISIBILITY_MAXIMUM, Decimal::zero());
+        let mut remaining = fee_due;
+        let mut locked = Decimal::zero();
+        for (component_address, vault_id, mut fee) in self.locked_fees.drain(..).collect::<Vec<_>>() {
+            let amount = fee.total_amount();
+            locked += amount;
+            // Royalties are paid first, which `check_fee_payment()` guarantees to be covered
+            let royalty_charge = if amount < royalty_remaining { amount } else { royalty_remaining };
+            royalty.put(fee.take_by_amount(royalty_charge).unwrap()).unwrap();
+            royalty_remaining -= royalty_charge;
+
+            let amount = fee.total_amount();
+            let charge = if amount < remaining { amount } else { remaining };
+            fee.take_by_amount(charge).unwrap();
+            remaining -= charge;
+
+            if !fee.is_empty() {
+                let entry = self
+                    .vaults
+                    .get_mut(&(component_address, vault_id))
+                    .expect("Fee vault should have been returned");
+                entry.value.put(Bucket::new(fee)).unwrap();
+            }
+        }
+        for (recipient, amount) in self.royalties.drain(..).collect::<Vec<_>>() {
+            self.add_royalty(recipient, royalty.take_by_amount(amount).unwrap());
+        }
+        let burned = fee_due - remaining;
+        let mut fee_summary = self.fee_summary(locked, burned);
+        fee_summary.royalties = royalty_due;
+        self.burn_fee(burned);
+        fee_summary
+    }
+
+    fn add_royalty(&mut self, recipient: RoyaltyRecipient, royalty: ResourceContainer) {
+        let result = match recipient {
+            RoyaltyRecipient::Package(package_address) => self
+                .get_package_mut(&package_address)
+                .expect("Royalty package should exist")
+                .add_royalty(royalty),
+            RoyaltyRecipient::Component(component_address) => self
+                .get_component_mut(component_address)
+                .expect("Royalty component should exist")
+                .add_royalty(royalty),
+        };
+        result.expect("Royalties should be XRD");
+    }
+
+    /// Charges fees against committed vault state, for a transaction whose own state updates
+    /// have been discarded.
+    pub fn charge_committed_fees(
+        &mut self,
+        locked_fees: &[(ComponentAddress, VaultId, Decimal)],
+        fee_due: Decimal,
+    ) -> Decimal {
+        let mut remaining = fee_due;
+        for (component_address, vault_id, amount) in locked_fees {
+            if !remaining.is_positive() {
+                break;
+            }
+            let existing: Option<(Vault, (Hash, u32))> = self
+                .substate_store
+                .get_decoded_child_substate(component_address, vault_id);
+            if existing.is_none() {
+                continue;
+            }
+
+            let mut vault = self.borrow_vault_mut(component_address, vault_id);
+            let mut charge = if *amount < remaining { *amount } else { remaining };
+            if vault.total_amount() < charge {
+                charge = vault.total_amount();
+            }
+            if vault.take(charge).is_ok() {
+                remaining -= charge;
+            }
+            self.return_borrowed_vault(component_address, vault_id, vault);
+        }
+        let burned = fee_due - remaining;
+        self.burn_fee(burned);
+        burned
+    }
+
+    fn burn_fee(&mut self, amount: Decimal) {
+        if amount.is_positive() {
+            let mut radix_token = self
+                .borrow_global_mut_resource_manager(RADIX_TOKEN)
+                .expect("XRD resource manager should exist");
+            radix_token.burn(amount);
+            self.return_borrowed_global_resource_manager(RADIX_TOKEN, radix_token);
+        }
+    }
+
+    /// Returns the fee summary, given the XRD locked and burned.
+    pub fn fee_summary(&self, locked: Decimal, burned: Decimal) -> FeeSummary {
+        FeeSummary {
+            cost_unit_limit: self.cost_unit_counter.limit(),
+            cost_unit_consumed: self.cost_unit_counter.consumed(),
+            cost_unit_price: self.cost_unit_price,
+            cost_unit_breakdown: self.cost_unit_counter.breakdown().clone(),
+            locked,
+            burned,
+            royalties: Decimal::zero(),
+        }
+    }
+
     /// Returns an immutable reference to a package, if exists.
     pub fn get_package(&mut self, package_address: &PackageAddress) -> Option<&Package> {
         if self.packages.contains_key(package_address) {
@@ -188,6 +718,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
 
         if let Some((package, phys_id)) = self.substate_store.get_decoded_substate(package_address)
         {
+            self.charge_read();
             self.packages.insert(
                 package_address.clone(),
                 SubstateUpdate {
@@ -201,9 +732,20 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
     }
 
+    /// Returns a mutable reference to a package, if exists.
+    pub fn get_package_mut(&mut self, package_address: &PackageAddress) -> Option<&mut Package> {
+        if self.get_package(package_address).is_none() {
+            return None;
+        }
+        journal!(self, packages, Package, *package_address);
+        self.packages.get_mut(package_address).map(|p| &mut p.value)
+    }
+
     /// Inserts a new package.
     pub fn create_package(&mut self, package: Package) -> PackageAddress {
+        self.charge_write(package.code().len());
         let package_address = self.new_package_address();
+        journal!(self, packages, Package, package_address);
         self.packages.insert(
             package_address,
             SubstateUpdate {
@@ -218,6 +760,13 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         component_address: ComponentAddress,
     ) -> Result<Component, RuntimeError> {
+        journal!(self, components, Component, component_address);
+        journal!(
+            self,
+            borrowed_components,
+            BorrowedComponent,
+            component_address
+        );
         let maybe_component = self.components.remove(&component_address);
         if let Some(SubstateUpdate { value, prev_id }) = maybe_component {
             self.borrowed_components.insert(component_address, prev_id);
@@ -227,6 +776,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         } else if let Some((component, phys_id)) =
             self.substate_store.get_decoded_substate(&component_address)
         {
+            self.charge_read();
             self.borrowed_components
                 .insert(component_address, Some(phys_id));
             Ok(component)
@@ -240,7 +790,15 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         component_address: ComponentAddress,
         component: Component,
     ) {
+        journal!(self, components, Component, component_address);
+        journal!(
+            self,
+            borrowed_components,
+            BorrowedComponent,
+            component_address
+        );
         if let Some(prev_id) = self.borrowed_components.remove(&component_address) {
+            self.charge_write(component.state().len());
             self.components.insert(
                 component_address,
                 SubstateUpdate {
@@ -262,6 +820,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         if let Some((component, phys_id)) =
             self.substate_store.get_decoded_substate(&component_address)
         {
+            self.charge_read();
             self.components.insert(
                 component_address,
                 SubstateUpdate {
@@ -275,9 +834,44 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
     }
 
+    /// Returns a component which is not borrowed.
+    ///
+    /// A borrowed component is not loaded again, so that reentrancy is still detected.
+    pub fn get_unborrowed_component(
+        &mut self,
+        component_address: ComponentAddress,
+    ) -> Result<&Component, RuntimeError> {
+        if self.borrowed_components.contains_key(&component_address) {
+            return Err(RuntimeError::ComponentReentrancy(component_address));
+        }
+        self.get_component(component_address)
+            .ok_or(RuntimeError::ComponentNotFound(component_address))
+    }
+
+    /// Returns a mutable reference to a component, if exists.
+    pub fn get_component_mut(
+        &mut self,
+        component_address: ComponentAddress,
+    ) -> Option<&mut Component> {
+        if self.get_component(component_address).is_none() {
+            return None;
+        }
+        journal!(self, components, Component, component_address);
+        self.components
+            .get_mut(&component_address)
+            .map(|c| &mut c.value)
+    }
+
     /// Inserts a new component.
     pub fn create_component(&mut self, component: Component) -> ComponentAddress {
-        let component_address = self.new_component_address();
+        self.charge_write(component.state().len());
+        let package_address = component.package_address();
+        let component_address = if is_native_package(&package_address) {
+            self.new_native_component_address(package_address)
+        } else {
+            self.new_component_address()
+        };
+        journal!(self, components, Component, component_address);
         self.components.insert(
             component_address,
             SubstateUpdate {
@@ -305,6 +899,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             &non_fungible_address.resource_address(),
             &non_fungible_address.non_fungible_id(),
         ) {
+            self.charge_read();
             self.non_fungibles.insert(
                 non_fungible_address.clone(),
                 SubstateUpdate {
@@ -334,6 +929,13 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             );
         let prev_id = cur.map(|(_, cur_id)| cur_id);
 
+        self.charge_write(0);
+        journal!(
+            self,
+            non_fungibles,
+            NonFungible,
+            non_fungible_address.clone()
+        );
         self.non_fungibles.insert(
             non_fungible_address,
             SubstateUpdate {
@@ -351,13 +953,8 @@ impl<'s, S: SubstateStore> Track<'s, S> {
     ) -> Option<Vec<u8>> {
         let canonical_id = (component_address.clone(), lazy_map_id.clone(), key.to_vec());
 
-        if self.lazy_map_entries.contains_key(&canonical_id) {
-            return Some(
-                self.lazy_map_entries
-                    .get(&canonical_id)
-                    .map(|r| r.value.clone())
-                    .unwrap(),
-            );
+        if let Some(entry) = self.lazy_map_entries.get(&canonical_id) {
+            return entry.value.clone();
         }
 
         let grand_child_key = key.to_vec();
@@ -366,16 +963,167 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             lazy_map_id,
             &grand_child_key,
         );
-        if let Some((ref entry_bytes, phys_id)) = value {
+        if let Some((entry_bytes, phys_id)) = value {
+            self.charge_read();
             self.lazy_map_entries.insert(
                 canonical_id,
                 SubstateUpdate {
                     prev_id: Some(phys_id),
-                    value: entry_bytes.clone(),
+                    value: Some(entry_bytes.clone()),
                 },
             );
+            return Some(entry_bytes);
+        }
+        None
+    }
+
+    /// Returns up to `limit` entries of a lazy map whose key comes after `start_after`,
+    /// ordered by key.
+    ///
+    /// The ledger is read from the cursor on, and only the entries returned are charged for.
+    pub fn get_lazy_map_entries(
+        &mut self,
+        component_address: ComponentAddress,
+        lazy_map_id: &LazyMapId,
+        start_after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, Vec<u8>)> {
+        // Uncommitted changes, which take precedence over the ledger
+        let overlay: BTreeMap<Vec<u8>, Option<Vec<u8>>> = self
+            .lazy_map_entries
+            .iter()
+            .filter(|((address, id, _), _)| *address == component_address && id == lazy_map_id)
+            .map(|((_, _, key), entry)| (key.clone(), entry.value.clone()))
+            .collect();
+
+        let mut entries = Vec::new();
+        let mut cursor = start_after;
+        while entries.len() < limit {
+            let wanted = limit - entries.len();
+            let page = self.substate_store.get_grand_child_substates(
+                &component_address,
+                lazy_map_id,
+                cursor.as_deref(),
+                wanted,
+            );
+            // The last key covered by this page, or `None` once the ledger is exhausted
+            let end = if page.len() < wanted {
+                None
+            } else {
+                page.last().map(|(key, _)| key.clone())
+            };
+
+            let mut candidates = BTreeMap::new();
+            for (key, substate) in page {
+                if !overlay.contains_key(&key) {
+                    candidates.insert(key, substate.value);
+                }
+            }
+            for (key, value) in &overlay {
+                let after_cursor = cursor.as_ref().map_or(true, |cursor| key > cursor);
+                let before_end = end.as_ref().map_or(true, |end| key <= end);
+                if let Some(value) = value.as_ref().filter(|_| after_cursor && before_end) {
+                    candidates.insert(key.clone(), value.clone());
+                }
+            }
+
+            for entry in candidates.into_iter().take(wanted) {
+                self.charge_read();
+                entries.push(entry);
+            }
+
+            match end {
+                Some(end) => cursor = Some(end),
+                None => break,
+            }
+        }
+
+        entries
+    }
+
+    /// Returns the number of entries of a lazy map, which is stored alongside the map.
+    pub fn get_lazy_map_len(
+        &mut self,
+        component_address: ComponentAddress,
+        lazy_map_id: &LazyMapId,
+    ) -> u32 {
+        let canonical_id = (component_address, *lazy_map_id);
+        if let Some(len) = self.lazy_map_lens.get(&canonical_id) {
+            return len.value;
+        }
+
+        self.charge_read();
+        let update = match self
+            .substate_store
+            .get_decoded_child_substate(&component_address, &lazy_map_len_key(lazy_map_id))
+        {
+            Some((len, phys_id)) => SubstateUpdate {
+                prev_id: Some(phys_id),
+                value: len,
+            },
+            None => {
+                // Maps written before lengths were stored are counted once
+                let len = self
+                    .substate_store
+                    .get_grand_child_substates(&component_address, lazy_map_id, None, usize::MAX)
+                    .len();
+                for _ in 0..len {
+                    self.charge_read();
+                }
+                SubstateUpdate {
+                    prev_id: None,
+                    value: len as u32,
+                }
+            }
+        };
+        let len = update.value;
+        self.lazy_map_lens.insert(canonical_id, update);
+        len
+    }
+
+    fn update_lazy_map_len(
+        &mut self,
+        component_address: ComponentAddress,
+        lazy_map_id: &LazyMapId,
+        added: bool,
+    ) {
+        self.get_lazy_map_len(component_address, lazy_map_id);
+        journal!(
+            self,
+            lazy_map_lens,
+            LazyMapLen,
+            (component_address, *lazy_map_id)
+        );
+        let len = self
+            .lazy_map_lens
+            .get_mut(&(component_address, *lazy_map_id))
+            .unwrap();
+        if added {
+            len.value += 1;
+        } else {
+            len.value -= 1;
         }
-        value.map(|r| r.0)
+    }
+
+    /// Removes a lazy map entry, returning the previous value if any.
+    ///
+    /// The substate of the entry is downed and deleted on commit.
+    pub fn remove_lazy_map_entry(
+        &mut self,
+        component_address: ComponentAddress,
+        lazy_map_id: LazyMapId,
+        key: Vec<u8>,
+    ) -> Option<Vec<u8>> {
+        let old_value = self.get_lazy_map_entry(component_address, &lazy_map_id, &key);
+        if old_value.is_some() {
+            self.charge_write(key.len());
+            let canonical_id = (component_address, lazy_map_id.clone(), key);
+            journal!(self, lazy_map_entries, LazyMapEntry, canonical_id.clone());
+            // The entry was cached by the read above
+            self.lazy_map_entries.get_mut(&canonical_id).unwrap().value = None;
+            self.update_lazy_map_len(component_address, &lazy_map_id, false);
+        }
+        old_value
     }
 
     pub fn put_lazy_map_entry(
@@ -386,6 +1134,8 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         value: Vec<u8>,
     ) {
         let canonical_id = (component_address.clone(), lazy_map_id.clone(), key.clone());
+        self.charge_write(key.len() + value.len());
+        journal!(self, lazy_map_entries, LazyMapEntry, canonical_id.clone());
 
         if !self.lazy_map_entries.contains_key(&canonical_id) {
             let entry = self.substate_store.get_decoded_grand_child_substate(
@@ -398,7 +1148,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                     canonical_id,
                     SubstateUpdate {
                         prev_id: Some(phys_id),
-                        value,
+                        value: Some(value),
                     },
                 );
                 return;
@@ -406,14 +1156,19 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(entry) = self.lazy_map_entries.get_mut(&canonical_id) {
-            entry.value = value;
+            let added = entry.value.is_none();
+            entry.value = Some(value);
+            if added {
+                self.update_lazy_map_len(component_address, &lazy_map_id, true);
+            }
         } else {
+            self.update_lazy_map_len(component_address, &lazy_map_id, true);
             // TODO: Virtual Down
             self.lazy_map_entries.insert(
                 canonical_id,
                 SubstateUpdate {
                     prev_id: None,
-                    value,
+                    value: Some(value),
                 },
             );
         }
@@ -434,6 +1189,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         if let Some((resource_manager, phys_id)) =
             self.substate_store.get_decoded_substate(resource_address)
         {
+            self.charge_read();
             self.resource_managers.insert(
                 resource_address.clone(),
                 SubstateUpdate {
@@ -453,6 +1209,13 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         resource_address: ResourceAddress,
     ) -> Result<ResourceManager, RuntimeError> {
+        journal!(self, resource_managers, ResourceManager, resource_address);
+        journal!(
+            self,
+            borrowed_resource_managers,
+            BorrowedResourceManager,
+            resource_address
+        );
         let maybe_resource = self.resource_managers.remove(&resource_address);
         if self
             .borrowed_resource_managers
@@ -466,6 +1229,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         } else if let Some((resource_manager, phys_id)) =
             self.substate_store.get_decoded_substate(&resource_address)
         {
+            self.charge_read();
             self.borrowed_resource_managers
                 .insert(resource_address, Some(phys_id));
             Ok(resource_manager)
@@ -479,7 +1243,15 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         resource_address: ResourceAddress,
         resource_manager: ResourceManager,
     ) {
+        journal!(self, resource_managers, ResourceManager, resource_address);
+        journal!(
+            self,
+            borrowed_resource_managers,
+            BorrowedResourceManager,
+            resource_address
+        );
         if let Some(prev_id) = self.borrowed_resource_managers.remove(&resource_address) {
+            self.charge_write(0);
             self.resource_managers.insert(
                 resource_address,
                 SubstateUpdate {
@@ -497,7 +1269,9 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         resource_manager: ResourceManager,
     ) -> ResourceAddress {
+        self.charge_write(0);
         let resource_address = self.new_resource_address();
+        journal!(self, resource_managers, ResourceManager, resource_address);
         self.resource_managers.insert(
             resource_address,
             SubstateUpdate {
@@ -508,18 +1282,36 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         resource_address
     }
 
+    /// Returns whether a global component owns the given vault.
+    pub fn vault_exists(&self, component_address: &ComponentAddress, vid: &VaultId) -> bool {
+        let canonical_id = (component_address.clone(), vid.clone());
+        if self.vaults.contains_key(&canonical_id)
+            || self.borrowed_vaults.contains_key(&canonical_id)
+        {
+            return true;
+        }
+
+        let existing: Option<(Vault, (Hash, u32))> = self
+            .substate_store
+            .get_decoded_child_substate(component_address, vid);
+        existing.is_some()
+    }
+
     pub fn borrow_vault_mut(&mut self, component_address: &ComponentAddress, vid: &VaultId) -> Vault {
         let canonical_id = (component_address.clone(), vid.clone());
         if self.borrowed_vaults.contains_key(&canonical_id) {
             panic!("Invalid vault reentrancy");
         }
 
+        self.journal_vault(canonical_id);
+        journal!(self, borrowed_vaults, BorrowedVault, canonical_id);
         if let Some(SubstateUpdate { value, prev_id }) = self.vaults.remove(&canonical_id) {
             self.borrowed_vaults.insert(canonical_id, prev_id);
             return value;
         }
 
         if let Some((vault, phys_id)) = self.substate_store.get_decoded_child_substate(component_address, vid) {
+            self.charge_read();
             self.borrowed_vaults
                 .insert(canonical_id, Some(phys_id));
             return vault;
@@ -535,7 +1327,10 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         vault: Vault,
     ) {
         let canonical_id = (component_address.clone(), vid.clone());
+        self.journal_vault(canonical_id);
+        journal!(self, borrowed_vaults, BorrowedVault, canonical_id);
         if let Some(prev_id) = self.borrowed_vaults.remove(&canonical_id) {
+            self.charge_write(0);
             self.vaults.insert(
                 canonical_id,
                 SubstateUpdate {
@@ -556,6 +1351,8 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         vault: Vault,
     ) {
         let canonical_id = (component_address, vault_id);
+        self.charge_write(0);
+        self.journal_vault(canonical_id);
         self.vaults.insert(
             canonical_id,
             SubstateUpdate {
@@ -584,6 +1381,18 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         component_address
     }
 
+    /// Creates a new address for a component of a native package.
+    fn new_native_component_address(
+        &mut self,
+        package_address: PackageAddress,
+    ) -> ComponentAddress {
+        let component_address = self
+            .id_allocator
+            .new_native_component_address(self.transaction_hash(), package_address)
+            .unwrap();
+        component_address
+    }
+
     /// Creates a new resource address.
     fn new_resource_address(&mut self) -> ResourceAddress {
         let resource_address = self
@@ -622,8 +1431,82 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             .unwrap()
     }
 
+    /// Returns the changes that `commit()` would apply, compared with the committed state.
+    pub fn state_changes(&self) -> StateChanges {
+        let mut state_changes = StateChanges::new();
+
+        for (component_address, component) in &self.components {
+            let before: Option<Component> = component.prev_id.and_then(|_| {
+                self.substate_store
+                    .get_decoded_substate(component_address)
+                    .map(|(c, _)| c)
+            });
+            let before = before.map(|c| c.state().to_vec());
+            let after = component.value.state().to_vec();
+            if before.as_ref() != Some(&after) {
+                state_changes
+                    .component_state_changes
+                    .push(ComponentStateChange {
+                        component_address: *component_address,
+                        before,
+                        after,
+                    });
+            }
+        }
+
+        for (resource_address, resource_manager) in &self.resource_managers {
+            let before: Option<ResourceManager> = resource_manager.prev_id.and_then(|_| {
+                self.substate_store
+                    .get_decoded_substate(resource_address)
+                    .map(|(r, _)| r)
+            });
+            let supply_before = before.map(|r| r.total_supply()).unwrap_or_default();
+            let delta = resource_manager.value.total_supply() - supply_before;
+            if !delta.is_zero() {
+                state_changes.supply_changes.push(SupplyChange {
+                    resource_address: *resource_address,
+                    delta,
+                });
+            }
+        }
+
+        for ((component_address, vault_id), vault) in &self.vaults {
+            let before: Option<Vault> = vault.prev_id.and_then(|_| {
+                self.substate_store
+                    .get_decoded_child_substate(component_address, vault_id)
+                    .map(|(v, _)| v)
+            });
+            if let Some(balance_change) = BalanceChange::between(before.as_ref(), &vault.value) {
+                state_changes.vault_changes.push(VaultChange {
+                    component_address: *component_address,
+                    vault_id: *vault_id,
+                    resource_address: vault.value.resource_address(),
+                    balance_change,
+                });
+            }
+        }
+
+        for ((component_address, lazy_map_id, key), entry) in &self.lazy_map_entries {
+            let before = entry.prev_id.and_then(|_| {
+                self.substate_store
+                    .get_decoded_grand_child_substate(component_address, lazy_map_id, key)
+                    .map(|(value, _)| value)
+            });
+            if before != entry.value {
+                state_changes.lazy_map_entry_changes.push(LazyMapEntryChange {
+                    component_address: *component_address,
+                    lazy_map_id: *lazy_map_id,
+                    key: key.clone(),
+                    value: entry.value.clone(),
+                });
+            }
+        }
+
+        state_changes
+    }
+
     /// Commits changes to the underlying ledger.
-    /// Currently none of these objects are deleted so all commits are puts
+    /// Only removed lazy map entries are deleted; everything else is a put
     pub fn commit(&mut self) -> CommitReceipt {
         // Sanity check
         if !self.borrowed_components.is_empty() {
@@ -692,15 +1575,48 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             if let Some(prev_id) = entry.prev_id {
                 receipt.down(prev_id);
             }
+
+            let (component_address, lazy_map_id, key) = entry_id;
+            match entry.value {
+                Some(value) => {
+                    let phys_id = id_gen.next();
+                    receipt.up(phys_id);
+
+                    self.substate_store.put_encoded_grand_child_substate(
+                        &component_address,
+                        &lazy_map_id,
+                        &key,
+                        &value,
+                        phys_id,
+                    );
+                }
+                None => {
+                    if entry.prev_id.is_some() {
+                        self.substate_store.remove_grand_child_substate(
+                            &component_address,
+                            &lazy_map_id,
+                            &key,
+                        );
+                    }
+                }
+            }
+        }
+
+        let len_ids: Vec<(ComponentAddress, LazyMapId)> =
+            self.lazy_map_lens.keys().cloned().collect();
+        for len_id in len_ids {
+            let len = self.lazy_map_lens.remove(&len_id).unwrap();
+            if let Some(prev_id) = len.prev_id {
+                receipt.down(prev_id);
+            }
             let phys_id = id_gen.next();
             receipt.up(phys_id);
 
-            let (component_address, lazy_map_id, key) = entry_id;
-            self.substate_store.put_encoded_grand_child_substate(
+            let (component_address, lazy_map_id) = len_id;
+            self.substate_store.put_encoded_child_substate(
                 &component_address,
-                &lazy_map_id,
-                &key,
-                &entry.value,
+                &lazy_map_len_key(&lazy_map_id),
+                &len.value,
                 phys_id,
             );
         }
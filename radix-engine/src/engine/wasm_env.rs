@@ -5,6 +5,10 @@ use wasmi::*;
 pub const ENGINE_FUNCTION_INDEX: usize = 0;
 /// Radix Engine entrance function name.
 pub const ENGINE_FUNCTION_NAME: &str = "radix_engine";
+/// Gas metering function index.
+pub const GAS_FUNCTION_INDEX: usize = 1;
+/// Gas metering function name, as injected by instrumentation.
+pub const GAS_FUNCTION_NAME: &str = "gas";
 
 /// An `env` module resolver defines how symbols in `env` are resolved.
 pub struct EnvModuleResolver;
@@ -25,6 +29,17 @@ impl ModuleImportResolver for EnvModuleResolver {
                     ENGINE_FUNCTION_INDEX,
                 ))
             }
+            GAS_FUNCTION_NAME => {
+                if signature.params() != [ValueType::I32] || signature.return_type() != None {
+                    return Err(Error::Instantiation(
+                        "Function signature does not match".into(),
+                    ));
+                }
+                Ok(FuncInstance::alloc_host(
+                    signature.clone(),
+                    GAS_FUNCTION_INDEX,
+                ))
+            }
             _ => Err(Error::Instantiation(format!(
                 "Export {} not found",
                 field_name
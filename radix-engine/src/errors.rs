@@ -7,6 +7,7 @@ use scrypto::values::*;
 use wasmi::*;
 
 use crate::engine::*;
+use crate::fee::*;
 use crate::model::*;
 
 /// Error coming from WASMI module which maps to wasmi:Error but is cloneable
@@ -64,6 +65,10 @@ pub enum WasmValidationError {
     NoPackageInitExport(WasmiError),
     /// package_init function is not the correct interface
     InvalidPackageInit,
+    /// The wasm module can't be instrumented with gas metering.
+    InstructionMeteringFailed,
+    /// The wasm module can't be instrumented with a stack height limit.
+    StackMeteringFailed,
 }
 
 /// Represents an error when validating a transaction.
@@ -74,6 +79,15 @@ pub enum TransactionValidationError {
     VaultNotAllowed(VaultId),
     LazyMapNotAllowed(LazyMapId),
     InvalidSignature,
+    UnsupportedVersion(u8),
+    InvalidNetwork(Network),
+    InvalidEpochRange,
+    TransactionNotYetValid(u64),
+    TransactionExpired(u64),
+    MissingNotarySignature,
+    IntentAlreadyCommitted(Hash),
+    BlobNotFound(Hash),
+    UnreferencedBlob(Hash),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -187,6 +201,21 @@ pub enum RuntimeError {
     /// Resource manager access error.
     ResourceManagerError(ResourceManagerError),
 
+    /// Component native function error.
+    ComponentError(ComponentError),
+
+    /// Pool access error.
+    PoolError(PoolError),
+
+    /// Access controller error.
+    AccessControllerError(AccessControllerError),
+
+    /// Validator error.
+    ValidatorError(ValidatorError),
+
+    /// Epoch manager error.
+    EpochManagerError(EpochManagerError),
+
     /// Bucket access error.
     BucketError(BucketError),
 
@@ -242,6 +271,32 @@ pub enum RuntimeError {
     /// Can't move restricted proof.
     CantMoveRestrictedProof(ProofId),
 
+    /// Error when consuming cost units.
+    CostingError(CostUnitCounterError),
+
+    /// WASM execution ran out of gas.
+    WasmGasExhausted(CostUnitCounterError),
+
+    /// Fees can only be locked from vaults owned by a global component.
+    LockFeeFromLocalVault(VaultId),
+
+    /// A call which locked fees can't be rolled back.
+    CantRollbackFeeLock,
+
+    /// Only blueprints and components can be called with rollback.
+    IllegalTryCall,
+
+    /// The component has not opted into the requested migration.
+    MigrationNotAllowed(ComponentAddress),
+
+    /// The first package is not a later version of the second one.
+    NotAnUpgrade(PackageAddress, PackageAddress),
+
+    /// The locked fees don't cover the royalties charged.
+    RoyaltyNotPaid(Decimal),
+
+    /// The royalties of a component can only be claimed by the component itself.
+    RoyaltyNotClaimable(ComponentAddress),
 }
 
 impl fmt::Display for RuntimeError {
@@ -0,0 +1,167 @@
+use scrypto::rust::collections::BTreeMap;
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
+
+/// Represents an error when consuming cost units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CostUnitCounterError {
+    /// The transaction has consumed more cost units than its limit.
+    LimitExceeded { limit: u32, consumed: u32 },
+    /// The cost units consumed are not covered by the system loan and the fees locked so far.
+    OutOfBalance { balance: u32, consumed: u32 },
+    /// The system loan has not been repaid by the end of the transaction.
+    SystemLoanNotRepaid { credited: u32, consumed: u32 },
+    /// The counter can't hold the cost units consumed.
+    CounterOverflow,
+}
+
+/// Keeps track of the cost units consumed by a transaction.
+///
+/// Execution starts on a system loan; locking fees credits the counter with the cost units
+/// they can pay for, which extends the balance beyond the loan.
+#[derive(Debug, Clone)]
+pub struct CostUnitCounter {
+    /// The maximum number of cost units the transaction can consume
+    limit: u32,
+    /// The cost units lent by the system before any fee is locked
+    system_loan: u32,
+    /// The cost units paid for by locked fees
+    credited: u32,
+    /// The cost units consumed so far
+    consumed: u32,
+    /// The cost units consumed, grouped by reason
+    breakdown: BTreeMap<String, u32>,
+}
+
+impl CostUnitCounter {
+    pub fn new(limit: u32, system_loan: u32) -> Self {
+        Self {
+            limit,
+            system_loan,
+            credited: 0,
+            consumed: 0,
+            breakdown: BTreeMap::new(),
+        }
+    }
+
+    /// Consumes cost units.
+    ///
+    /// Cost units are recorded even when the limit or the balance is exceeded, so that a failed
+    /// consumption keeps failing on any subsequent check.
+    pub fn consume(&mut self, n: u32, reason: &str) -> Result<(), CostUnitCounterError> {
+        self.consumed = self
+            .consumed
+            .checked_add(n)
+            .ok_or(CostUnitCounterError::CounterOverflow)?;
+        *self.breakdown.entry(reason.to_string()).or_default() += n;
+        self.check()
+    }
+
+    /// Credits the cost units paid for by a fee lock.
+    pub fn credit(&mut self, n: u32) {
+        self.credited = self.credited.saturating_add(n);
+    }
+
+    /// Checks that the cost units consumed are within the limit and the balance.
+    pub fn check(&self) -> Result<(), CostUnitCounterError> {
+        if self.consumed > self.limit {
+            Err(CostUnitCounterError::LimitExceeded {
+                limit: self.limit,
+                consumed: self.consumed,
+            })
+        } else if self.consumed > self.balance() {
+            Err(CostUnitCounterError::OutOfBalance {
+                balance: self.balance(),
+                consumed: self.consumed,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks that the cost units consumed are fully paid for by locked fees.
+    pub fn check_loan_repaid(&self) -> Result<(), CostUnitCounterError> {
+        if self.consumed > self.credited {
+            Err(CostUnitCounterError::SystemLoanNotRepaid {
+                credited: self.credited,
+                consumed: self.consumed,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn consumed(&self) -> u32 {
+        self.consumed
+    }
+
+    pub fn balance(&self) -> u32 {
+        self.system_loan.saturating_add(self.credited)
+    }
+
+    pub fn breakdown(&self) -> &BTreeMap<String, u32> {
+        &self.breakdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consume_within_loan() {
+        let mut counter = CostUnitCounter::new(100, 10);
+        assert_eq!(counter.consume(10, "a"), Ok(()));
+        assert_eq!(
+            counter.check_loan_repaid(),
+            Err(CostUnitCounterError::SystemLoanNotRepaid {
+                credited: 0,
+                consumed: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_consume_beyond_balance() {
+        let mut counter = CostUnitCounter::new(100, 10);
+        assert_eq!(
+            counter.consume(11, "a"),
+            Err(CostUnitCounterError::OutOfBalance {
+                balance: 10,
+                consumed: 11
+            })
+        );
+        counter.credit(50);
+        assert_eq!(counter.check(), Ok(()));
+        assert_eq!(counter.check_loan_repaid(), Ok(()));
+    }
+
+    #[test]
+    fn test_consume_beyond_limit() {
+        let mut counter = CostUnitCounter::new(100, 10);
+        counter.credit(1000);
+        assert_eq!(
+            counter.consume(101, "a"),
+            Err(CostUnitCounterError::LimitExceeded {
+                limit: 100,
+                consumed: 101
+            })
+        );
+        assert!(counter.consume(0, "b").is_err());
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let mut counter = CostUnitCounter::new(100, 100);
+        counter.consume(1, "a").unwrap();
+        counter.consume(2, "b").unwrap();
+        counter.consume(3, "a").unwrap();
+        assert_eq!(counter.breakdown().get("a"), Some(&4));
+        assert_eq!(counter.breakdown().get("b"), Some(&2));
+        assert_eq!(counter.consumed(), 6);
+    }
+}
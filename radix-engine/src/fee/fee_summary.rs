@@ -0,0 +1,22 @@
+use scrypto::math::Decimal;
+use scrypto::rust::collections::BTreeMap;
+use scrypto::rust::string::String;
+
+/// Summarizes the cost units consumed by a transaction and the fees paid for them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeSummary {
+    /// The maximum number of cost units the transaction could consume
+    pub cost_unit_limit: u32,
+    /// The cost units consumed
+    pub cost_unit_consumed: u32,
+    /// The price of a cost unit, in XRD, including the tip
+    pub cost_unit_price: Decimal,
+    /// The cost units consumed, grouped by reason
+    pub cost_unit_breakdown: BTreeMap<String, u32>,
+    /// The XRD locked for fee payment
+    pub locked: Decimal,
+    /// The XRD charged and burned
+    pub burned: Decimal,
+    /// The XRD paid in royalties to packages and components
+    pub royalties: Decimal,
+}
@@ -0,0 +1,84 @@
+/// The default maximum number of cost units a transaction can consume.
+pub const DEFAULT_COST_UNIT_LIMIT: u32 = 10_000_000;
+
+/// The costs, in cost units, of the operations metered by the engine.
+#[derive(Debug, Clone)]
+pub struct FeeTable {
+    system_loan: u32,
+    tx_base_fee: u32,
+    invoke_snode: u32,
+    wasm_instantiation_per_byte: u32,
+    wasm_gas: u32,
+    wasm_engine_call: u32,
+    wasm_engine_call_per_byte: u32,
+    read_substate: u32,
+    write_substate: u32,
+    write_substate_per_byte: u32,
+}
+
+impl FeeTable {
+    pub fn new() -> Self {
+        Self {
+            system_loan: 1_000_000,
+            tx_base_fee: 10_000,
+            invoke_snode: 2_000,
+            wasm_instantiation_per_byte: 1,
+            wasm_gas: 1,
+            wasm_engine_call: 500,
+            wasm_engine_call_per_byte: 2,
+            read_substate: 1_000,
+            write_substate: 1_000,
+            write_substate_per_byte: 2,
+        }
+    }
+
+    /// Cost units lent to a transaction before it locks any fee.
+    pub fn system_loan(&self) -> u32 {
+        self.system_loan
+    }
+
+    /// Flat cost of every transaction.
+    pub fn tx_base_fee(&self) -> u32 {
+        self.tx_base_fee
+    }
+
+    /// Cost of invoking a function or method on any SNode.
+    pub fn invoke_snode(&self) -> u32 {
+        self.invoke_snode
+    }
+
+    /// Cost of instantiating a WASM module, per byte of code.
+    pub fn wasm_instantiation_per_byte(&self) -> u32 {
+        self.wasm_instantiation_per_byte
+    }
+
+    /// Cost of a unit of gas reported by instrumented WASM code.
+    pub fn wasm_gas(&self) -> u32 {
+        self.wasm_gas
+    }
+
+    /// Flat cost of a call from WASM into the engine.
+    pub fn wasm_engine_call(&self) -> u32 {
+        self.wasm_engine_call
+    }
+
+    /// Cost of a call from WASM into the engine, per byte of input.
+    pub fn wasm_engine_call_per_byte(&self) -> u32 {
+        self.wasm_engine_call_per_byte
+    }
+
+    /// Cost of loading a substate from the substate store.
+    pub fn read_substate(&self) -> u32 {
+        self.read_substate
+    }
+
+    /// Flat cost of writing a substate.
+    pub fn write_substate(&self) -> u32 {
+        self.write_substate
+    }
+
+    /// Cost of writing a substate, per byte of encoded value where known.
+    pub fn write_substate_per_byte(&self) -> u32 {
+        self.write_substate_per_byte
+    }
+}
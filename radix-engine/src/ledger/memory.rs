@@ -1,6 +1,9 @@
 use sbor::Encode;
 use scrypto::buffer::scrypto_encode;
-use scrypto::rust::collections::HashMap;
+use scrypto::engine::types::*;
+use scrypto::rust::collections::{BTreeMap, HashMap};
+use scrypto::rust::ops::Bound;
+use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 
 use crate::ledger::traits::Substate;
@@ -10,8 +13,12 @@ use crate::ledger::*;
 #[derive(Debug, Clone)]
 pub struct InMemorySubstateStore {
     substates: HashMap<Vec<u8>, Substate>,
-    child_substates: HashMap<Vec<u8>, Substate>,
+    child_substates: BTreeMap<Vec<u8>, Substate>,
     current_epoch: u64,
+    current_time: u64,
+    committed_intents: HashMap<Hash, u64>,
+    /// The committed intents by expiry epoch, so that expired ones are pruned without a scan
+    intents_by_expiry: BTreeMap<u64, Vec<Hash>>,
     nonce: u64,
 }
 
@@ -19,8 +26,11 @@ impl InMemorySubstateStore {
     pub fn new() -> Self {
         Self {
             substates: HashMap::new(),
-            child_substates: HashMap::new(),
+            child_substates: BTreeMap::new(),
             current_epoch: 0,
+            current_time: 0,
+            committed_intents: HashMap::new(),
+            intents_by_expiry: BTreeMap::new(),
             nonce: 0,
         }
     }
@@ -59,12 +69,68 @@ impl SubstateStore for InMemorySubstateStore {
         self.child_substates.insert(id, substate);
     }
 
+    fn remove_child_substate<T: Encode>(&mut self, address: &T, key: &[u8]) {
+        let mut id = scrypto_encode(address);
+        id.extend(key.to_vec());
+        self.child_substates.remove(&id);
+    }
+
+    fn get_child_substates_with_prefix<T: Encode>(
+        &self,
+        address: &T,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, Substate)> {
+        let mut id = scrypto_encode(address);
+        id.extend(prefix.to_vec());
+        let start = match start_after {
+            Some(start_after) => {
+                let mut start = id.clone();
+                start.extend(start_after.to_vec());
+                Bound::Excluded(start)
+            }
+            None => Bound::Included(id.clone()),
+        };
+        self.child_substates
+            .range((start, Bound::Unbounded))
+            .take_while(|(key, _)| key.starts_with(&id))
+            .take(limit)
+            .map(|(key, substate)| (key[id.len()..].to_vec(), substate.clone()))
+            .collect()
+    }
+
     fn get_epoch(&self) -> u64 {
         self.current_epoch
     }
 
     fn set_epoch(&mut self, epoch: u64) {
         self.current_epoch = epoch;
+        let unexpired = self.intents_by_expiry.split_off(&epoch.saturating_add(1));
+        for intent_hash in self.intents_by_expiry.values().flatten() {
+            self.committed_intents.remove(intent_hash);
+        }
+        self.intents_by_expiry = unexpired;
+    }
+
+    fn get_current_time(&self) -> u64 {
+        self.current_time
+    }
+
+    fn set_current_time(&mut self, timestamp: u64) {
+        self.current_time = round_to_clock_precision(timestamp);
+    }
+
+    fn is_intent_committed(&self, intent_hash: &Hash) -> bool {
+        self.committed_intents.contains_key(intent_hash)
+    }
+
+    fn put_committed_intent(&mut self, intent_hash: &Hash, expiry_epoch: u64) {
+        self.committed_intents.insert(*intent_hash, expiry_epoch);
+        self.intents_by_expiry
+            .entry(expiry_epoch)
+            .or_insert(vec![])
+            .push(*intent_hash);
     }
 
     fn get_nonce(&self) -> u64 {
@@ -2,7 +2,9 @@ mod memory;
 mod traits;
 
 pub use memory::InMemorySubstateStore;
+pub use traits::round_to_clock_precision;
 pub use traits::QueryableSubstateStore;
 pub use traits::Substate;
 pub use traits::SubstateIdGenerator;
 pub use traits::SubstateStore;
+pub use traits::CLOCK_PRECISION;
@@ -18,10 +18,20 @@ const XRD_NAME: &str = "Radix";
 const XRD_DESCRIPTION: &str = "The Radix Public Network's native token, used to pay the network's required transaction fees and to secure the network through staking to its validator nodes.";
 const XRD_URL: &str = "https://tokens.radixdlt.com";
 const XRD_MAX_SUPPLY: i128 = 24_000_000_000i128;
+const XRD_EMISSION_PER_EPOCH: i128 = 1_000i128;
 const XRD_VAULT_ID: VaultId = (Hash([0u8; 32]), 0);
 const XRD_VAULT: scrypto::resource::Vault = scrypto::resource::Vault(XRD_VAULT_ID);
 
 const SYSTEM_COMPONENT_NAME: &str = "System";
+const EPOCH_MANAGER_NAME: &str = "EpochManager";
+
+/// The precision of the ledger clock, in seconds.
+pub const CLOCK_PRECISION: u64 = 60;
+
+/// Rounds a timestamp down to the precision of the ledger clock.
+pub fn round_to_clock_precision(timestamp: u64) -> u64 {
+    timestamp - timestamp % CLOCK_PRECISION
+}
 
 #[derive(TypeId, Encode, Decode)]
 struct SystemComponentState {
@@ -67,6 +77,18 @@ pub trait SubstateStore {
 
     fn get_child_substate<T: Encode>(&self, address: &T, key: &[u8]) -> Option<Substate>;
     fn put_child_substate<T: Encode>(&mut self, address: &T, key: &[u8], substate: Substate);
+    fn remove_child_substate<T: Encode>(&mut self, address: &T, key: &[u8]);
+
+    /// Returns up to `limit` child substates whose key starts with the given prefix and comes
+    /// after `start_after` once stripped of it, ordered by key.
+    /// The returned keys have the prefix stripped.
+    fn get_child_substates_with_prefix<T: Encode>(
+        &self,
+        address: &T,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, Substate)>;
 
     // Temporary Encoded/Decoded interface
     fn get_decoded_substate<A: Encode, T: Decode>(&self, address: &A) -> Option<(T, (Hash, u32))> {
@@ -124,6 +146,20 @@ pub trait SubstateStore {
         self.get_child_substate(address, &key)
             .map(|s| (s.value, s.phys_id))
     }
+    fn get_grand_child_substates<A: Encode, C: Encode>(
+        &self,
+        address: &A,
+        child_key: &C,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, Substate)> {
+        self.get_child_substates_with_prefix(
+            address,
+            &scrypto_encode(child_key),
+            start_after,
+            limit,
+        )
+    }
     fn put_encoded_grand_child_substate<A: Encode, C: Encode>(
         &mut self,
         address: &A,
@@ -143,6 +179,16 @@ pub trait SubstateStore {
             },
         );
     }
+    fn remove_grand_child_substate<A: Encode, C: Encode>(
+        &mut self,
+        address: &A,
+        child_key: &C,
+        grand_child_key: &[u8],
+    ) {
+        let mut key = scrypto_encode(child_key);
+        key.extend(grand_child_key.to_vec());
+        self.remove_child_substate(address, &key);
+    }
 
     fn bootstrap(&mut self) {
         let package: Option<Package> = self
@@ -186,13 +232,35 @@ pub trait SubstateStore {
             let mut ecdsa_resource_auth = HashMap::new();
             ecdsa_resource_auth.insert(Withdraw, (rule!(allow_all), LOCKED));
             let ecdsa_token = ResourceManager::new(
-                ResourceType::NonFungible,
+                ResourceType::NonFungible {
+                    id_type: NonFungibleIdType::Bytes,
+                },
                 HashMap::new(),
                 ecdsa_resource_auth,
             )
             .unwrap();
             self.put_encoded_substate(&ECDSA_TOKEN, &ecdsa_token, id_gen.next());
 
+            let mut ed25519_resource_auth = HashMap::new();
+            ed25519_resource_auth.insert(Withdraw, (rule!(allow_all), LOCKED));
+            let ed25519_token = ResourceManager::new(
+                ResourceType::NonFungible {
+                    id_type: NonFungibleIdType::Bytes,
+                },
+                HashMap::new(),
+                ed25519_resource_auth,
+            )
+            .unwrap();
+            self.put_encoded_substate(&ED25519_TOKEN, &ed25519_token, id_gen.next());
+
+            let system_token = ResourceManager::new(
+                ResourceType::Fungible { divisibility: 0 },
+                HashMap::new(),
+                HashMap::new(),
+            )
+            .unwrap();
+            self.put_encoded_substate(&SYSTEM_TOKEN, &system_token, id_gen.next());
+
             // Instantiate system component
             let system_vault = Vault::new(minted_xrd);
             self.put_encoded_child_substate(
@@ -209,13 +277,35 @@ pub trait SubstateStore {
                 scrypto_encode(&SystemComponentState { xrd: XRD_VAULT }),
             );
             self.put_encoded_substate(&SYSTEM_COMPONENT, &system_component, id_gen.next());
+
+            // Instantiate epoch manager, with no validators registered
+            let epoch_manager = Component::new(
+                VALIDATOR_PACKAGE,
+                EPOCH_MANAGER_NAME.to_owned(),
+                vec![],
+                scrypto_encode(&EpochManager::new(XRD_EMISSION_PER_EPOCH.into())),
+            );
+            self.put_encoded_substate(&EPOCH_MANAGER, &epoch_manager, id_gen.next());
         }
     }
 
     fn get_epoch(&self) -> u64;
 
+    /// Sets the current epoch, pruning the committed intents which have expired by then.
     fn set_epoch(&mut self, epoch: u64);
 
+    /// Returns the ledger time, in seconds since the Unix epoch.
+    fn get_current_time(&self) -> u64;
+
+    /// Sets the ledger time, rounding it down to the precision of the ledger clock.
+    fn set_current_time(&mut self, timestamp: u64);
+
+    /// Returns whether a transaction intent has been committed and hasn't expired yet.
+    fn is_intent_committed(&self, intent_hash: &Hash) -> bool;
+
+    /// Records a committed transaction intent, which can be forgotten from the given epoch on.
+    fn put_committed_intent(&mut self, intent_hash: &Hash, expiry_epoch: u64);
+
     // TODO: redefine what nonce is and how it's updated
     // For now, we bump nonce only when a transaction has been committed
     // or when an account is created (for testing).
@@ -9,6 +9,8 @@ compile_error!("Feature `std` and `alloc` can't be enabled at the same time.");
 pub mod engine;
 /// Radix Engine errors.
 pub mod errors;
+/// Transaction fee metering.
+pub mod fee;
 /// Radix ledger abstraction.
 pub mod ledger;
 /// Radix Engine transaction and state models.
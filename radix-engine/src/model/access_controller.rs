@@ -0,0 +1,195 @@
+use sbor::*;
+use scrypto::buffer::{scrypto_decode, scrypto_encode};
+use scrypto::engine::types::*;
+use scrypto::resource::AccessRule;
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec;
+use scrypto::rust::vec::Vec;
+use scrypto::values::ScryptoValue;
+
+use crate::engine::SystemApi;
+use crate::model::{
+    convert, Component, MethodAuthorization, ProofError, ResourceContainerError,
+    ResourceContainerId,
+};
+
+/// Represents an error when accessing an access controller.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessControllerError {
+    InvalidRequestData(DecodeError),
+    MethodNotFound(String),
+    NotAnAccessController(ComponentAddress),
+    EmptyBadge,
+    NoPendingRecovery,
+    /// The confirmation delay of the pending recovery ends at the given epoch.
+    RecoveryDelayNotElapsed(u64),
+    ResourceContainerError(ResourceContainerError),
+    ProofError(ProofError),
+    CouldNotTakeBucket,
+    CouldNotCreateProof,
+    CouldNotCreateComponent,
+}
+
+/// A proposal of the recovery role to replace the primary rule.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+struct RecoveryProposal {
+    primary_rule: AccessRule,
+    /// The epoch from which the proposal can be confirmed.
+    confirmable_from_epoch: u64,
+}
+
+/// A native component which holds a badge, and controls who can use it.
+///
+/// The primary role creates proofs of the badge. The recovery role replaces the primary rule,
+/// which takes effect only once the confirmation delay has passed since it was proposed, so
+/// that the primary role has time to cancel a recovery it did not ask for.
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct AccessController {
+    badge: scrypto::resource::Vault,
+    primary_rule: AccessRule,
+    recovery_rule: AccessRule,
+    /// The number of epochs between proposing and confirming a recovery.
+    confirmation_delay: u64,
+    pending_recovery: Option<RecoveryProposal>,
+}
+
+impl AccessController {
+    /// Returns the authorization of a method of this access controller.
+    pub fn authorization(&self, function: &str) -> Vec<MethodAuthorization> {
+        match function {
+            "create_proof" | "cancel_recovery" => {
+                vec![convert(&Type::Unit, &Value::Unit, &self.primary_rule)]
+            }
+            "initiate_recovery" | "confirm_recovery" => {
+                vec![convert(&Type::Unit, &Value::Unit, &self.recovery_rule)]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn create_proof<S: SystemApi>(
+        &self,
+        controller_address: ComponentAddress,
+        system_api: &mut S,
+    ) -> Result<scrypto::resource::Proof, AccessControllerError> {
+        let vault_id = self.badge.0;
+        let mut vault = system_api.borrow_global_mut_vault(&controller_address, &vault_id);
+        let proof = vault.create_proof(ResourceContainerId::Vault(vault_id));
+        system_api.return_borrowed_global_vault(&controller_address, &vault_id, vault);
+        let proof_id = system_api
+            .create_proof(proof.map_err(AccessControllerError::ProofError)?)
+            .map_err(|_| AccessControllerError::CouldNotCreateProof)?;
+        Ok(scrypto::resource::Proof(proof_id))
+    }
+
+    fn confirm_recovery(&mut self, current_epoch: u64) -> Result<(), AccessControllerError> {
+        let proposal = self
+            .pending_recovery
+            .take()
+            .ok_or(AccessControllerError::NoPendingRecovery)?;
+        if current_epoch < proposal.confirmable_from_epoch {
+            let confirmable_from_epoch = proposal.confirmable_from_epoch;
+            self.pending_recovery = Some(proposal);
+            return Err(AccessControllerError::RecoveryDelayNotElapsed(
+                confirmable_from_epoch,
+            ));
+        }
+        self.primary_rule = proposal.primary_rule;
+        Ok(())
+    }
+
+    pub fn static_main<S: SystemApi>(
+        function: &str,
+        args: Vec<ScryptoValue>,
+        system_api: &mut S,
+    ) -> Result<ScryptoValue, AccessControllerError> {
+        match function {
+            "create" => {
+                let bucket: scrypto::resource::Bucket = scrypto_decode(&args[0].raw)
+                    .map_err(AccessControllerError::InvalidRequestData)?;
+                let primary_rule: AccessRule = scrypto_decode(&args[1].raw)
+                    .map_err(AccessControllerError::InvalidRequestData)?;
+                let recovery_rule: AccessRule = scrypto_decode(&args[2].raw)
+                    .map_err(AccessControllerError::InvalidRequestData)?;
+                let confirmation_delay: u64 = scrypto_decode(&args[3].raw)
+                    .map_err(AccessControllerError::InvalidRequestData)?;
+
+                let container = system_api
+                    .take_bucket(bucket.0)
+                    .map_err(|_| AccessControllerError::CouldNotTakeBucket)?
+                    .into_container()
+                    .map_err(AccessControllerError::ResourceContainerError)?;
+                if container.is_empty() {
+                    return Err(AccessControllerError::EmptyBadge);
+                }
+                let vault_id = system_api.create_vault(container);
+
+                let controller = Self {
+                    badge: scrypto::resource::Vault(vault_id),
+                    primary_rule,
+                    recovery_rule,
+                    confirmation_delay,
+                    pending_recovery: None,
+                };
+                let component = Component::new(
+                    ACCESS_CONTROLLER_PACKAGE,
+                    "AccessController".to_string(),
+                    vec![],
+                    scrypto_encode(&controller),
+                );
+                let component_address = system_api
+                    .create_component(component)
+                    .map_err(|_| AccessControllerError::CouldNotCreateComponent)?;
+                Ok(ScryptoValue::from_value(&component_address))
+            }
+            _ => Err(AccessControllerError::MethodNotFound(function.to_string())),
+        }
+    }
+
+    pub fn main<S: SystemApi>(
+        &mut self,
+        controller_address: ComponentAddress,
+        function: &str,
+        args: Vec<ScryptoValue>,
+        system_api: &mut S,
+    ) -> Result<ScryptoValue, AccessControllerError> {
+        match function {
+            "create_proof" => {
+                let proof = self.create_proof(controller_address, system_api)?;
+                Ok(ScryptoValue::from_value(&proof))
+            }
+            "initiate_recovery" => {
+                let primary_rule: AccessRule = scrypto_decode(&args[0].raw)
+                    .map_err(AccessControllerError::InvalidRequestData)?;
+                // A new proposal replaces any pending one, and restarts the delay
+                let confirmable_from_epoch = system_api
+                    .get_epoch()
+                    .saturating_add(self.confirmation_delay);
+                self.pending_recovery = Some(RecoveryProposal {
+                    primary_rule,
+                    confirmable_from_epoch,
+                });
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "confirm_recovery" => {
+                self.confirm_recovery(system_api.get_epoch())?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "cancel_recovery" => {
+                self.pending_recovery
+                    .take()
+                    .ok_or(AccessControllerError::NoPendingRecovery)?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "get_pending_recovery" => {
+                let pending_recovery = self
+                    .pending_recovery
+                    .as_ref()
+                    .map(|p| (p.primary_rule.clone(), p.confirmable_from_epoch));
+                Ok(ScryptoValue::from_value(&pending_recovery))
+            }
+            _ => Err(AccessControllerError::MethodNotFound(function.to_string())),
+        }
+    }
+}
@@ -203,6 +203,10 @@ fn soft_to_hard_proof_rule(schema: &Type, proof_rule: &ProofRule, dom: &Value) -
             let hard_resources = soft_to_hard_resource_list(schema, resources, dom);
             HardProofRule::CountOf(hard_count, hard_resources)
         }
+        ProofRule::FromEpoch(epoch) => HardProofRule::FromEpoch(*epoch),
+        ProofRule::BeforeEpoch(epoch) => HardProofRule::BeforeEpoch(*epoch),
+        ProofRule::FromTime(timestamp) => HardProofRule::FromTime(*timestamp),
+        ProofRule::BeforeTime(timestamp) => HardProofRule::BeforeTime(*timestamp),
     }
 }
 
@@ -105,6 +105,14 @@ impl AuthZone {
                 self.push(proof);
                 Ok(ScryptoValue::from_value(&()))
             }
+            "drain" => {
+                let mut proofs = Vec::new();
+                for proof in self.proofs.drain(..) {
+                    let proof_id = system_api.create_proof(proof).map_err(|_| AuthZoneError::CouldNotCreateProof)?;
+                    proofs.push(scrypto::resource::Proof(proof_id));
+                }
+                Ok(ScryptoValue::from_value(&proofs))
+            }
             "create_proof" => {
                 let resource_address = scrypto_decode(&args[0].raw).map_err(|e| AuthZoneError::InvalidRequestData(e))?;
                 let resource_manager: ResourceManager = system_api.borrow_global_mut_resource_manager(resource_address).map_err(|_| AuthZoneError::CouldNotGetResource)?;
@@ -61,7 +61,7 @@ impl Bucket {
             ResourceType::Fungible { .. } => {
                 self.create_proof_by_amount(self.total_amount(), container_id)
             }
-            ResourceType::NonFungible => {
+            ResourceType::NonFungible { .. } => {
                 self.create_proof_by_ids(&self.total_ids().unwrap(), container_id)
             }
         }
@@ -147,6 +147,11 @@ impl Bucket {
             .map(|c| c.into_inner())
     }
 
+    /// Returns a copy of the content of this bucket, for rolling back to.
+    pub(crate) fn snapshot(&self) -> ResourceContainer {
+        self.borrow_container().snapshot()
+    }
+
     fn borrow_container(&self) -> Ref<ResourceContainer> {
         self.container.borrow()
     }
@@ -229,7 +234,10 @@ impl Bucket {
             .borrow_global_mut_resource_manager(resource_address)
             .unwrap();
         resource_manager.burn(self.total_amount());
-        if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
+        if matches!(
+            resource_manager.resource_type(),
+            ResourceType::NonFungible { .. }
+        ) {
             for id in self.total_ids().unwrap() {
                 let non_fungible_address = NonFungibleAddress::new(resource_address, id);
                 system_api.set_non_fungible(non_fungible_address, Option::None);
@@ -1,19 +1,42 @@
 use sbor::*;
+use scrypto::buffer::scrypto_decode;
+use scrypto::component::RoyaltyConfig;
+use scrypto::constants::RADIX_TOKEN;
 use scrypto::engine::types::*;
-use scrypto::resource::AccessRules;
+use scrypto::resource::{AccessRule, AccessRules, DIVISIBILITY_MAXIMUM};
+use scrypto::rust::collections::HashMap;
 use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 
-use crate::model::{convert, MethodAuthorization};
+use crate::model::{
+    convert, Bucket, MethodAuthorization, ResourceContainer, ResourceContainerError, Vault,
+    VaultError,
+};
+
+/// Represents an error when accessing the native functions of a component.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComponentError {
+    InvalidRequestData(DecodeError),
+    MethodNotFound(String),
+}
 
 /// A component is an instance of blueprint.
-#[derive(Debug, TypeId, Encode, Decode)]
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct Component {
     package_address: PackageAddress,
     blueprint_name: String,
     auths: Vec<AccessRules>,
     state: Vec<u8>,
+    royalty_config: RoyaltyConfig,
+    /// The vault collecting the XRD paid in royalties, until claimed
+    royalty_vault: Vault,
+    metadata: HashMap<String, String>,
+    metadata_update_rule: AccessRule,
+    /// The rule which guards migrations, if the component opted into them
+    migration_rule: Option<AccessRule>,
 }
 
 impl Component {
@@ -28,9 +51,41 @@ impl Component {
             blueprint_name,
             auths: method_auth,
             state,
+            royalty_config: RoyaltyConfig::new(),
+            royalty_vault: Vault::new(ResourceContainer::new_fungible(
+                RADIX_TOKEN,
+                DIVISIBILITY_MAXIMUM,
+                Decimal::zero(),
+            )),
+            metadata: HashMap::new(),
+            metadata_update_rule: AccessRule::DenyAll,
+            migration_rule: None,
         }
     }
 
+    /// Sets the royalties charged to callers of the component's methods.
+    pub fn with_royalty_config(mut self, royalty_config: RoyaltyConfig) -> Self {
+        self.royalty_config = royalty_config;
+        self
+    }
+
+    /// Sets the initial metadata and the rule which guards its updates.
+    pub fn with_metadata(
+        mut self,
+        metadata: HashMap<String, String>,
+        metadata_update_rule: AccessRule,
+    ) -> Self {
+        self.metadata = metadata;
+        self.metadata_update_rule = metadata_update_rule;
+        self
+    }
+
+    /// Sets the rule which must be satisfied to migrate the component.
+    pub fn with_migration_rule(mut self, migration_rule: Option<AccessRule>) -> Self {
+        self.migration_rule = migration_rule;
+        self
+    }
+
     pub fn method_authorization(
         &self,
         schema: &Type,
@@ -56,6 +111,11 @@ impl Component {
         self.package_address.clone()
     }
 
+    /// Moves the component to another version of its package.
+    pub fn set_package_address(&mut self, package_address: PackageAddress) {
+        self.package_address = package_address;
+    }
+
     pub fn blueprint_name(&self) -> &str {
         &self.blueprint_name
     }
@@ -67,4 +127,75 @@ impl Component {
     pub fn set_state(&mut self, new_state: Vec<u8>) {
         self.state = new_state;
     }
+
+    pub fn royalty_config(&self) -> &RoyaltyConfig {
+        &self.royalty_config
+    }
+
+    /// Returns the XRD collected in royalties and not claimed yet.
+    pub fn royalty(&self) -> Decimal {
+        self.royalty_vault.total_amount()
+    }
+
+    /// Puts the XRD paid in royalties into the royalty vault.
+    pub fn add_royalty(
+        &mut self,
+        royalty: ResourceContainer,
+    ) -> Result<(), ResourceContainerError> {
+        self.royalty_vault.put(Bucket::new(royalty))
+    }
+
+    /// Takes all the royalties collected so far.
+    pub fn take_royalty(&mut self) -> Result<ResourceContainer, VaultError> {
+        self.royalty_vault.take(self.royalty_vault.total_amount())
+    }
+
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    pub fn metadata_update_rule(&self) -> &AccessRule {
+        &self.metadata_update_rule
+    }
+
+    /// Returns the authorization of a migration, or `None` if the component can't be migrated.
+    pub fn migration_authorization(&self) -> Option<MethodAuthorization> {
+        self.migration_rule
+            .as_ref()
+            .map(|rule| convert(&Type::Unit, &Value::Unit, rule))
+    }
+
+    /// Returns the authorization of a native function of this component.
+    pub fn native_authorization(&self, function: &str) -> Vec<MethodAuthorization> {
+        match function {
+            "update_metadata" | "set_metadata_update_rule" => vec![convert(
+                &Type::Unit,
+                &Value::Unit,
+                &self.metadata_update_rule,
+            )],
+            _ => vec![],
+        }
+    }
+
+    pub fn main(
+        &mut self,
+        function: &str,
+        args: Vec<ScryptoValue>,
+    ) -> Result<ScryptoValue, ComponentError> {
+        match function {
+            "update_metadata" => {
+                let metadata: HashMap<String, String> =
+                    scrypto_decode(&args[0].raw).map_err(ComponentError::InvalidRequestData)?;
+                self.metadata = metadata;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "set_metadata_update_rule" => {
+                let rule: AccessRule =
+                    scrypto_decode(&args[0].raw).map_err(ComponentError::InvalidRequestData)?;
+                self.metadata_update_rule = rule;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            _ => Err(ComponentError::MethodNotFound(function.to_string())),
+        }
+    }
 }
@@ -0,0 +1,130 @@
+use sbor::*;
+use scrypto::engine::types::*;
+use scrypto::math::RoundingMode;
+use scrypto::resource::{require, AccessRule, AccessRuleNode};
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec;
+use scrypto::rust::vec::Vec;
+use scrypto::values::ScryptoValue;
+
+use crate::engine::SystemApi;
+use crate::model::{convert, MethodAuthorization, ResourceManagerError, Validator};
+
+/// Represents an error when accessing the epoch manager.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EpochManagerError {
+    InvalidRequestData(DecodeError),
+    MethodNotFound(String),
+    InvalidValidator(ComponentAddress),
+    ResourceManagerError(ResourceManagerError),
+    CouldNotMintEmission,
+}
+
+/// The native component which keeps the set of registered validators, and ends epochs.
+///
+/// At the end of every epoch, newly minted XRD is distributed to the registered validators in
+/// proportion to their stake. The epoch manager is created by the bootstrap process, and only
+/// the system can end an epoch.
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct EpochManager {
+    validators: Vec<ComponentAddress>,
+    /// The amount of XRD distributed at the end of every epoch.
+    emission_per_epoch: Decimal,
+}
+
+impl EpochManager {
+    pub fn new(emission_per_epoch: Decimal) -> Self {
+        Self {
+            validators: Vec::new(),
+            emission_per_epoch,
+        }
+    }
+
+    pub fn validators(&self) -> &[ComponentAddress] {
+        &self.validators
+    }
+
+    /// Adds a validator to the set, returning false if it is already registered.
+    pub fn register(&mut self, validator_address: ComponentAddress) -> bool {
+        if self.validators.contains(&validator_address) {
+            return false;
+        }
+        self.validators.push(validator_address);
+        true
+    }
+
+    /// Removes a validator from the set, returning false if it is not registered.
+    pub fn unregister(&mut self, validator_address: ComponentAddress) -> bool {
+        let len = self.validators.len();
+        self.validators
+            .retain(|address| *address != validator_address);
+        self.validators.len() != len
+    }
+
+    /// Returns the authorization of a method of the epoch manager.
+    pub fn authorization(&self, function: &str) -> Vec<MethodAuthorization> {
+        match function {
+            "next_epoch" => {
+                let rule = AccessRule::Protected(AccessRuleNode::ProofRule(require(SYSTEM_TOKEN)));
+                vec![convert(&Type::Unit, &Value::Unit, &rule)]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Mints the emission of the epoch, and stakes it to the registered validators.
+    ///
+    /// Every share is rounded down, so slightly less than the emission may be minted.
+    fn distribute_emission<S: SystemApi>(
+        &self,
+        system_api: &mut S,
+    ) -> Result<(), EpochManagerError> {
+        let mut validators = Vec::new();
+        for validator_address in &self.validators {
+            let validator = Validator::load(*validator_address, system_api)
+                .map_err(|_| EpochManagerError::InvalidValidator(*validator_address))?;
+            let stake = validator.stake_amount(*validator_address, system_api);
+            validators.push((*validator_address, validator, stake));
+        }
+        let total_stake: Decimal = validators.iter().map(|(_, _, stake)| *stake).sum();
+        if !total_stake.is_positive() {
+            return Ok(());
+        }
+
+        for (validator_address, validator, stake) in validators {
+            let emission = (self.emission_per_epoch * stake / total_stake)
+                .round(18, RoundingMode::TowardsZero);
+            if !emission.is_positive() {
+                continue;
+            }
+            let mut resource_manager = system_api
+                .borrow_global_mut_resource_manager(RADIX_TOKEN)
+                .map_err(|_| EpochManagerError::CouldNotMintEmission)?;
+            let minted = resource_manager.mint_fungible(emission, RADIX_TOKEN);
+            system_api.return_borrowed_global_resource_manager(RADIX_TOKEN, resource_manager);
+            let minted = minted.map_err(EpochManagerError::ResourceManagerError)?;
+            validator
+                .add_stake(validator_address, minted, system_api)
+                .map_err(|_| EpochManagerError::InvalidValidator(validator_address))?;
+        }
+        Ok(())
+    }
+
+    pub fn main<S: SystemApi>(
+        &self,
+        function: &str,
+        _args: Vec<ScryptoValue>,
+        system_api: &mut S,
+    ) -> Result<ScryptoValue, EpochManagerError> {
+        match function {
+            "next_epoch" => {
+                self.distribute_emission(system_api)?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "get_validators" => Ok(ScryptoValue::from_value(&self.validators)),
+            "get_emission_per_epoch" => Ok(ScryptoValue::from_value(&self.emission_per_epoch)),
+            _ => Err(EpochManagerError::MethodNotFound(function.to_string())),
+        }
+    }
+}
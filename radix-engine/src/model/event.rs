@@ -0,0 +1,13 @@
+use scrypto::engine::types::*;
+use scrypto::rust::string::String;
+use scrypto::values::ScryptoValue;
+
+/// An event emitted by a blueprint.
+#[derive(Debug, Clone)]
+pub struct Event {
+    /// The blueprint or component which emitted the event.
+    pub actor: ScryptoActorInfo,
+    /// The name of the event type.
+    pub name: String,
+    pub data: ScryptoValue,
+}
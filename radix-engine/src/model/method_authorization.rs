@@ -2,6 +2,7 @@ use sbor::*;
 use scrypto::engine::types::*;
 use scrypto::rust::vec::Vec;
 
+use crate::ledger::CLOCK_PRECISION;
 use crate::model::method_authorization::MethodAuthorizationError::NotAuthorized;
 use crate::model::{AuthZone, Proof};
 
@@ -11,6 +12,14 @@ pub enum MethodAuthorizationError {
     UnsupportedMethod,
 }
 
+/// The ledger epoch and time which time-based rules are checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedgerClock {
+    pub epoch: u64,
+    /// Seconds since the Unix epoch, rounded down to the clock precision
+    pub timestamp: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TypeId, Encode, Decode)]
 pub enum HardDecimal {
     Amount(Decimal),
@@ -99,10 +108,18 @@ pub enum HardProofRule {
     AllOf(HardProofRuleResourceList),
     AnyOf(HardProofRuleResourceList),
     CountOf(HardCount, HardProofRuleResourceList),
+    FromEpoch(u64),
+    BeforeEpoch(u64),
+    FromTime(u64),
+    BeforeTime(u64),
 }
 
 impl HardProofRule {
-    pub fn check(&self, auth_zones: &[&AuthZone]) -> Result<(), MethodAuthorizationError> {
+    pub fn check(
+        &self,
+        auth_zones: &[&AuthZone],
+        clock: LedgerClock,
+    ) -> Result<(), MethodAuthorizationError> {
         match self {
             HardProofRule::This(resource) => {
                 if resource.check(auth_zones) {
@@ -151,6 +168,16 @@ impl HardProofRule {
                 }
                 Err(NotAuthorized)
             }
+            HardProofRule::FromEpoch(epoch) if clock.epoch >= *epoch => Ok(()),
+            HardProofRule::BeforeEpoch(epoch) if clock.epoch < *epoch => Ok(()),
+            HardProofRule::FromTime(timestamp) if clock.timestamp >= *timestamp => Ok(()),
+            // The actual time may be anywhere within the rounded clock period, so all of it
+            // has to be before the deadline
+            HardProofRule::BeforeTime(timestamp)
+                if clock.timestamp + CLOCK_PRECISION <= *timestamp =>
+            {
+                Ok(())
+            }
             _ => Err(NotAuthorized),
         }
     }
@@ -164,17 +191,21 @@ pub enum HardAuthRule {
 }
 
 impl HardAuthRule {
-    fn check(&self, auth_zones: &[&AuthZone]) -> Result<(), MethodAuthorizationError> {
+    fn check(
+        &self,
+        auth_zones: &[&AuthZone],
+        clock: LedgerClock,
+    ) -> Result<(), MethodAuthorizationError> {
         match self {
-            HardAuthRule::ProofRule(rule) => rule.check(auth_zones),
+            HardAuthRule::ProofRule(rule) => rule.check(auth_zones, clock),
             HardAuthRule::AnyOf(rules) => {
-                if !rules.iter().any(|r| r.check(auth_zones).is_ok()) {
+                if !rules.iter().any(|r| r.check(auth_zones, clock).is_ok()) {
                     return Err(NotAuthorized);
                 }
                 Ok(())
             }
             HardAuthRule::AllOf(rules) => {
-                if rules.iter().any(|r| r.check(auth_zones).is_err()) {
+                if rules.iter().any(|r| r.check(auth_zones, clock).is_err()) {
                     return Err(NotAuthorized);
                 }
                 Ok(())
@@ -193,9 +224,13 @@ pub enum MethodAuthorization {
 }
 
 impl MethodAuthorization {
-    pub fn check(&self, auth_zones: &[&AuthZone]) -> Result<(), MethodAuthorizationError> {
+    pub fn check(
+        &self,
+        auth_zones: &[&AuthZone],
+        clock: LedgerClock,
+    ) -> Result<(), MethodAuthorizationError> {
         match self {
-            MethodAuthorization::Protected(rule) => rule.check(auth_zones),
+            MethodAuthorization::Protected(rule) => rule.check(auth_zones, clock),
             MethodAuthorization::AllowAll => Ok(()),
             MethodAuthorization::DenyAll => Err(MethodAuthorizationError::NotAuthorized),
             MethodAuthorization::Unsupported => Err(MethodAuthorizationError::UnsupportedMethod),
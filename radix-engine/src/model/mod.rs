@@ -1,37 +1,54 @@
+mod access_controller;
 mod auth_converter;
 mod auth_zone;
 mod bucket;
 mod component;
+mod epoch_manager;
+mod event;
 mod method_authorization;
 mod non_fungible;
 mod package;
+mod pool;
 mod proof;
 mod receipt;
 mod resource;
 mod resource_manager;
+mod state_changes;
 mod transaction;
 mod transaction_process;
 mod validated_transaction;
+mod validator;
 mod vault;
 mod worktop;
 
+pub use access_controller::{AccessController, AccessControllerError};
 pub use auth_zone::{AuthZone, AuthZoneError};
 pub use auth_converter::convert;
 pub use bucket::{Bucket, BucketError};
-pub use component::Component;
+pub use component::{Component, ComponentError};
+pub use epoch_manager::{EpochManager, EpochManagerError};
+pub use event::Event;
 pub use method_authorization::{
-    HardProofRule, HardResourceOrNonFungible, MethodAuthorization, MethodAuthorizationError,
+    HardProofRule, HardResourceOrNonFungible, LedgerClock, MethodAuthorization,
+    MethodAuthorizationError,
 };
 pub use non_fungible::NonFungible;
 pub use package::{Package, PackageError};
+pub use pool::{Pool, PoolError};
 pub use proof::*;
 pub use receipt::Receipt;
 pub use resource::*;
 pub use resource_manager::{ResourceManager, ResourceManagerError};
+pub use state_changes::{
+    BalanceChange, ComponentStateChange, LazyMapEntryChange, StateChanges, SupplyChange,
+    VaultChange,
+};
 pub use transaction_process::{TransactionProcess};
 pub use transaction::{
-    Instruction, SignedTransaction, Transaction,
+    Instruction, Network, SignedTransaction, Transaction, TransactionHeader,
+    TRANSACTION_VERSION_V1,
 };
 pub use validated_transaction::{ValidatedTransaction, ValidatedInstruction};
-pub use vault::{Vault, VaultError};
+pub use validator::{Validator, ValidatorError, UNSTAKE_DELAY};
+pub use vault::{Vault, VaultError, VaultSnapshot};
 pub use worktop::{Worktop, WorktopError};
@@ -1,25 +1,77 @@
 use sbor::*;
-use scrypto::abi::{Function, Method};
+use scrypto::abi::{Event, Function, Method};
 use scrypto::buffer::scrypto_decode;
+use scrypto::component::{PackageAddress, RoyaltyConfig};
+use scrypto::constants::RADIX_TOKEN;
+use scrypto::math::Decimal;
+use scrypto::resource::{AccessRule, DIVISIBILITY_MAXIMUM};
 use scrypto::rust::collections::HashMap;
 use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 use scrypto::values::ScryptoValue;
+use wasm_instrument::gas_metering::{self, ConstantCostRules};
+use wasm_instrument::inject_stack_limiter;
+use wasm_instrument::parity_wasm;
 use wasmi::{
-    ExternVal, ImportsBuilder, MemoryRef, Module, ModuleInstance, ModuleRef, NopExternals,
-    RuntimeValue,
+    ExternVal, Externals, ImportsBuilder, MemoryRef, Module, ModuleInstance, ModuleRef,
+    RuntimeArgs, RuntimeValue, Trap,
 };
 
-use crate::engine::{EnvModuleResolver, SystemApi};
-use crate::errors::WasmValidationError;
+use crate::engine::{EnvModuleResolver, SystemApi, GAS_FUNCTION_INDEX, GAS_FUNCTION_NAME};
+use crate::errors::{RuntimeError, WasmValidationError};
+use crate::fee::{CostUnitCounter, DEFAULT_COST_UNIT_LIMIT};
+use crate::model::{Bucket, ResourceContainer, ResourceContainerError, Vault, VaultError};
+
+/// Gas charged for every executed wasm instruction.
+pub const INSTRUCTION_COST: u32 = 1;
+/// Gas charged for every page of memory grown.
+pub const MEMORY_GROW_COST: u32 = 1024;
+/// The maximum stack height of a wasm call, in stack slots.
+pub const MAX_STACK_HEIGHT: u32 = 64 * 1024;
+
+/// Externals used when calling ABI exports at publish time, which only meter gas.
+struct AbiGasMeter {
+    cost_unit_counter: CostUnitCounter,
+}
+
+impl Externals for AbiGasMeter {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        match index {
+            GAS_FUNCTION_INDEX => {
+                let gas: u32 = args.nth_checked(0)?;
+                self.cost_unit_counter
+                    .consume(gas, GAS_FUNCTION_NAME)
+                    .map_err(|e| Trap::from(RuntimeError::WasmGasExhausted(e)))?;
+                Ok(None)
+            }
+            _ => Err(RuntimeError::HostFunctionNotFound(index).into()),
+        }
+    }
+}
 
 /// A collection of blueprints, compiled and published as a single unit.
+///
+/// Packages are immutable; an upgrade publishes a new version which links back to the
+/// package it replaces.
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct Package {
     code: Vec<u8>,
     blueprints: HashMap<String, Type>,
+    version: u32,
+    predecessor: Option<PackageAddress>,
+    /// The upgrade of this package, which makes it an older version
+    successor: Option<PackageAddress>,
+    upgrade_rule: Option<AccessRule>,
+    royalty_configs: HashMap<String, RoyaltyConfig>,
+    /// The vault collecting the XRD paid in royalties, until claimed
+    royalty_vault: Vault,
+    metadata: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -28,6 +80,12 @@ pub enum PackageError {
     BlueprintNotFound,
     WasmValidationError(WasmValidationError),
     MethodNotFound(String),
+    PackageNotFound(PackageAddress),
+    NotUpgradeable(PackageAddress),
+    AlreadyUpgraded(PackageAddress),
+    NoOwner(PackageAddress),
+    CouldNotCreateBucket,
+    VaultError(VaultError),
 }
 
 impl Package {
@@ -41,9 +99,13 @@ impl Package {
             .deny_floating_point()
             .map_err(|_| WasmValidationError::FloatingPointNotAllowed)?;
 
+        // Instrument with gas metering and stack height limit
+        let code = Self::instrument(&code)?;
+        let instrumented = Self::parse_module(&code)?;
+
         // Instantiate
         let instance = ModuleInstance::new(
-            &parsed,
+            &instrumented,
             &ImportsBuilder::new().with_resolver("env", &EnvModuleResolver),
         )
         .map_err(|_| WasmValidationError::InvalidModule)?;
@@ -72,10 +134,16 @@ impl Package {
             .collect();
 
         let mut blueprints = HashMap::new();
+        let mut gas_meter = AbiGasMeter {
+            cost_unit_counter: CostUnitCounter::new(
+                DEFAULT_COST_UNIT_LIMIT,
+                DEFAULT_COST_UNIT_LIMIT,
+            ),
+        };
 
         for method_name in blueprint_abi_methods {
             let rtn = module
-                .invoke_export(&method_name, &[], &mut NopExternals)
+                .invoke_export(&method_name, &[], &mut gas_meter)
                 .map_err(|e| WasmValidationError::NoPackageInitExport(e.into()))?
                 .ok_or(WasmValidationError::InvalidPackageInit)?;
 
@@ -91,7 +159,7 @@ impl Package {
                         .get_into((ptr + 4) as u32, &mut data)
                         .map_err(|_| WasmValidationError::InvalidPackageInit)?;
 
-                    let result: (Type, Vec<Function>, Vec<Method>) = scrypto_decode(&data)
+                    let result = Self::decode_abi(&data)
                         .map_err(|_| WasmValidationError::InvalidPackageInit)?;
                     Ok(result.0)
                 }
@@ -105,13 +173,110 @@ impl Package {
             }
         }
 
-        Ok(Self { blueprints, code })
+        Ok(Self {
+            code,
+            blueprints,
+            version: 1,
+            predecessor: None,
+            successor: None,
+            upgrade_rule: None,
+            royalty_configs: HashMap::new(),
+            royalty_vault: Vault::new(ResourceContainer::new_fungible(
+                RADIX_TOKEN,
+                DIVISIBILITY_MAXIMUM,
+                Decimal::zero(),
+            )),
+            metadata: HashMap::new(),
+        })
+    }
+
+    /// Allows the package to be upgraded by whoever satisfies the given rule.
+    pub fn with_upgrade_rule(mut self, upgrade_rule: AccessRule) -> Self {
+        self.upgrade_rule = Some(upgrade_rule);
+        self
+    }
+
+    /// Makes this package the next version of the given one, inheriting its upgrade rule and
+    /// metadata.
+    pub fn upgrade_of(mut self, predecessor_address: PackageAddress, predecessor: &Package) -> Self {
+        self.version = predecessor.version + 1;
+        self.predecessor = Some(predecessor_address);
+        self.upgrade_rule = predecessor.upgrade_rule.clone();
+        self.metadata = predecessor.metadata.clone();
+        self
+    }
+
+    /// Decodes the output of a blueprint ABI export.
+    ///
+    /// Blueprints compiled before events were introduced don't export event schemas.
+    pub fn decode_abi(
+        data: &[u8],
+    ) -> Result<(Type, Vec<Function>, Vec<Method>, Vec<Event>), DecodeError> {
+        scrypto_decode(data).or_else(|e| {
+            scrypto_decode::<(Type, Vec<Function>, Vec<Method>)>(data)
+                .map(|(schema, functions, methods)| (schema, functions, methods, Vec::new()))
+                .map_err(|_| e)
+        })
     }
 
     pub fn code(&self) -> &[u8] {
         &self.code
     }
 
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the address of the package this one is an upgrade of.
+    pub fn predecessor(&self) -> Option<PackageAddress> {
+        self.predecessor
+    }
+
+    /// Returns the address of the upgrade of this package, if it has been upgraded.
+    pub fn successor(&self) -> Option<PackageAddress> {
+        self.successor
+    }
+
+    /// Returns the rule the package owner satisfies, which authorizes upgrades, royalty and
+    /// metadata management.
+    pub fn upgrade_rule(&self) -> Option<&AccessRule> {
+        self.upgrade_rule.as_ref()
+    }
+
+    pub fn royalty_config(&self, blueprint_name: &str) -> Option<&RoyaltyConfig> {
+        self.royalty_configs.get(blueprint_name)
+    }
+
+    pub fn set_royalty_config(&mut self, blueprint_name: String, royalty_config: RoyaltyConfig) {
+        self.royalty_configs.insert(blueprint_name, royalty_config);
+    }
+
+    /// Returns the XRD collected in royalties and not claimed yet.
+    pub fn royalty(&self) -> Decimal {
+        self.royalty_vault.total_amount()
+    }
+
+    /// Puts the XRD paid in royalties into the royalty vault.
+    pub fn add_royalty(
+        &mut self,
+        royalty: ResourceContainer,
+    ) -> Result<(), ResourceContainerError> {
+        self.royalty_vault.put(Bucket::new(royalty))
+    }
+
+    /// Takes all the royalties collected so far.
+    pub fn take_royalty(&mut self) -> Result<ResourceContainer, VaultError> {
+        self.royalty_vault.take(self.royalty_vault.total_amount())
+    }
+
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    pub fn set_metadata(&mut self, metadata: HashMap<String, String>) {
+        self.metadata = metadata;
+    }
+
     pub fn contains_blueprint(&self, blueprint_name: &str) -> bool {
         self.blueprints.contains_key(blueprint_name)
     }
@@ -128,6 +293,20 @@ impl Package {
         Ok(inst)
     }
 
+    fn instrument(code: &[u8]) -> Result<Vec<u8>, WasmValidationError> {
+        let module = parity_wasm::deserialize_buffer(code)
+            .map_err(|_| WasmValidationError::InvalidModule)?;
+        let module = gas_metering::inject(
+            module,
+            &ConstantCostRules::new(INSTRUCTION_COST, MEMORY_GROW_COST),
+            "env",
+        )
+        .map_err(|_| WasmValidationError::InstructionMeteringFailed)?;
+        let module = inject_stack_limiter(module, MAX_STACK_HEIGHT)
+            .map_err(|_| WasmValidationError::StackMeteringFailed)?;
+        parity_wasm::serialize(module).map_err(|_| WasmValidationError::InvalidModule)
+    }
+
     fn parse_module(code: &[u8]) -> Result<Module, WasmValidationError> {
         Module::from_buffer(code).map_err(|_| WasmValidationError::InvalidModule)
     }
@@ -149,6 +328,19 @@ impl Package {
         }
     }
 
+    fn borrow_owned_package<S: SystemApi>(
+        package_address: PackageAddress,
+        system_api: &mut S,
+    ) -> Result<&mut Package, PackageError> {
+        let package = system_api
+            .get_package_mut(&package_address)
+            .ok_or(PackageError::PackageNotFound(package_address))?;
+        if package.upgrade_rule().is_none() {
+            return Err(PackageError::NoOwner(package_address));
+        }
+        Ok(package)
+    }
+
     pub fn static_main<S: SystemApi>(
         function: &str,
         args: Vec<ScryptoValue>,
@@ -162,6 +354,88 @@ impl Package {
                 let package_address = system_api.create_package(package);
                 Ok(ScryptoValue::from_value(&package_address))
             }
+            "publish_with_upgrade_rule" => {
+                let bytes =
+                    scrypto_decode(&args[0].raw).map_err(PackageError::InvalidRequestData)?;
+                let upgrade_rule: AccessRule =
+                    scrypto_decode(&args[1].raw).map_err(PackageError::InvalidRequestData)?;
+                let package = Package::new(bytes)
+                    .map_err(PackageError::WasmValidationError)?
+                    .with_upgrade_rule(upgrade_rule);
+                let package_address = system_api.create_package(package);
+                Ok(ScryptoValue::from_value(&package_address))
+            }
+            // The upgrade rule of the predecessor is checked before getting here
+            "upgrade" => {
+                let predecessor_address: PackageAddress =
+                    scrypto_decode(&args[0].raw).map_err(PackageError::InvalidRequestData)?;
+                let bytes =
+                    scrypto_decode(&args[1].raw).map_err(PackageError::InvalidRequestData)?;
+                let predecessor = system_api
+                    .get_package(&predecessor_address)
+                    .ok_or(PackageError::PackageNotFound(predecessor_address))?
+                    .clone();
+                if predecessor.upgrade_rule().is_none() {
+                    return Err(PackageError::NotUpgradeable(predecessor_address));
+                }
+                // Only the latest version can be upgraded, so that versions form a single line
+                if predecessor.successor().is_some() {
+                    return Err(PackageError::AlreadyUpgraded(predecessor_address));
+                }
+                let package = Package::new(bytes)
+                    .map_err(PackageError::WasmValidationError)?
+                    .upgrade_of(predecessor_address, &predecessor);
+                let package_address = system_api.create_package(package);
+                system_api
+                    .get_package_mut(&predecessor_address)
+                    .ok_or(PackageError::PackageNotFound(predecessor_address))?
+                    .successor = Some(package_address);
+                Ok(ScryptoValue::from_value(&package_address))
+            }
+            // The upgrade rule of the package is checked before getting here
+            "set_royalty_config" => {
+                let package_address: PackageAddress =
+                    scrypto_decode(&args[0].raw).map_err(PackageError::InvalidRequestData)?;
+                let blueprint_name: String =
+                    scrypto_decode(&args[1].raw).map_err(PackageError::InvalidRequestData)?;
+                let royalty_config: RoyaltyConfig =
+                    scrypto_decode(&args[2].raw).map_err(PackageError::InvalidRequestData)?;
+                let package = Self::borrow_owned_package(package_address, system_api)?;
+                if !package.contains_blueprint(&blueprint_name) {
+                    return Err(PackageError::BlueprintNotFound);
+                }
+                package.set_royalty_config(blueprint_name, royalty_config);
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "get_metadata" => {
+                let package_address: PackageAddress =
+                    scrypto_decode(&args[0].raw).map_err(PackageError::InvalidRequestData)?;
+                let package = system_api
+                    .get_package(&package_address)
+                    .ok_or(PackageError::PackageNotFound(package_address))?;
+                Ok(ScryptoValue::from_value(package.metadata()))
+            }
+            "set_metadata" => {
+                let package_address: PackageAddress =
+                    scrypto_decode(&args[0].raw).map_err(PackageError::InvalidRequestData)?;
+                let metadata: HashMap<String, String> =
+                    scrypto_decode(&args[1].raw).map_err(PackageError::InvalidRequestData)?;
+                Self::borrow_owned_package(package_address, system_api)?.set_metadata(metadata);
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "claim_royalty" => {
+                let package_address: PackageAddress =
+                    scrypto_decode(&args[0].raw).map_err(PackageError::InvalidRequestData)?;
+                let royalty = Self::borrow_owned_package(package_address, system_api)?
+                    .take_royalty()
+                    .map_err(PackageError::VaultError)?;
+                let bucket_id = system_api
+                    .create_bucket(royalty)
+                    .map_err(|_| PackageError::CouldNotCreateBucket)?;
+                Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(
+                    bucket_id,
+                )))
+            }
             _ => Err(PackageError::MethodNotFound(function.to_string())),
         }
     }
@@ -0,0 +1,449 @@
+use sbor::*;
+use scrypto::buffer::{scrypto_decode, scrypto_encode};
+use scrypto::engine::types::*;
+use scrypto::math::RoundingMode;
+use scrypto::resource::{AccessRule, DIVISIBILITY_MAXIMUM};
+use scrypto::rust::collections::HashMap;
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec;
+use scrypto::rust::vec::Vec;
+use scrypto::values::ScryptoValue;
+
+use crate::engine::SystemApi;
+use crate::model::{
+    convert, Bucket, Component, MethodAuthorization, ResourceContainer, ResourceContainerError,
+    ResourceManager, ResourceManagerError, Vault, VaultError,
+};
+
+/// Represents an error when accessing a pool.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PoolError {
+    InvalidRequestData(DecodeError),
+    MethodNotFound(String),
+    NotAPool(ComponentAddress),
+    NoResources,
+    DuplicateResource(ResourceAddress),
+    InvalidResource(ResourceAddress),
+    ResourceNotInPool(ResourceAddress),
+    MissingContribution(ResourceAddress),
+    InvalidPoolUnit(ResourceAddress),
+    PoolEmpty,
+    ResourceContainerError(ResourceContainerError),
+    VaultError(VaultError),
+    ResourceManagerError(ResourceManagerError),
+    CouldNotCreateBucket,
+    CouldNotTakeBucket,
+    CouldNotCreateComponent,
+}
+
+/// A native pool of fungible resources, owned by the holders of its pool units.
+///
+/// A pool is stored as a component of the `POOL_PACKAGE`, and the reserves are vaults of that
+/// component. One and two-resource pools are pools with one or two reserves.
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct Pool {
+    reserves: Vec<(ResourceAddress, scrypto::resource::Vault)>,
+    pool_unit_resource_address: ResourceAddress,
+    /// The rule which guards the protected deposit and withdraw methods.
+    manager_rule: AccessRule,
+}
+
+impl Pool {
+    fn new<S: SystemApi>(
+        resource_addresses: Vec<ResourceAddress>,
+        manager_rule: AccessRule,
+        system_api: &mut S,
+    ) -> Result<Self, PoolError> {
+        if resource_addresses.is_empty() {
+            return Err(PoolError::NoResources);
+        }
+
+        let mut reserves = Vec::new();
+        for resource_address in resource_addresses {
+            if reserves
+                .iter()
+                .any(|(address, _)| *address == resource_address)
+            {
+                return Err(PoolError::DuplicateResource(resource_address));
+            }
+            let resource_type = Self::resource_type(resource_address, system_api)?;
+            if !matches!(resource_type, ResourceType::Fungible { .. }) {
+                return Err(PoolError::InvalidResource(resource_address));
+            }
+            let container = ResourceContainer::new_empty(resource_address, resource_type);
+            let vault_id = system_api.create_vault(container);
+            reserves.push((resource_address, scrypto::resource::Vault(vault_id)));
+        }
+
+        // Pool units are only minted and burnt by the pool itself
+        let mut metadata = HashMap::new();
+        metadata.insert("name".to_string(), "Pool Unit".to_string());
+        let resource_manager = ResourceManager::new(
+            ResourceType::Fungible {
+                divisibility: DIVISIBILITY_MAXIMUM,
+            },
+            metadata,
+            HashMap::new(),
+        )
+        .map_err(PoolError::ResourceManagerError)?;
+        let pool_unit_resource_address = system_api.create_resource(resource_manager);
+
+        Ok(Self {
+            reserves,
+            pool_unit_resource_address,
+            manager_rule,
+        })
+    }
+
+    pub fn pool_unit_resource_address(&self) -> ResourceAddress {
+        self.pool_unit_resource_address
+    }
+
+    /// Returns the authorization of a method of this pool.
+    pub fn authorization(&self, function: &str) -> Vec<MethodAuthorization> {
+        match function {
+            "protected_deposit" | "protected_withdraw" => {
+                vec![convert(&Type::Unit, &Value::Unit, &self.manager_rule)]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn resource_type<S: SystemApi>(
+        resource_address: ResourceAddress,
+        system_api: &mut S,
+    ) -> Result<ResourceType, PoolError> {
+        let resource_manager = system_api
+            .borrow_global_mut_resource_manager(resource_address)
+            .map_err(|_| PoolError::InvalidResource(resource_address))?;
+        let resource_type = resource_manager.resource_type();
+        system_api.return_borrowed_global_resource_manager(resource_address, resource_manager);
+        Ok(resource_type)
+    }
+
+    fn reserve_index(&self, resource_address: ResourceAddress) -> Result<usize, PoolError> {
+        self.reserves
+            .iter()
+            .position(|(address, _)| *address == resource_address)
+            .ok_or(PoolError::ResourceNotInPool(resource_address))
+    }
+
+    /// Runs an operation against a reserve vault, returning the vault whether it fails or not.
+    fn with_reserve<S: SystemApi, T, F: FnOnce(&mut Vault) -> Result<T, PoolError>>(
+        &self,
+        pool_address: ComponentAddress,
+        index: usize,
+        system_api: &mut S,
+        operation: F,
+    ) -> Result<T, PoolError> {
+        let vault_id = self.reserves[index].1 .0;
+        let mut vault = system_api.borrow_global_mut_vault(&pool_address, &vault_id);
+        let result = operation(&mut vault);
+        system_api.return_borrowed_global_vault(&pool_address, &vault_id, vault);
+        result
+    }
+
+    fn reserve_amounts<S: SystemApi>(
+        &self,
+        pool_address: ComponentAddress,
+        system_api: &mut S,
+    ) -> Result<Vec<(Decimal, u8)>, PoolError> {
+        let mut amounts = Vec::new();
+        for index in 0..self.reserves.len() {
+            amounts.push(self.with_reserve(pool_address, index, system_api, |vault| {
+                Ok((vault.total_amount(), vault.resource_type().divisibility()))
+            })?);
+        }
+        Ok(amounts)
+    }
+
+    fn deposit<S: SystemApi>(
+        &self,
+        pool_address: ComponentAddress,
+        index: usize,
+        container: ResourceContainer,
+        system_api: &mut S,
+    ) -> Result<(), PoolError> {
+        self.with_reserve(pool_address, index, system_api, |vault| {
+            vault.check_not_frozen().map_err(PoolError::VaultError)?;
+            vault
+                .put(Bucket::new(container))
+                .map_err(PoolError::ResourceContainerError)
+        })
+    }
+
+    fn withdraw<S: SystemApi>(
+        &self,
+        pool_address: ComponentAddress,
+        index: usize,
+        amount: Decimal,
+        system_api: &mut S,
+    ) -> Result<ResourceContainer, PoolError> {
+        self.with_reserve(pool_address, index, system_api, |vault| {
+            vault.check_not_frozen().map_err(PoolError::VaultError)?;
+            vault.take(amount).map_err(PoolError::VaultError)
+        })
+    }
+
+    fn take_container<S: SystemApi>(
+        bucket: scrypto::resource::Bucket,
+        system_api: &mut S,
+    ) -> Result<ResourceContainer, PoolError> {
+        system_api
+            .take_bucket(bucket.0)
+            .map_err(|_| PoolError::CouldNotTakeBucket)?
+            .into_container()
+            .map_err(PoolError::ResourceContainerError)
+    }
+
+    fn create_bucket<S: SystemApi>(
+        container: ResourceContainer,
+        system_api: &mut S,
+    ) -> Result<scrypto::resource::Bucket, PoolError> {
+        system_api
+            .create_bucket(container)
+            .map(scrypto::resource::Bucket)
+            .map_err(|_| PoolError::CouldNotCreateBucket)
+    }
+
+    /// Adds resources to the pool, in return for newly minted pool units.
+    ///
+    /// The first contribution must include every resource and sets the ratio of the reserves.
+    /// Later contributions are taken in the ratio of the reserves, and whatever exceeds it is
+    /// returned as change, one bucket per reserve with a contribution.
+    fn contribute<S: SystemApi>(
+        &self,
+        pool_address: ComponentAddress,
+        buckets: Vec<scrypto::resource::Bucket>,
+        system_api: &mut S,
+    ) -> Result<(scrypto::resource::Bucket, Vec<scrypto::resource::Bucket>), PoolError> {
+        let mut contributions: Vec<Option<ResourceContainer>> =
+            self.reserves.iter().map(|_| None).collect();
+        for bucket in buckets {
+            let container = Self::take_container(bucket, system_api)?;
+            let index = self.reserve_index(container.resource_address())?;
+            if let Some(contribution) = contributions[index].as_mut() {
+                contribution
+                    .put(container)
+                    .map_err(PoolError::ResourceContainerError)?;
+            } else {
+                contributions[index] = Some(container);
+            }
+        }
+        let amounts: Vec<Decimal> = contributions
+            .iter()
+            .map(|c| c.as_ref().map_or(Decimal::zero(), |c| c.total_amount()))
+            .collect();
+
+        let total_supply = self.pool_unit_total_supply(system_api)?;
+        let (taken_amounts, pool_unit_amount) = if total_supply.is_zero() {
+            for ((resource_address, _), amount) in self.reserves.iter().zip(&amounts) {
+                if !amount.is_positive() {
+                    return Err(PoolError::MissingContribution(*resource_address));
+                }
+            }
+            (amounts.clone(), amounts[0])
+        } else {
+            // Take the largest share of every reserve which all the contributions can cover
+            let reserve_amounts = self.reserve_amounts(pool_address, system_api)?;
+            let ratio = Self::min_ratio(&amounts, &reserve_amounts)?;
+            let taken_amounts = Self::share_of(&reserve_amounts, ratio);
+
+            // Rounding the amounts down to the divisibility of their resource may lower the
+            // share actually contributed, which is what the pool units are minted for
+            let ratio = Self::min_ratio(&taken_amounts, &reserve_amounts)?;
+            let pool_unit_amount =
+                (total_supply * ratio).round(DIVISIBILITY_MAXIMUM, RoundingMode::TowardsZero);
+            (taken_amounts, pool_unit_amount)
+        };
+
+        let mut change = Vec::new();
+        for (index, contribution) in contributions.into_iter().enumerate() {
+            if let Some(mut container) = contribution {
+                let taken = container
+                    .take_by_amount(taken_amounts[index])
+                    .map_err(PoolError::ResourceContainerError)?;
+                self.deposit(pool_address, index, taken, system_api)?;
+                change.push(Self::create_bucket(container, system_api)?);
+            }
+        }
+
+        let mut resource_manager = system_api
+            .borrow_global_mut_resource_manager(self.pool_unit_resource_address)
+            .map_err(|_| PoolError::InvalidPoolUnit(self.pool_unit_resource_address))?;
+        let pool_units =
+            resource_manager.mint_fungible(pool_unit_amount, self.pool_unit_resource_address);
+        system_api.return_borrowed_global_resource_manager(
+            self.pool_unit_resource_address,
+            resource_manager,
+        );
+        let pool_units = pool_units.map_err(PoolError::ResourceManagerError)?;
+
+        Ok((Self::create_bucket(pool_units, system_api)?, change))
+    }
+
+    /// Returns the smallest ratio of an amount to its reserve, ignoring empty reserves.
+    fn min_ratio(
+        amounts: &[Decimal],
+        reserve_amounts: &[(Decimal, u8)],
+    ) -> Result<Decimal, PoolError> {
+        amounts
+            .iter()
+            .zip(reserve_amounts)
+            .filter(|(_, (reserve, _))| reserve.is_positive())
+            .map(|(amount, (reserve, _))| *amount / *reserve)
+            .min()
+            .ok_or(PoolError::PoolEmpty)
+    }
+
+    /// Returns the given share of every reserve, rounded down to the divisibility of its
+    /// resource.
+    fn share_of(reserve_amounts: &[(Decimal, u8)], ratio: Decimal) -> Vec<Decimal> {
+        reserve_amounts
+            .iter()
+            .map(|(reserve, divisibility)| {
+                (*reserve * ratio).round(*divisibility, RoundingMode::TowardsZero)
+            })
+            .collect()
+    }
+
+    fn pool_unit_total_supply<S: SystemApi>(
+        &self,
+        system_api: &mut S,
+    ) -> Result<Decimal, PoolError> {
+        let resource_manager = system_api
+            .borrow_global_mut_resource_manager(self.pool_unit_resource_address)
+            .map_err(|_| PoolError::InvalidPoolUnit(self.pool_unit_resource_address))?;
+        let total_supply = resource_manager.total_supply();
+        system_api.return_borrowed_global_resource_manager(
+            self.pool_unit_resource_address,
+            resource_manager,
+        );
+        Ok(total_supply)
+    }
+
+    /// Burns pool units, in return for their share of every reserve.
+    fn redeem<S: SystemApi>(
+        &self,
+        pool_address: ComponentAddress,
+        bucket: scrypto::resource::Bucket,
+        system_api: &mut S,
+    ) -> Result<Vec<scrypto::resource::Bucket>, PoolError> {
+        let container = Self::take_container(bucket, system_api)?;
+        if container.resource_address() != self.pool_unit_resource_address {
+            return Err(PoolError::InvalidPoolUnit(container.resource_address()));
+        }
+
+        let amount = container.total_amount();
+        let mut resource_manager = system_api
+            .borrow_global_mut_resource_manager(self.pool_unit_resource_address)
+            .map_err(|_| PoolError::InvalidPoolUnit(self.pool_unit_resource_address))?;
+        let total_supply = resource_manager.total_supply();
+        resource_manager.burn(amount);
+        system_api.return_borrowed_global_resource_manager(
+            self.pool_unit_resource_address,
+            resource_manager,
+        );
+        let share = if total_supply.is_zero() {
+            Decimal::zero()
+        } else {
+            amount / total_supply
+        };
+
+        let mut buckets = Vec::new();
+        for index in 0..self.reserves.len() {
+            let container = self.with_reserve(pool_address, index, system_api, |vault| {
+                vault.check_not_frozen().map_err(PoolError::VaultError)?;
+                let divisibility = vault.resource_type().divisibility();
+                let owed =
+                    (vault.total_amount() * share).round(divisibility, RoundingMode::TowardsZero);
+                vault.take(owed).map_err(PoolError::VaultError)
+            })?;
+            buckets.push(Self::create_bucket(container, system_api)?);
+        }
+        Ok(buckets)
+    }
+
+    pub fn static_main<S: SystemApi>(
+        function: &str,
+        args: Vec<ScryptoValue>,
+        system_api: &mut S,
+    ) -> Result<ScryptoValue, PoolError> {
+        match function {
+            "create" => {
+                let resource_addresses: Vec<ResourceAddress> =
+                    scrypto_decode(&args[0].raw).map_err(PoolError::InvalidRequestData)?;
+                let manager_rule: AccessRule =
+                    scrypto_decode(&args[1].raw).map_err(PoolError::InvalidRequestData)?;
+                let pool = Self::new(resource_addresses, manager_rule, system_api)?;
+                let component = Component::new(
+                    POOL_PACKAGE,
+                    "Pool".to_string(),
+                    vec![],
+                    scrypto_encode(&pool),
+                );
+                let component_address = system_api
+                    .create_component(component)
+                    .map_err(|_| PoolError::CouldNotCreateComponent)?;
+                Ok(ScryptoValue::from_value(&component_address))
+            }
+            _ => Err(PoolError::MethodNotFound(function.to_string())),
+        }
+    }
+
+    pub fn main<S: SystemApi>(
+        &self,
+        pool_address: ComponentAddress,
+        function: &str,
+        args: Vec<ScryptoValue>,
+        system_api: &mut S,
+    ) -> Result<ScryptoValue, PoolError> {
+        match function {
+            "contribute" => {
+                let buckets: Vec<scrypto::resource::Bucket> =
+                    scrypto_decode(&args[0].raw).map_err(PoolError::InvalidRequestData)?;
+                let (pool_units, change) = self.contribute(pool_address, buckets, system_api)?;
+                Ok(ScryptoValue::from_value(&(pool_units, change)))
+            }
+            "redeem" => {
+                let bucket: scrypto::resource::Bucket =
+                    scrypto_decode(&args[0].raw).map_err(PoolError::InvalidRequestData)?;
+                let buckets = self.redeem(pool_address, bucket, system_api)?;
+                Ok(ScryptoValue::from_value(&buckets))
+            }
+            "protected_deposit" => {
+                let bucket: scrypto::resource::Bucket =
+                    scrypto_decode(&args[0].raw).map_err(PoolError::InvalidRequestData)?;
+                let container = Self::take_container(bucket, system_api)?;
+                let index = self.reserve_index(container.resource_address())?;
+                self.deposit(pool_address, index, container, system_api)?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "protected_withdraw" => {
+                let resource_address: ResourceAddress =
+                    scrypto_decode(&args[0].raw).map_err(PoolError::InvalidRequestData)?;
+                let amount: Decimal =
+                    scrypto_decode(&args[1].raw).map_err(PoolError::InvalidRequestData)?;
+                let index = self.reserve_index(resource_address)?;
+                let container = self.withdraw(pool_address, index, amount, system_api)?;
+                let bucket = Self::create_bucket(container, system_api)?;
+                Ok(ScryptoValue::from_value(&bucket))
+            }
+            "get_vault_amounts" => {
+                let amounts = self.reserve_amounts(pool_address, system_api)?;
+                let vault_amounts: HashMap<ResourceAddress, Decimal> = self
+                    .reserves
+                    .iter()
+                    .map(|(resource_address, _)| *resource_address)
+                    .zip(amounts.into_iter().map(|(amount, _)| amount))
+                    .collect();
+                Ok(ScryptoValue::from_value(&vault_amounts))
+            }
+            "get_pool_unit_resource_address" => {
+                Ok(ScryptoValue::from_value(&self.pool_unit_resource_address))
+            }
+            _ => Err(PoolError::MethodNotFound(function.to_string())),
+        }
+    }
+}
@@ -10,6 +10,7 @@ use scrypto::values::*;
 
 use crate::engine::CommitReceipt;
 use crate::errors::*;
+use crate::fee::FeeSummary;
 use crate::model::*;
 
 /// Represents a transaction receipt.
@@ -19,9 +20,13 @@ pub struct Receipt {
     pub result: Result<(), RuntimeError>,
     pub outputs: Vec<ScryptoValue>,
     pub logs: Vec<(Level, String)>,
+    /// Events emitted by a successful transaction, in order.
+    pub events: Vec<Event>,
     pub new_package_addresses: Vec<PackageAddress>,
     pub new_component_addresses: Vec<ComponentAddress>,
     pub new_resource_addresses: Vec<ResourceAddress>,
+    pub state_changes: StateChanges,
+    pub fee_summary: FeeSummary,
     pub execution_time: Option<u128>,
 }
 
@@ -57,6 +62,33 @@ impl fmt::Debug for Receipt {
                 .unwrap_or(String::from("?"))
         )?;
 
+        write!(
+            f,
+            "\n{} {} XRD burned, {} XRD paid in royalties, {} XRD locked",
+            "Transaction Fee:".bold().green(),
+            self.fee_summary.burned,
+            self.fee_summary.royalties,
+            self.fee_summary.locked,
+        )?;
+
+        write!(
+            f,
+            "\n{} {} / {} @ {} XRD",
+            "Cost Units:".bold().green(),
+            self.fee_summary.cost_unit_consumed,
+            self.fee_summary.cost_unit_limit,
+            self.fee_summary.cost_unit_price,
+        )?;
+        for (i, (reason, cost_units)) in self.fee_summary.cost_unit_breakdown.iter().enumerate() {
+            write!(
+                f,
+                "\n{} {}: {}",
+                prefix!(i, self.fee_summary.cost_unit_breakdown),
+                reason,
+                cost_units
+            )?;
+        }
+
         write!(f, "\n{}", "Instructions:".bold().green())?;
         for (i, inst) in self.validated_transaction.instructions.iter().enumerate() {
             write!(
@@ -65,6 +97,20 @@ impl fmt::Debug for Receipt {
                 prefix!(i, self.validated_transaction.instructions),
                 match inst {
                     ValidatedInstruction::PublishPackage { .. } => "PublishPackage {..}".to_owned(),
+                    ValidatedInstruction::PublishPackageWithUpgradeRule { upgrade_rule, .. } => {
+                        format!(
+                            "PublishPackageWithUpgradeRule {{ upgrade_rule: {:?}, .. }}",
+                            upgrade_rule
+                        )
+                    }
+                    ValidatedInstruction::UpgradePackage {
+                        package_address, ..
+                    } => {
+                        format!(
+                            "UpgradePackage {{ package_address: {:?}, .. }}",
+                            package_address
+                        )
+                    }
                     i @ _ => format!("{:?}", i),
                 }
             )?;
@@ -87,6 +133,17 @@ impl fmt::Debug for Receipt {
             write!(f, "\n{} [{:5}] {}", prefix!(i, self.logs), l, m)?;
         }
 
+        write!(f, "\n{} {}", "Events:".bold().green(), self.events.len())?;
+        for (i, event) in self.events.iter().enumerate() {
+            write!(
+                f,
+                "\n{} {} {}",
+                prefix!(i, self.events),
+                event.name,
+                event.data
+            )?;
+        }
+
         write!(
             f,
             "\n{} {}",
@@ -121,6 +178,57 @@ impl fmt::Debug for Receipt {
             )?;
         }
 
+        write!(
+            f,
+            "\n{} {}",
+            "State Changes:".bold().green(),
+            self.state_changes.vault_changes.len()
+                + self.state_changes.component_state_changes.len()
+                + self.state_changes.supply_changes.len()
+                + self.state_changes.lazy_map_entry_changes.len()
+        )?;
+        for (i, change) in self.state_changes.vault_changes.iter().enumerate() {
+            write!(
+                f,
+                "\n{} Vault: {}{:?} {} {}",
+                prefix!(i, self.state_changes.vault_changes),
+                change.component_address,
+                change.vault_id,
+                change.resource_address,
+                match &change.balance_change {
+                    BalanceChange::Fungible(delta) => format!("{}", delta),
+                    BalanceChange::NonFungible { added, removed } =>
+                        format!("+{:?} -{:?}", added, removed),
+                }
+            )?;
+        }
+        for (i, change) in self.state_changes.component_state_changes.iter().enumerate() {
+            write!(
+                f,
+                "\n{} Component: {}",
+                prefix!(i, self.state_changes.component_state_changes),
+                change.component_address
+            )?;
+        }
+        for (i, change) in self.state_changes.supply_changes.iter().enumerate() {
+            write!(
+                f,
+                "\n{} Supply: {} {}",
+                prefix!(i, self.state_changes.supply_changes),
+                change.resource_address,
+                change.delta
+            )?;
+        }
+        for (i, change) in self.state_changes.lazy_map_entry_changes.iter().enumerate() {
+            write!(
+                f,
+                "\n{} Lazy Map: {}{:?}",
+                prefix!(i, self.state_changes.lazy_map_entry_changes),
+                change.component_address,
+                change.lazy_map_id
+            )?;
+        }
+
         Ok(())
     }
 }
@@ -6,7 +6,9 @@ use scrypto::engine::types::*;
 use scrypto::prelude::AccessRule::{AllowAll, DenyAll};
 use scrypto::prelude::ResourceMethod::Withdraw;
 use scrypto::resource::Mutability::LOCKED;
-use scrypto::resource::ResourceMethod::{Burn, Mint, UpdateMetadata, UpdateNonFungibleData};
+use scrypto::resource::ResourceMethod::{
+    Burn, Freeze, Mint, Recall, UpdateMetadata, UpdateNonFungibleData,
+};
 use scrypto::resource::*;
 use scrypto::rust::collections::*;
 use scrypto::rust::string::String;
@@ -32,6 +34,8 @@ pub enum ResourceManagerError {
     InvalidResourceFlags(u64),
     InvalidMintPermission,
     ResourceTypeDoesNotMatch,
+    NonFungibleIdTypeDoesNotMatch(NonFungibleIdType, NonFungibleIdType),
+    InvalidNonFungibleId(NonFungibleId),
     MaxMintAmountExceeded,
     InvalidNonFungibleData,
     NonFungibleAlreadyExists(NonFungibleAddress),
@@ -120,9 +124,13 @@ impl ResourceManager {
         method_table.insert("mint".to_string(), Some(Mint));
         method_table.insert("burn".to_string(), Some(Burn));
         method_table.insert("take_from_vault".to_string(), Some(Withdraw));
+        method_table.insert("lock_fee".to_string(), Some(Withdraw));
         method_table.insert("put_into_vault".to_string(), Some(Deposit));
         method_table.insert("update_metadata".to_string(), Some(UpdateMetadata));
-        if let ResourceType::NonFungible = resource_type {
+        method_table.insert("recall_vault".to_string(), Some(Recall));
+        method_table.insert("freeze_vault".to_string(), Some(Freeze));
+        method_table.insert("unfreeze_vault".to_string(), Some(Freeze));
+        if let ResourceType::NonFungible { .. } = resource_type {
             method_table.insert("take_non_fungibles_from_vault".to_string(), Some(Withdraw));
         }
 
@@ -139,6 +147,7 @@ impl ResourceManager {
             "get_bucket_resource_address",
             "get_vault_amount",
             "get_vault_resource_address",
+            "is_vault_frozen",
             "create_vault_proof",
             "create_vault_proof_by_amount",
             "create_vault_proof_by_ids",
@@ -146,7 +155,7 @@ impl ResourceManager {
             method_table.insert(pub_method.to_string(), None);
         }
 
-        if let ResourceType::NonFungible = resource_type {
+        if let ResourceType::NonFungible { .. } = resource_type {
             method_table.insert(
                 "update_non_fungible_mutable_data".to_string(),
                 Some(UpdateNonFungibleData),
@@ -170,6 +179,8 @@ impl ResourceManager {
             (Deposit, (AllowAll, LOCKED)),
             (UpdateMetadata, (DenyAll, LOCKED)),
             (UpdateNonFungibleData, (DenyAll, LOCKED)),
+            (Recall, (DenyAll, LOCKED)),
+            (Freeze, (DenyAll, LOCKED)),
         ] {
             let entry = auth.remove(&auth_entry_key).unwrap_or(default);
             authorization.insert(auth_entry_key, MethodEntry::new(entry));
@@ -280,15 +291,29 @@ impl ResourceManager {
         Ok(validated)
     }
 
-    fn mint_non_fungibles<S: SystemApi>(
+    pub fn mint_non_fungibles<S: SystemApi>(
         &mut self,
         entries: HashMap<NonFungibleId, (Vec<u8>, Vec<u8>)>,
         self_address: ResourceAddress,
         system_api: &mut S,
     ) -> Result<ResourceContainer, ResourceManagerError> {
         // check resource type
-        if !matches!(self.resource_type, ResourceType::NonFungible) {
-            return Err(ResourceManagerError::ResourceTypeDoesNotMatch);
+        let id_type = match self.resource_type {
+            ResourceType::NonFungible { id_type } => id_type,
+            _ => return Err(ResourceManagerError::ResourceTypeDoesNotMatch),
+        };
+
+        // check non-fungible ids
+        for id in entries.keys() {
+            if id.id_type() != id_type {
+                return Err(ResourceManagerError::NonFungibleIdTypeDoesNotMatch(
+                    id.id_type(),
+                    id_type,
+                ));
+            }
+            if id.validate().is_err() {
+                return Err(ResourceManagerError::InvalidNonFungibleId(id.clone()));
+            }
         }
 
         // check amount
@@ -321,7 +346,11 @@ impl ResourceManager {
             ids.insert(id);
         }
 
-        Ok(ResourceContainer::new_non_fungible(self_address, ids))
+        Ok(ResourceContainer::new_non_fungible(
+            self_address,
+            id_type,
+            ids,
+        ))
     }
 
     pub fn burn(&mut self, amount: Decimal) {
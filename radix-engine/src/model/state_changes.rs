@@ -0,0 +1,130 @@
+use scrypto::engine::types::*;
+use scrypto::rust::collections::*;
+use scrypto::rust::vec::Vec;
+
+use crate::model::Vault;
+
+/// The change in the content of a vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BalanceChange {
+    /// Amount added to the vault, negative if withdrawn.
+    Fungible(Decimal),
+    NonFungible {
+        added: BTreeSet<NonFungibleId>,
+        removed: BTreeSet<NonFungibleId>,
+    },
+}
+
+impl BalanceChange {
+    /// Compares the content of a vault before and after a transaction.
+    ///
+    /// Returns `None` if the content is unchanged.
+    pub fn between(before: Option<&Vault>, after: &Vault) -> Option<Self> {
+        let change = match after.resource_type() {
+            ResourceType::NonFungible { .. } => {
+                let ids_after = after.total_ids().unwrap();
+                let ids_before = before
+                    .map(|v| v.total_ids().unwrap())
+                    .unwrap_or_default();
+                let added: BTreeSet<NonFungibleId> =
+                    ids_after.difference(&ids_before).cloned().collect();
+                let removed: BTreeSet<NonFungibleId> =
+                    ids_before.difference(&ids_after).cloned().collect();
+                if added.is_empty() && removed.is_empty() {
+                    return None;
+                }
+                BalanceChange::NonFungible { added, removed }
+            }
+            ResourceType::Fungible { .. } => {
+                let amount_before = before.map(|v| v.total_amount()).unwrap_or_default();
+                let delta = after.total_amount() - amount_before;
+                if delta.is_zero() {
+                    return None;
+                }
+                BalanceChange::Fungible(delta)
+            }
+        };
+        Some(change)
+    }
+}
+
+/// A change in the content of a vault owned by a component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultChange {
+    pub component_address: ComponentAddress,
+    pub vault_id: VaultId,
+    pub resource_address: ResourceAddress,
+    pub balance_change: BalanceChange,
+}
+
+/// A change of component state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentStateChange {
+    pub component_address: ComponentAddress,
+    /// The state before the transaction, or `None` if the component is new.
+    pub before: Option<Vec<u8>>,
+    pub after: Vec<u8>,
+}
+
+/// A change in the total supply of a resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupplyChange {
+    pub resource_address: ResourceAddress,
+    /// Amount minted, negative if burned.
+    pub delta: Decimal,
+}
+
+/// A lazy map entry written by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LazyMapEntryChange {
+    pub component_address: ComponentAddress,
+    pub lazy_map_id: LazyMapId,
+    pub key: Vec<u8>,
+    /// The new value, or `None` if the entry is removed.
+    pub value: Option<Vec<u8>>,
+}
+
+/// The state changes committed by a transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateChanges {
+    pub vault_changes: Vec<VaultChange>,
+    pub component_state_changes: Vec<ComponentStateChange>,
+    pub supply_changes: Vec<SupplyChange>,
+    pub lazy_map_entry_changes: Vec<LazyMapEntryChange>,
+}
+
+impl StateChanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vault_changes.is_empty()
+            && self.component_state_changes.is_empty()
+            && self.supply_changes.is_empty()
+            && self.lazy_map_entry_changes.is_empty()
+    }
+
+    /// Returns the net change in the amount of a resource held by a component.
+    ///
+    /// For a non-fungible resource, this is the number of non-fungibles added minus the number
+    /// removed.
+    pub fn balance_change(
+        &self,
+        component_address: ComponentAddress,
+        resource_address: ResourceAddress,
+    ) -> Decimal {
+        self.vault_changes
+            .iter()
+            .filter(|c| {
+                c.component_address == component_address && c.resource_address == resource_address
+            })
+            .map(|c| match &c.balance_change {
+                BalanceChange::Fungible(delta) => *delta,
+                BalanceChange::NonFungible { added, removed } => {
+                    Decimal::from(added.len() as u64) - Decimal::from(removed.len() as u64)
+                }
+            })
+            .sum()
+    }
+}
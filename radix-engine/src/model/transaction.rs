@@ -1,21 +1,100 @@
+use sbor::any::{traverse_any, CustomValueVisitor};
+use sbor::path::MutableSborPath;
 use sbor::*;
 use scrypto::buffer::scrypto_encode;
 use scrypto::crypto::*;
+use scrypto::component::RoyaltyConfig;
 use scrypto::engine::types::*;
-use scrypto::rust::collections::BTreeSet;
+use scrypto::resource::AccessRule;
+use scrypto::rust::collections::{BTreeSet, HashMap, HashSet};
 use scrypto::rust::string::String;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
+use scrypto::types::ScryptoType;
 use scrypto::values::*;
 
 use crate::engine::*;
 use crate::errors::*;
 use crate::model::{ValidatedInstruction, ValidatedTransaction};
 
+/// The current version of the transaction format.
+pub const TRANSACTION_VERSION_V1: u8 = 1;
+
+/// The maximum number of epochs a transaction can be valid for.
+///
+/// This bounds how long a committed intent has to be remembered to reject replays.
+pub const MAX_EPOCH_RANGE: u64 = 100;
+
+/// Identifies the network a transaction is intended for.
+#[derive(Debug, Clone, Copy, TypeId, Encode, Decode, PartialEq, Eq)]
+pub enum Network {
+    LocalSimulator,
+    InternalTestnet,
+    Mainnet,
+}
+
+/// Represents the header of a transaction, which is covered by the signatures.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct TransactionHeader {
+    /// The version of the transaction format
+    pub version: u8,
+    /// The network the transaction is intended for
+    pub network: Network,
+    /// The first epoch in which the transaction can be committed
+    pub start_epoch_inclusive: u64,
+    /// The epoch from which the transaction is no longer valid
+    pub end_epoch_exclusive: u64,
+    /// The key the transaction has to be notarized by, if any
+    pub notary_public_key: Option<PublicKey>,
+    /// The tip paid on top of the cost unit price, in percent
+    pub tip_percentage: u32,
+}
+
+impl TransactionHeader {
+    /// Creates a header for the local simulator, which is valid for `MAX_EPOCH_RANGE` epochs
+    /// from the given one.
+    pub fn new(current_epoch: u64) -> Self {
+        Self {
+            version: TRANSACTION_VERSION_V1,
+            network: Network::LocalSimulator,
+            start_epoch_inclusive: current_epoch,
+            end_epoch_exclusive: current_epoch.saturating_add(MAX_EPOCH_RANGE),
+            notary_public_key: None,
+            tip_percentage: 0,
+        }
+    }
+
+    /// Checks that the transaction can be committed in the given epoch.
+    pub fn check_epoch(&self, current_epoch: u64) -> Result<(), TransactionValidationError> {
+        if current_epoch < self.start_epoch_inclusive {
+            return Err(TransactionValidationError::TransactionNotYetValid(
+                current_epoch,
+            ));
+        }
+        if current_epoch >= self.end_epoch_exclusive {
+            return Err(TransactionValidationError::TransactionExpired(current_epoch));
+        }
+        Ok(())
+    }
+}
+
+impl Default for TransactionHeader {
+    /// Creates a header for the local simulator, which is valid for `MAX_EPOCH_RANGE` epochs
+    /// from genesis.
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
 /// Represents an unsigned transaction
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct Transaction {
+    pub header: TransactionHeader,
     pub instructions: Vec<Instruction>,
+    /// Large payloads, such as package code, which instructions refer to by hash.
+    ///
+    /// Blobs are not covered by the signatures, as their hashes already are.
+    pub blobs: Vec<Vec<u8>>,
 }
 
 /// Represents a signed transaction
@@ -23,7 +102,7 @@ pub struct SignedTransaction {
     /// The unsigned transaction
     pub transaction: Transaction,
     /// The signatures. Public keys are for signature algorithm that doesn't support public key recovery, e.g. ed25519.
-    pub signatures: Vec<(EcdsaPublicKey, EcdsaSignature)>,
+    pub signatures: Vec<(PublicKey, Signature)>,
 }
 
 
@@ -122,8 +201,66 @@ pub enum Instruction {
         method: String,
     },
 
-    /// Publishes a package.
-    PublishPackage { code: Vec<u8> },
+    /// Publishes a package, whose code is the blob of the given hash.
+    PublishPackage { code: Hash },
+
+    /// Publishes a package which can be upgraded by whoever satisfies the rule.
+    PublishPackageWithUpgradeRule {
+        code: Hash,
+        upgrade_rule: AccessRule,
+    },
+
+    /// Publishes a new version of a package, whose code is the blob of the given hash.
+    UpgradePackage {
+        package_address: PackageAddress,
+        code: Hash,
+    },
+
+    /// Migrates a component to a newer version of its package.
+    MigrateComponent {
+        component_address: ComponentAddress,
+        package_address: PackageAddress,
+        function: String,
+    },
+
+    /// Sets the royalties charged to callers of a blueprint.
+    ///
+    /// The upgrade rule of the package must be satisfied by the auth zone.
+    SetPackageRoyaltyConfig {
+        package_address: PackageAddress,
+        blueprint_name: String,
+        royalty_config: RoyaltyConfig,
+    },
+
+    /// Claims the royalties collected by a package, and puts them on the worktop.
+    ///
+    /// The upgrade rule of the package must be satisfied by the auth zone.
+    ClaimPackageRoyalty { package_address: PackageAddress },
+
+    /// Replaces the metadata of a package.
+    ///
+    /// The upgrade rule of the package must be satisfied by the auth zone.
+    SetPackageMetadata {
+        package_address: PackageAddress,
+        metadata: HashMap<String, String>,
+    },
+
+    /// Replaces the metadata of a component.
+    ///
+    /// The metadata update rule of the component must be satisfied by the auth zone.
+    SetComponentMetadata {
+        component_address: ComponentAddress,
+        metadata: HashMap<String, String>,
+    },
+
+    /// Recalls resources from a vault of a component, and puts them on the worktop.
+    ///
+    /// The recall rule of the resource must be satisfied by the auth zone.
+    RecallVault {
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        amount: Decimal,
+    },
 
     /// Specifies transaction nonce
     Nonce {
@@ -136,47 +273,73 @@ impl Transaction {
         scrypto_encode(self)
     }
 
+    /// Returns the bytes covered by the signatures, which leave out the blobs.
+    pub fn signed_payload(&self) -> Vec<u8> {
+        scrypto_encode(&(self.header.clone(), self.instructions.clone()))
+    }
+
     pub fn raw_hash(&self) -> Hash {
-        hash(self.to_vec())
+        hash(self.signed_payload())
     }
 
     pub fn add_nonce(&mut self, nonce: u64) {
         self.instructions.push(Instruction::Nonce { nonce });
     }
 
-    // TODO: introduce a `Signer` trait
     pub fn sign<'a, T: AsRef<[&'a EcdsaPrivateKey]>>(self, sks: T) -> SignedTransaction {
-        let msg = self.to_vec();
-        let signatures = sks
-            .as_ref()
-            .iter()
-            .map(|sk| (sk.public_key(), sk.sign(&msg)))
-            .collect();
-
-        SignedTransaction {
+        let mut signed = SignedTransaction {
             transaction: self,
-            signatures: signatures,
+            signatures: Vec::new(),
+        };
+        for sk in sks.as_ref() {
+            signed = signed.sign_with(*sk);
         }
+        signed
     }
-}
 
-impl SignedTransaction {
-    pub fn validate(&self) -> Result<ValidatedTransaction, TransactionValidationError> {
+    /// Validates this transaction as if it were signed by the given public keys, without
+    /// checking any signature.
+    ///
+    /// This is only meant for previewing transactions.
+    pub fn validate_with_signers(
+        &self,
+        network: Network,
+        current_epoch: u64,
+        signers: Vec<PublicKey>,
+    ) -> Result<ValidatedTransaction, TransactionValidationError> {
         let mut instructions = vec![];
-        let mut signers = vec![];
 
-        // verify signature (may defer to runtime)
-        let msg = self.transaction.to_vec();
-        for (pk, sig) in &self.signatures {
-            if !EcdsaVerifier::verify(&msg, pk, sig) {
-                return Err(TransactionValidationError::InvalidSignature);
-            }
-            signers.push(pk.clone());
+        // verify header
+        let header = &self.header;
+        if header.version != TRANSACTION_VERSION_V1 {
+            return Err(TransactionValidationError::UnsupportedVersion(
+                header.version,
+            ));
         }
+        if header.network != network {
+            return Err(TransactionValidationError::InvalidNetwork(header.network));
+        }
+        if header.start_epoch_inclusive >= header.end_epoch_exclusive
+            || header.end_epoch_exclusive - header.start_epoch_inclusive > MAX_EPOCH_RANGE
+        {
+            return Err(TransactionValidationError::InvalidEpochRange);
+        }
+        header.check_epoch(current_epoch)?;
+
+        // index blobs, so that each of them is hashed only once
+        let blobs: HashMap<Hash, &Vec<u8>> =
+            self.blobs.iter().map(|blob| (hash(blob), blob)).collect();
+        let resolve_blob = |blob_hash: Hash| {
+            blobs
+                .get(&blob_hash)
+                .map(|blob| blob.to_vec())
+                .ok_or(TransactionValidationError::BlobNotFound(blob_hash))
+        };
 
         // semantic analysis
         let mut id_validator = IdValidator::new();
-        for inst in &self.transaction.instructions {
+        let mut referenced_blobs = HashSet::new();
+        for inst in &self.instructions {
             match inst.clone() {
                 Instruction::TakeFromWorktop { resource_address } => {
                     id_validator
@@ -306,11 +469,13 @@ impl SignedTransaction {
                     function,
                     args,
                 } => {
+                    let args = Self::validate_args(args, &mut id_validator)?;
+                    referenced_blobs.extend(Self::hashes_in_args(&args));
                     instructions.push(ValidatedInstruction::CallFunction {
                         package_address,
                         blueprint_name,
                         function,
-                        args: Self::validate_args(args, &mut id_validator)?,
+                        args,
                     });
                 }
                 Instruction::CallMethod {
@@ -318,10 +483,12 @@ impl SignedTransaction {
                     method,
                     args,
                 } => {
+                    let args = Self::validate_args(args, &mut id_validator)?;
+                    referenced_blobs.extend(Self::hashes_in_args(&args));
                     instructions.push(ValidatedInstruction::CallMethod {
                         component_address,
                         method,
-                        args: Self::validate_args(args, &mut id_validator)?,
+                        args,
                     });
                 }
                 Instruction::CallMethodWithAllResources {
@@ -337,7 +504,82 @@ impl SignedTransaction {
                     });
                 }
                 Instruction::PublishPackage { code } => {
-                    instructions.push(ValidatedInstruction::PublishPackage { code });
+                    referenced_blobs.insert(code);
+                    instructions.push(ValidatedInstruction::PublishPackage {
+                        code: resolve_blob(code)?,
+                    });
+                }
+                Instruction::PublishPackageWithUpgradeRule { code, upgrade_rule } => {
+                    referenced_blobs.insert(code);
+                    instructions.push(ValidatedInstruction::PublishPackageWithUpgradeRule {
+                        code: resolve_blob(code)?,
+                        upgrade_rule,
+                    });
+                }
+                Instruction::UpgradePackage {
+                    package_address,
+                    code,
+                } => {
+                    referenced_blobs.insert(code);
+                    instructions.push(ValidatedInstruction::UpgradePackage {
+                        package_address,
+                        code: resolve_blob(code)?,
+                    });
+                }
+                Instruction::MigrateComponent {
+                    component_address,
+                    package_address,
+                    function,
+                } => {
+                    instructions.push(ValidatedInstruction::MigrateComponent {
+                        component_address,
+                        package_address,
+                        function,
+                    });
+                }
+                Instruction::SetPackageRoyaltyConfig {
+                    package_address,
+                    blueprint_name,
+                    royalty_config,
+                } => {
+                    instructions.push(ValidatedInstruction::SetPackageRoyaltyConfig {
+                        package_address,
+                        blueprint_name,
+                        royalty_config,
+                    });
+                }
+                Instruction::ClaimPackageRoyalty { package_address } => {
+                    instructions
+                        .push(ValidatedInstruction::ClaimPackageRoyalty { package_address });
+                }
+                Instruction::SetPackageMetadata {
+                    package_address,
+                    metadata,
+                } => {
+                    instructions.push(ValidatedInstruction::SetPackageMetadata {
+                        package_address,
+                        metadata,
+                    });
+                }
+                Instruction::SetComponentMetadata {
+                    component_address,
+                    metadata,
+                } => {
+                    instructions.push(ValidatedInstruction::SetComponentMetadata {
+                        component_address,
+                        metadata,
+                    });
+                }
+                Instruction::RecallVault {
+                    component_address,
+                    vault_id,
+                    amount,
+                } => {
+                    instructions.push(ValidatedInstruction::RecallVault {
+                        component_address,
+                        vault_id,
+                        amount,
+                    });
                 }
                 Instruction::Nonce { .. } => {
                     // TODO: validate nonce
@@ -345,8 +587,17 @@ impl SignedTransaction {
             }
         }
 
+        // blobs are only paid for by the instructions which use them
+        if let Some(blob_hash) = blobs
+            .keys()
+            .find(|blob_hash| !referenced_blobs.contains(*blob_hash))
+        {
+            return Err(TransactionValidationError::UnreferencedBlob(*blob_hash));
+        }
+
         Ok(ValidatedTransaction {
-            raw_hash: self.transaction.raw_hash(),
+            raw_hash: self.raw_hash(),
+            header: header.clone(),
             instructions,
             signers,
         })
@@ -377,6 +628,67 @@ impl SignedTransaction {
         }
         Ok(result)
     }
+
+    /// Returns the hashes in call arguments, as a `Blob` value is passed by its hash.
+    fn hashes_in_args(args: &[ScryptoValue]) -> Vec<Hash> {
+        let mut collector = HashCollector(Vec::new());
+        for arg in args {
+            traverse_any(&mut MutableSborPath::new(), &arg.dom, &mut collector).unwrap();
+        }
+        collector.0
+    }
+}
+
+/// Collects the hashes in a value.
+struct HashCollector(Vec<Hash>);
+
+impl CustomValueVisitor for HashCollector {
+    type Err = ();
+
+    fn visit(&mut self, _: &mut MutableSborPath, type_id: u8, data: &[u8]) -> Result<(), ()> {
+        if type_id == ScryptoType::Hash.id() {
+            if let Ok(hash) = Hash::try_from(data) {
+                self.0.push(hash);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SignedTransaction {
+    /// Adds a signature of the given signer, of any supported scheme.
+    pub fn sign_with<S: Signer>(mut self, signer: &S) -> Self {
+        let msg = self.transaction.signed_payload();
+        self.signatures
+            .push((signer.public_key(), signer.sign(&msg)));
+        self
+    }
+
+    /// Validates this transaction against the network and the current epoch of a ledger.
+    pub fn validate(
+        &self,
+        network: Network,
+        current_epoch: u64,
+    ) -> Result<ValidatedTransaction, TransactionValidationError> {
+        let mut signers = vec![];
+
+        // verify signature (may defer to runtime)
+        let msg = self.transaction.signed_payload();
+        for (pk, sig) in &self.signatures {
+            if !SignatureVerifier::verify(&msg, pk, sig) {
+                return Err(TransactionValidationError::InvalidSignature);
+            }
+            signers.push(pk.clone());
+        }
+        if let Some(notary_public_key) = &self.transaction.header.notary_public_key {
+            if !signers.contains(notary_public_key) {
+                return Err(TransactionValidationError::MissingNotarySignature);
+            }
+        }
+
+        self.transaction
+            .validate_with_signers(network, current_epoch, signers)
+    }
 }
 
 #[cfg(test)]
@@ -392,6 +704,7 @@ mod tests {
         assert_eq!(
             SignedTransaction {
                 transaction: Transaction {
+                    header: TransactionHeader::default(),
                     instructions: vec![Instruction::CallMethod {
                         component_address: ComponentAddress([1u8; 26]),
                         method: "test".to_owned(),
@@ -400,10 +713,11 @@ mod tests {
                             0,
                         )))],
                     }],
+                    blobs: Vec::new(),
                 },
                 signatures: Vec::new(),
             }
-            .validate(),
+            .validate(Network::LocalSimulator, 0),
             Err(TransactionValidationError::VaultNotAllowed((
                 Hash([2u8; 32]),
                 0,
@@ -416,6 +730,7 @@ mod tests {
         assert_eq!(
             SignedTransaction {
                 transaction: Transaction {
+                    header: TransactionHeader::default(),
                     instructions: vec![Instruction::CallMethod {
                         component_address: ComponentAddress([1u8; 26]),
                         method: "test".to_owned(),
@@ -425,14 +740,146 @@ mod tests {
                             value: PhantomData,
                         })],
                     }],
+                    blobs: Vec::new(),
                 },
                 signatures: Vec::new()
             }
-            .validate(),
+            .validate(Network::LocalSimulator, 0),
             Err(TransactionValidationError::LazyMapNotAllowed((
                 Hash([2u8; 32]),
                 0,
             ))),
         );
     }
+
+    fn transaction_with_header(header: TransactionHeader) -> SignedTransaction {
+        SignedTransaction {
+            transaction: Transaction {
+                header,
+                instructions: Vec::new(),
+                blobs: Vec::new(),
+            },
+            signatures: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn should_reject_transaction_for_other_network() {
+        let transaction = transaction_with_header(TransactionHeader {
+            network: Network::Mainnet,
+            ..TransactionHeader::default()
+        });
+        assert_eq!(
+            transaction.validate(Network::LocalSimulator, 0),
+            Err(TransactionValidationError::InvalidNetwork(Network::Mainnet))
+        );
+    }
+
+    #[test]
+    fn should_reject_transaction_outside_epoch_range() {
+        let transaction = transaction_with_header(TransactionHeader {
+            start_epoch_inclusive: 10,
+            end_epoch_exclusive: 20,
+            ..TransactionHeader::default()
+        });
+        assert_eq!(
+            transaction.validate(Network::LocalSimulator, 9),
+            Err(TransactionValidationError::TransactionNotYetValid(9))
+        );
+        assert!(transaction.validate(Network::LocalSimulator, 19).is_ok());
+        assert_eq!(
+            transaction.validate(Network::LocalSimulator, 20),
+            Err(TransactionValidationError::TransactionExpired(20))
+        );
+    }
+
+    #[test]
+    fn should_reject_transaction_valid_for_too_many_epochs() {
+        let transaction = transaction_with_header(TransactionHeader {
+            start_epoch_inclusive: 10,
+            end_epoch_exclusive: 10 + MAX_EPOCH_RANGE + 1,
+            ..TransactionHeader::default()
+        });
+        assert_eq!(
+            transaction.validate(Network::LocalSimulator, 10),
+            Err(TransactionValidationError::InvalidEpochRange)
+        );
+        assert!(transaction_with_header(TransactionHeader::new(500))
+            .validate(Network::LocalSimulator, 500 + MAX_EPOCH_RANGE - 1)
+            .is_ok());
+    }
+
+    #[test]
+    fn should_reject_transaction_not_signed_by_notary() {
+        let notary = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let transaction = Transaction {
+            header: TransactionHeader {
+                notary_public_key: Some(notary.public_key().into()),
+                ..TransactionHeader::default()
+            },
+            instructions: Vec::new(),
+            blobs: Vec::new(),
+        };
+        assert_eq!(
+            transaction.clone().sign([]).validate(Network::LocalSimulator, 0),
+            Err(TransactionValidationError::MissingNotarySignature)
+        );
+        assert!(transaction
+            .sign([&notary])
+            .validate(Network::LocalSimulator, 0)
+            .is_ok());
+    }
+
+    #[test]
+    fn should_resolve_blobs_by_hash() {
+        let code = vec![1u8, 2u8, 3u8];
+        let mut transaction = Transaction {
+            header: TransactionHeader::default(),
+            instructions: vec![Instruction::PublishPackage { code: hash(&code) }],
+            blobs: Vec::new(),
+        };
+        assert_eq!(
+            transaction
+                .clone()
+                .sign([])
+                .validate(Network::LocalSimulator, 0),
+            Err(TransactionValidationError::BlobNotFound(hash(&code)))
+        );
+
+        transaction.blobs.push(code.clone());
+        assert_eq!(
+            transaction
+                .sign([])
+                .validate(Network::LocalSimulator, 0)
+                .map(|validated| validated.instructions),
+            Ok(vec![ValidatedInstruction::PublishPackage { code }])
+        );
+    }
+
+    #[test]
+    fn should_reject_unreferenced_blobs() {
+        let code = vec![1u8, 2u8, 3u8];
+        let mut transaction = Transaction {
+            header: TransactionHeader::default(),
+            instructions: Vec::new(),
+            blobs: vec![code.clone()],
+        };
+        assert_eq!(
+            transaction
+                .clone()
+                .sign([])
+                .validate(Network::LocalSimulator, 0),
+            Err(TransactionValidationError::UnreferencedBlob(hash(&code)))
+        );
+
+        transaction.instructions.push(Instruction::CallMethod {
+            component_address: ComponentAddress([1u8; 26]),
+            method: "store".to_owned(),
+            args: vec![scrypto_encode(&hash(&code))],
+        });
+        assert!(transaction
+            .sign([])
+            .validate(Network::LocalSimulator, 0)
+            .is_ok());
+    }
 }
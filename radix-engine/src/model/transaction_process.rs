@@ -1,4 +1,6 @@
-use scrypto::core::SNodeRef;
+use sbor::any::encode_any;
+use sbor::Encoder;
+use scrypto::core::{Expression, SNodeRef};
 use scrypto::engine::types::*;
 use scrypto::prelude::ScryptoActor;
 use scrypto::rust::collections::{HashMap};
@@ -44,6 +46,46 @@ impl TransactionProcess {
         Ok(values)
     }
 
+    /// Replaces the manifest expressions in the values with the resources they stand for, taken
+    /// from the worktop or the auth zone.
+    fn resolve_expressions<S: SystemApi>(
+        &mut self,
+        values: Vec<ScryptoValue>,
+        system_api: &mut S,
+    ) -> Result<Vec<ScryptoValue>, RuntimeError> {
+        let mut resolved = Vec::new();
+        for mut value in values {
+            if value.expressions.is_empty() {
+                resolved.push(value);
+                continue;
+            }
+
+            for (expression, path) in value.expressions.clone() {
+                let resources = match expression {
+                    Expression::EntireWorktop => system_api.invoke_snode(
+                        SNodeRef::WorktopRef,
+                        "drain".to_string(),
+                        vec![],
+                    )?,
+                    Expression::EntireAuthZone => system_api.invoke_snode(
+                        SNodeRef::AuthZoneRef,
+                        "drain".to_string(),
+                        vec![],
+                    )?,
+                };
+                *path.get_from_value_mut(&mut value.dom).unwrap() = resources.dom;
+            }
+
+            let mut bytes = Vec::new();
+            let mut enc = Encoder::with_type(&mut bytes);
+            encode_any(None, &value.dom, &mut enc);
+            resolved.push(
+                ScryptoValue::from_slice(&bytes).map_err(RuntimeError::ParseScryptoValueError)?,
+            );
+        }
+        Ok(resolved)
+    }
+
     pub fn outputs(&self) -> &[ScryptoValue] {
         &self.outputs
     }
@@ -300,6 +342,7 @@ impl TransactionProcess {
                     args,
                 } => {
                     self.replace_ids(args.clone())
+                        .and_then(|args| self.resolve_expressions(args, system_api))
                         .and_then(|args|
                             system_api.invoke_snode(
                                 SNodeRef::Scrypto(ScryptoActor::Blueprint(*package_address, blueprint_name.to_string())),
@@ -333,6 +376,7 @@ impl TransactionProcess {
                     args,
                 } => {
                     self.replace_ids(args.clone())
+                        .and_then(|args| self.resolve_expressions(args, system_api))
                         .and_then(|args|
                             system_api.invoke_snode(
                                 SNodeRef::Scrypto(ScryptoActor::Component(*component_address)),
@@ -397,6 +441,115 @@ impl TransactionProcess {
                         vec![ScryptoValue::from_value(code)],
                     )
                 },
+                ValidatedInstruction::PublishPackageWithUpgradeRule { code, upgrade_rule } => {
+                    system_api.invoke_snode(
+                        SNodeRef::PackageStatic,
+                        "publish_with_upgrade_rule".to_string(),
+                        vec![
+                            ScryptoValue::from_value(code),
+                            ScryptoValue::from_value(upgrade_rule),
+                        ],
+                    )
+                },
+                ValidatedInstruction::UpgradePackage { package_address, code } => {
+                    system_api.invoke_snode(
+                        SNodeRef::PackageStatic,
+                        "upgrade".to_string(),
+                        vec![
+                            ScryptoValue::from_value(package_address),
+                            ScryptoValue::from_value(code),
+                        ],
+                    )
+                },
+                ValidatedInstruction::MigrateComponent {
+                    component_address,
+                    package_address,
+                    function,
+                } => {
+                    system_api.invoke_snode(
+                        SNodeRef::MigrateComponent(*component_address, *package_address),
+                        function.to_string(),
+                        vec![],
+                    )
+                },
+                ValidatedInstruction::SetPackageRoyaltyConfig {
+                    package_address,
+                    blueprint_name,
+                    royalty_config,
+                } => {
+                    system_api.invoke_snode(
+                        SNodeRef::PackageStatic,
+                        "set_royalty_config".to_string(),
+                        vec![
+                            ScryptoValue::from_value(package_address),
+                            ScryptoValue::from_value(blueprint_name),
+                            ScryptoValue::from_value(royalty_config),
+                        ],
+                    )
+                },
+                ValidatedInstruction::ClaimPackageRoyalty { package_address } => {
+                    system_api.invoke_snode(
+                        SNodeRef::PackageStatic,
+                        "claim_royalty".to_string(),
+                        vec![ScryptoValue::from_value(package_address)],
+                    )
+                    .and_then(|result| {
+                        // Auto move into worktop
+                        for (bucket_id, _) in &result.bucket_ids {
+                            system_api.invoke_snode(
+                                SNodeRef::WorktopRef,
+                                "put".to_string(),
+                                vec![ScryptoValue::from_value(&scrypto::resource::Bucket(*bucket_id))]
+                            ).unwrap(); // TODO: Remove unwrap
+                        }
+                        Ok(result)
+                    })
+                },
+                ValidatedInstruction::SetPackageMetadata {
+                    package_address,
+                    metadata,
+                } => {
+                    system_api.invoke_snode(
+                        SNodeRef::PackageStatic,
+                        "set_metadata".to_string(),
+                        vec![
+                            ScryptoValue::from_value(package_address),
+                            ScryptoValue::from_value(metadata),
+                        ],
+                    )
+                },
+                ValidatedInstruction::SetComponentMetadata {
+                    component_address,
+                    metadata,
+                } => {
+                    system_api.invoke_snode(
+                        SNodeRef::ComponentRef(*component_address),
+                        "update_metadata".to_string(),
+                        vec![ScryptoValue::from_value(metadata)],
+                    )
+                },
+                ValidatedInstruction::RecallVault {
+                    component_address,
+                    vault_id,
+                    amount,
+                } => {
+                    system_api.invoke_snode(
+                        SNodeRef::ComponentVaultRef(*component_address, *vault_id),
+                        "recall_vault".to_string(),
+                        vec![ScryptoValue::from_value(amount)],
+                    )
+                    .and_then(|result| {
+                        // Auto move into worktop
+                        for (bucket_id, _) in &result.bucket_ids {
+                            system_api.invoke_snode(
+                                SNodeRef::WorktopRef,
+                                "put".to_string(),
+                                vec![ScryptoValue::from_value(&scrypto::resource::Bucket(*bucket_id))]
+                            ).unwrap(); // TODO: Remove unwrap
+                        }
+                        Ok(result)
+                    })
+                },
             }?;
             self.outputs.push(result);
         }
@@ -1,16 +1,21 @@
+use scrypto::component::RoyaltyConfig;
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
-use scrypto::rust::collections::{BTreeSet};
+use scrypto::rust::collections::{BTreeSet, HashMap};
 use scrypto::rust::string::String;
 use scrypto::rust::vec::Vec;
+use scrypto::resource::AccessRule;
 use scrypto::values::*;
 
+use crate::model::TransactionHeader;
+
 /// Represents a validated transaction
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidatedTransaction {
     pub raw_hash: Hash,
+    pub header: TransactionHeader,
     pub instructions: Vec<ValidatedInstruction>,
-    pub signers: Vec<EcdsaPublicKey>,
+    pub signers: Vec<PublicKey>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -83,4 +88,38 @@ pub enum ValidatedInstruction {
     PublishPackage {
         code: Vec<u8>,
     },
+    PublishPackageWithUpgradeRule {
+        code: Vec<u8>,
+        upgrade_rule: AccessRule,
+    },
+    UpgradePackage {
+        package_address: PackageAddress,
+        code: Vec<u8>,
+    },
+    MigrateComponent {
+        component_address: ComponentAddress,
+        package_address: PackageAddress,
+        function: String,
+    },
+    SetPackageRoyaltyConfig {
+        package_address: PackageAddress,
+        blueprint_name: String,
+        royalty_config: RoyaltyConfig,
+    },
+    ClaimPackageRoyalty {
+        package_address: PackageAddress,
+    },
+    SetPackageMetadata {
+        package_address: PackageAddress,
+        metadata: HashMap<String, String>,
+    },
+    SetComponentMetadata {
+        component_address: ComponentAddress,
+        metadata: HashMap<String, String>,
+    },
+    RecallVault {
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        amount: Decimal,
+    },
 }
\ No newline at end of file
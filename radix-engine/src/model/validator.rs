@@ -0,0 +1,458 @@
+use sbor::*;
+use scrypto::buffer::{scrypto_decode, scrypto_encode};
+use scrypto::engine::types::*;
+use scrypto::math::RoundingMode;
+use scrypto::resource::{AccessRule, DIVISIBILITY_MAXIMUM};
+use scrypto::rust::collections::HashMap;
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec;
+use scrypto::rust::vec::Vec;
+use scrypto::values::ScryptoValue;
+
+use crate::engine::SystemApi;
+use crate::model::{
+    convert, Bucket, Component, EpochManager, MethodAuthorization, ResourceContainer,
+    ResourceContainerError, ResourceManager, ResourceManagerError, Vault, VaultError,
+};
+
+/// The number of epochs between unstaking XRD and being able to claim it.
+pub const UNSTAKE_DELAY: u64 = 2;
+
+/// Represents an error when accessing a validator.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidatorError {
+    InvalidRequestData(DecodeError),
+    MethodNotFound(String),
+    NotAValidator(ComponentAddress),
+    InvalidStake(ResourceAddress),
+    InvalidStakeUnit(ResourceAddress),
+    InvalidUnstakeClaim(ResourceAddress),
+    /// The unstaked XRD can be claimed from the given epoch on.
+    UnstakeNotUnlocked(u64),
+    AlreadyRegistered,
+    NotRegistered,
+    ResourceContainerError(ResourceContainerError),
+    VaultError(VaultError),
+    ResourceManagerError(ResourceManagerError),
+    CouldNotAccessEpochManager,
+    CouldNotCreateBucket,
+    CouldNotTakeBucket,
+    CouldNotCreateComponent,
+}
+
+/// A native validator, which XRD holders stake to.
+///
+/// Stakers receive stake units, which are liquid and redeemable for a share of the staked XRD,
+/// emissions included. Unstaking returns an unstake claim, a non-fungible which can be
+/// exchanged for the XRD once `UNSTAKE_DELAY` epochs have passed.
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct Validator {
+    /// The rule which guards registration.
+    owner_rule: AccessRule,
+    stake: scrypto::resource::Vault,
+    /// The XRD which has been unstaked, but not claimed yet.
+    unstaking: scrypto::resource::Vault,
+    stake_unit_resource_address: ResourceAddress,
+    unstake_claim_resource_address: ResourceAddress,
+    next_unstake_claim_id: u64,
+}
+
+impl Validator {
+    fn new<S: SystemApi>(
+        owner_rule: AccessRule,
+        system_api: &mut S,
+    ) -> Result<Self, ValidatorError> {
+        let xrd_type = ResourceType::Fungible { divisibility: 18 };
+        let stake = system_api.create_vault(ResourceContainer::new_empty(RADIX_TOKEN, xrd_type));
+        let unstaking =
+            system_api.create_vault(ResourceContainer::new_empty(RADIX_TOKEN, xrd_type));
+
+        // Stake units and unstake claims are only minted and burnt by the validator itself
+        let mut metadata = HashMap::new();
+        metadata.insert("name".to_string(), "Stake Unit".to_string());
+        let resource_manager = ResourceManager::new(
+            ResourceType::Fungible {
+                divisibility: DIVISIBILITY_MAXIMUM,
+            },
+            metadata,
+            HashMap::new(),
+        )
+        .map_err(ValidatorError::ResourceManagerError)?;
+        let stake_unit_resource_address = system_api.create_resource(resource_manager);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("name".to_string(), "Unstake Claim".to_string());
+        let resource_manager = ResourceManager::new(
+            ResourceType::NonFungible {
+                id_type: NonFungibleIdType::U64,
+            },
+            metadata,
+            HashMap::new(),
+        )
+        .map_err(ValidatorError::ResourceManagerError)?;
+        let unstake_claim_resource_address = system_api.create_resource(resource_manager);
+
+        Ok(Self {
+            owner_rule,
+            stake: scrypto::resource::Vault(stake),
+            unstaking: scrypto::resource::Vault(unstaking),
+            stake_unit_resource_address,
+            unstake_claim_resource_address,
+            next_unstake_claim_id: 0,
+        })
+    }
+
+    /// Decodes a validator from the state of its component, leaving the component unchanged.
+    pub fn load<S: SystemApi>(
+        validator_address: ComponentAddress,
+        system_api: &mut S,
+    ) -> Result<Self, ValidatorError> {
+        let component = system_api
+            .borrow_global_mut_component(validator_address)
+            .map_err(|_| ValidatorError::NotAValidator(validator_address))?;
+        let validator = if component.package_address() == VALIDATOR_PACKAGE
+            && component.blueprint_name() == "Validator"
+        {
+            scrypto_decode(component.state()).map_err(ValidatorError::InvalidRequestData)
+        } else {
+            Err(ValidatorError::NotAValidator(validator_address))
+        };
+        system_api.return_borrowed_global_component(validator_address, component);
+        validator
+    }
+
+    /// Returns the authorization of a method of this validator.
+    pub fn authorization(&self, function: &str) -> Vec<MethodAuthorization> {
+        match function {
+            "register" | "unregister" => {
+                vec![convert(&Type::Unit, &Value::Unit, &self.owner_rule)]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Runs an operation against a vault of this validator, returning the vault whether it
+    /// fails or not.
+    fn with_vault<S: SystemApi, T, F: FnOnce(&mut Vault) -> Result<T, ValidatorError>>(
+        validator_address: ComponentAddress,
+        vault: &scrypto::resource::Vault,
+        system_api: &mut S,
+        operation: F,
+    ) -> Result<T, ValidatorError> {
+        let vault_id = vault.0;
+        let mut vault = system_api.borrow_global_mut_vault(&validator_address, &vault_id);
+        let result = operation(&mut vault);
+        system_api.return_borrowed_global_vault(&validator_address, &vault_id, vault);
+        result
+    }
+
+    /// Returns the amount of XRD staked to this validator, emissions included.
+    pub fn stake_amount<S: SystemApi>(
+        &self,
+        validator_address: ComponentAddress,
+        system_api: &mut S,
+    ) -> Decimal {
+        Self::with_vault(validator_address, &self.stake, system_api, |vault| {
+            Ok(vault.total_amount())
+        })
+        .unwrap()
+    }
+
+    /// Adds XRD to the stake without minting stake units, which raises their value.
+    pub fn add_stake<S: SystemApi>(
+        &self,
+        validator_address: ComponentAddress,
+        container: ResourceContainer,
+        system_api: &mut S,
+    ) -> Result<(), ValidatorError> {
+        if container.resource_address() != RADIX_TOKEN {
+            return Err(ValidatorError::InvalidStake(container.resource_address()));
+        }
+        Self::with_vault(validator_address, &self.stake, system_api, |vault| {
+            vault
+                .put(Bucket::new(container))
+                .map_err(ValidatorError::ResourceContainerError)
+        })
+    }
+
+    fn take_container<S: SystemApi>(
+        bucket: scrypto::resource::Bucket,
+        system_api: &mut S,
+    ) -> Result<ResourceContainer, ValidatorError> {
+        system_api
+            .take_bucket(bucket.0)
+            .map_err(|_| ValidatorError::CouldNotTakeBucket)?
+            .into_container()
+            .map_err(ValidatorError::ResourceContainerError)
+    }
+
+    fn create_bucket<S: SystemApi>(
+        container: ResourceContainer,
+        system_api: &mut S,
+    ) -> Result<scrypto::resource::Bucket, ValidatorError> {
+        system_api
+            .create_bucket(container)
+            .map(scrypto::resource::Bucket)
+            .map_err(|_| ValidatorError::CouldNotCreateBucket)
+    }
+
+    fn borrow_resource_manager<S: SystemApi>(
+        resource_address: ResourceAddress,
+        system_api: &mut S,
+    ) -> Result<ResourceManager, ValidatorError> {
+        system_api
+            .borrow_global_mut_resource_manager(resource_address)
+            .map_err(|_| ValidatorError::InvalidStakeUnit(resource_address))
+    }
+
+    /// Stakes XRD, in return for stake units worth the same share of the stake.
+    fn stake<S: SystemApi>(
+        &self,
+        validator_address: ComponentAddress,
+        bucket: scrypto::resource::Bucket,
+        system_api: &mut S,
+    ) -> Result<scrypto::resource::Bucket, ValidatorError> {
+        let container = Self::take_container(bucket, system_api)?;
+        let amount = container.total_amount();
+        let staked = self.stake_amount(validator_address, system_api);
+        self.add_stake(validator_address, container, system_api)?;
+
+        let mut resource_manager =
+            Self::borrow_resource_manager(self.stake_unit_resource_address, system_api)?;
+        let total_supply = resource_manager.total_supply();
+        let stake_unit_amount = if total_supply.is_zero() || staked.is_zero() {
+            amount
+        } else {
+            (amount * total_supply / staked).round(DIVISIBILITY_MAXIMUM, RoundingMode::TowardsZero)
+        };
+        let stake_units =
+            resource_manager.mint_fungible(stake_unit_amount, self.stake_unit_resource_address);
+        system_api.return_borrowed_global_resource_manager(
+            self.stake_unit_resource_address,
+            resource_manager,
+        );
+        let stake_units = stake_units.map_err(ValidatorError::ResourceManagerError)?;
+        Self::create_bucket(stake_units, system_api)
+    }
+
+    /// Burns stake units, and locks their share of the stake until it can be claimed.
+    fn unstake<S: SystemApi>(
+        &mut self,
+        validator_address: ComponentAddress,
+        bucket: scrypto::resource::Bucket,
+        system_api: &mut S,
+    ) -> Result<scrypto::resource::Bucket, ValidatorError> {
+        let container = Self::take_container(bucket, system_api)?;
+        if container.resource_address() != self.stake_unit_resource_address {
+            return Err(ValidatorError::InvalidStakeUnit(
+                container.resource_address(),
+            ));
+        }
+
+        let amount = container.total_amount();
+        let mut resource_manager =
+            Self::borrow_resource_manager(self.stake_unit_resource_address, system_api)?;
+        let total_supply = resource_manager.total_supply();
+        resource_manager.burn(amount);
+        system_api.return_borrowed_global_resource_manager(
+            self.stake_unit_resource_address,
+            resource_manager,
+        );
+
+        let staked = self.stake_amount(validator_address, system_api);
+        let xrd_amount = if total_supply.is_zero() {
+            Decimal::zero()
+        } else {
+            (staked * amount / total_supply).round(18, RoundingMode::TowardsZero)
+        };
+        let xrd = Self::with_vault(validator_address, &self.stake, system_api, |vault| {
+            vault.take(xrd_amount).map_err(ValidatorError::VaultError)
+        })?;
+        Self::with_vault(validator_address, &self.unstaking, system_api, |vault| {
+            vault
+                .put(Bucket::new(xrd))
+                .map_err(ValidatorError::ResourceContainerError)
+        })?;
+
+        // The claim records the amount and the epoch from which it can be claimed
+        let claimable_from_epoch = system_api.get_epoch().saturating_add(UNSTAKE_DELAY);
+        let id = NonFungibleId::from_u64(self.next_unstake_claim_id);
+        self.next_unstake_claim_id += 1;
+        let mut entries = HashMap::new();
+        entries.insert(
+            id,
+            (
+                scrypto_encode(&(xrd_amount, claimable_from_epoch)),
+                scrypto_encode(&()),
+            ),
+        );
+        let mut resource_manager =
+            Self::borrow_resource_manager(self.unstake_claim_resource_address, system_api)?;
+        let claim = resource_manager.mint_non_fungibles(
+            entries,
+            self.unstake_claim_resource_address,
+            system_api,
+        );
+        system_api.return_borrowed_global_resource_manager(
+            self.unstake_claim_resource_address,
+            resource_manager,
+        );
+        let claim = claim.map_err(ValidatorError::ResourceManagerError)?;
+        Self::create_bucket(claim, system_api)
+    }
+
+    /// Burns unstake claims, in return for the XRD they record.
+    fn claim_xrd<S: SystemApi>(
+        &self,
+        validator_address: ComponentAddress,
+        bucket: scrypto::resource::Bucket,
+        system_api: &mut S,
+    ) -> Result<scrypto::resource::Bucket, ValidatorError> {
+        let container = Self::take_container(bucket, system_api)?;
+        if container.resource_address() != self.unstake_claim_resource_address {
+            return Err(ValidatorError::InvalidUnstakeClaim(
+                container.resource_address(),
+            ));
+        }
+
+        let current_epoch = system_api.get_epoch();
+        let mut xrd_amount = Decimal::zero();
+        let ids = container
+            .total_ids()
+            .map_err(ValidatorError::ResourceContainerError)?;
+        for id in &ids {
+            let non_fungible_address =
+                NonFungibleAddress::new(self.unstake_claim_resource_address, id.clone());
+            let (amount, claimable_from_epoch): (Decimal, u64) = system_api
+                .get_non_fungible(&non_fungible_address)
+                .ok_or(ValidatorError::InvalidUnstakeClaim(
+                    self.unstake_claim_resource_address,
+                ))
+                .and_then(|non_fungible| {
+                    scrypto_decode(&non_fungible.immutable_data())
+                        .map_err(ValidatorError::InvalidRequestData)
+                })?;
+            if current_epoch < claimable_from_epoch {
+                return Err(ValidatorError::UnstakeNotUnlocked(claimable_from_epoch));
+            }
+            xrd_amount += amount;
+        }
+
+        let mut resource_manager =
+            Self::borrow_resource_manager(self.unstake_claim_resource_address, system_api)?;
+        resource_manager.burn(container.total_amount());
+        system_api.return_borrowed_global_resource_manager(
+            self.unstake_claim_resource_address,
+            resource_manager,
+        );
+        for id in ids {
+            let non_fungible_address =
+                NonFungibleAddress::new(self.unstake_claim_resource_address, id);
+            system_api.set_non_fungible(non_fungible_address, None);
+        }
+
+        let xrd = Self::with_vault(validator_address, &self.unstaking, system_api, |vault| {
+            vault.take(xrd_amount).map_err(ValidatorError::VaultError)
+        })?;
+        Self::create_bucket(xrd, system_api)
+    }
+
+    /// Adds this validator to, or removes it from, the set of the epoch manager.
+    fn set_registered<S: SystemApi>(
+        validator_address: ComponentAddress,
+        registered: bool,
+        system_api: &mut S,
+    ) -> Result<(), ValidatorError> {
+        let mut component = system_api
+            .borrow_global_mut_component(EPOCH_MANAGER)
+            .map_err(|_| ValidatorError::CouldNotAccessEpochManager)?;
+        let result = scrypto_decode::<EpochManager>(component.state())
+            .map_err(|_| ValidatorError::CouldNotAccessEpochManager)
+            .and_then(|mut epoch_manager| {
+                if registered && !epoch_manager.register(validator_address) {
+                    Err(ValidatorError::AlreadyRegistered)
+                } else if !registered && !epoch_manager.unregister(validator_address) {
+                    Err(ValidatorError::NotRegistered)
+                } else {
+                    component.set_state(scrypto_encode(&epoch_manager));
+                    Ok(())
+                }
+            });
+        system_api.return_borrowed_global_component(EPOCH_MANAGER, component);
+        result
+    }
+
+    pub fn static_main<S: SystemApi>(
+        function: &str,
+        args: Vec<ScryptoValue>,
+        system_api: &mut S,
+    ) -> Result<ScryptoValue, ValidatorError> {
+        match function {
+            "create" => {
+                let owner_rule: AccessRule =
+                    scrypto_decode(&args[0].raw).map_err(ValidatorError::InvalidRequestData)?;
+                let validator = Self::new(owner_rule, system_api)?;
+                let component = Component::new(
+                    VALIDATOR_PACKAGE,
+                    "Validator".to_string(),
+                    vec![],
+                    scrypto_encode(&validator),
+                );
+                let component_address = system_api
+                    .create_component(component)
+                    .map_err(|_| ValidatorError::CouldNotCreateComponent)?;
+                Ok(ScryptoValue::from_value(&component_address))
+            }
+            _ => Err(ValidatorError::MethodNotFound(function.to_string())),
+        }
+    }
+
+    pub fn main<S: SystemApi>(
+        &mut self,
+        validator_address: ComponentAddress,
+        function: &str,
+        args: Vec<ScryptoValue>,
+        system_api: &mut S,
+    ) -> Result<ScryptoValue, ValidatorError> {
+        match function {
+            "register" => {
+                Self::set_registered(validator_address, true, system_api)?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "unregister" => {
+                Self::set_registered(validator_address, false, system_api)?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "stake" => {
+                let bucket: scrypto::resource::Bucket =
+                    scrypto_decode(&args[0].raw).map_err(ValidatorError::InvalidRequestData)?;
+                let stake_units = self.stake(validator_address, bucket, system_api)?;
+                Ok(ScryptoValue::from_value(&stake_units))
+            }
+            "unstake" => {
+                let bucket: scrypto::resource::Bucket =
+                    scrypto_decode(&args[0].raw).map_err(ValidatorError::InvalidRequestData)?;
+                let claim = self.unstake(validator_address, bucket, system_api)?;
+                Ok(ScryptoValue::from_value(&claim))
+            }
+            "claim_xrd" => {
+                let bucket: scrypto::resource::Bucket =
+                    scrypto_decode(&args[0].raw).map_err(ValidatorError::InvalidRequestData)?;
+                let xrd = self.claim_xrd(validator_address, bucket, system_api)?;
+                Ok(ScryptoValue::from_value(&xrd))
+            }
+            "get_stake_amount" => {
+                let amount = self.stake_amount(validator_address, system_api);
+                Ok(ScryptoValue::from_value(&amount))
+            }
+            "get_stake_unit_resource_address" => {
+                Ok(ScryptoValue::from_value(&self.stake_unit_resource_address))
+            }
+            "get_unstake_claim_resource_address" => Ok(ScryptoValue::from_value(
+                &self.unstake_claim_resource_address,
+            )),
+            _ => Err(ValidatorError::MethodNotFound(function.to_string())),
+        }
+    }
+}
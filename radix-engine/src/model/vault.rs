@@ -1,5 +1,6 @@
 use sbor::*;
 use scrypto::buffer::scrypto_decode;
+use scrypto::constants::RADIX_TOKEN;
 use scrypto::engine::types::*;
 use scrypto::rust::cell::{Ref, RefCell, RefMut};
 use scrypto::rust::collections::BTreeSet;
@@ -24,18 +25,45 @@ pub enum VaultError {
     CouldNotTakeBucket,
     ProofError(ProofError),
     CouldNotCreateProof,
+    LockFeeNotRadixToken,
+    CouldNotLockFee,
+    VaultFrozen,
 }
 
 /// A persistent resource container.
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct Vault {
     container: Rc<RefCell<ResourceContainer>>,
+    /// Whether withdrawals and deposits are blocked by the resource issuer.
+    frozen: bool,
+}
+
+// Copies the content of the vault, so that the substates embedding one, like the royalty
+// vault of a package or component, can be journaled.
+impl Clone for Vault {
+    fn clone(&self) -> Self {
+        Self::from_snapshot(self.snapshot())
+    }
+}
+
+/// A copy of the state of a vault, for rolling back to.
+pub struct VaultSnapshot {
+    container: ResourceContainer,
+    frozen: bool,
 }
 
 impl Vault {
     pub fn new(container: ResourceContainer) -> Self {
         Self {
             container: Rc::new(RefCell::new(container)),
+            frozen: false,
+        }
+    }
+
+    pub(crate) fn from_snapshot(snapshot: VaultSnapshot) -> Self {
+        Self {
+            container: Rc::new(RefCell::new(snapshot.container)),
+            frozen: snapshot.frozen,
         }
     }
 
@@ -43,7 +71,7 @@ impl Vault {
         self.borrow_container_mut().put(other.into_container()?)
     }
 
-    fn take(&mut self, amount: Decimal) -> Result<ResourceContainer, VaultError> {
+    pub fn take(&mut self, amount: Decimal) -> Result<ResourceContainer, VaultError> {
         let container = self
             .borrow_container_mut()
             .take_by_amount(amount)
@@ -64,7 +92,7 @@ impl Vault {
             ResourceType::Fungible { .. } => {
                 self.create_proof_by_amount(self.total_amount(), container_id)
             }
-            ResourceType::NonFungible => {
+            ResourceType::NonFungible { .. } => {
                 self.create_proof_by_ids(&self.total_ids().unwrap(), container_id)
             }
         }
@@ -144,6 +172,32 @@ impl Vault {
         self.borrow_container().is_empty()
     }
 
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Returns a copy of the state of this vault, for rolling back to.
+    pub(crate) fn snapshot(&self) -> VaultSnapshot {
+        VaultSnapshot {
+            container: self.borrow_container().snapshot(),
+            frozen: self.frozen,
+        }
+    }
+
+    /// Restores the state of this vault, keeping the proofs of it valid.
+    pub(crate) fn restore(&mut self, snapshot: VaultSnapshot) {
+        *self.borrow_container_mut() = snapshot.container;
+        self.frozen = snapshot.frozen;
+    }
+
+    pub(crate) fn check_not_frozen(&self) -> Result<(), VaultError> {
+        if self.frozen {
+            Err(VaultError::VaultFrozen)
+        } else {
+            Ok(())
+        }
+    }
+
     fn borrow_container(&self) -> Ref<ResourceContainer> {
         self.container.borrow()
     }
@@ -161,6 +215,7 @@ impl Vault {
     ) -> Result<ScryptoValue, VaultError> {
         match function {
             "put_into_vault" => {
+                self.check_not_frozen()?;
                 let bucket: scrypto::resource::Bucket =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let bucket = system_api.take_bucket(bucket.0).map_err(|_| VaultError::CouldNotTakeBucket)?;
@@ -168,19 +223,49 @@ impl Vault {
                 Ok(ScryptoValue::from_value(&()))
             }
             "take_from_vault" => {
+                self.check_not_frozen()?;
                 let amount: Decimal =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let container = self.take(amount)?;
                 let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
                 Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
             }
+            "lock_fee" => {
+                self.check_not_frozen()?;
+                let amount: Decimal =
+                    scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
+                if self.resource_address() != RADIX_TOKEN {
+                    return Err(VaultError::LockFeeNotRadixToken);
+                }
+                let fee = self.take(amount)?;
+                system_api.lock_fee(vault_id, fee).map_err(|_| VaultError::CouldNotLockFee)?;
+                Ok(ScryptoValue::from_value(&()))
+            }
             "take_non_fungibles_from_vault" => {
+                self.check_not_frozen()?;
                 let non_fungible_ids: BTreeSet<NonFungibleId> =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let container = self.take_non_fungibles(&non_fungible_ids)?;
                 let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
                 Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
             }
+            // Recall takes resources even from a frozen vault, as it is the issuer acting
+            "recall_vault" => {
+                let amount: Decimal =
+                    scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
+                let container = self.take(amount)?;
+                let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
+                Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
+            }
+            "freeze_vault" => {
+                self.frozen = true;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "unfreeze_vault" => {
+                self.frozen = false;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "is_vault_frozen" => Ok(ScryptoValue::from_value(&self.frozen)),
             "get_vault_amount" => {
                 let amount = self.total_amount();
                 Ok(ScryptoValue::from_value(&amount))
@@ -194,11 +279,13 @@ impl Vault {
                 Ok(ScryptoValue::from_value(&ids))
             }
             "create_vault_proof" => {
+                self.check_not_frozen()?;
                 let proof = self.create_proof(ResourceContainerId::Vault(vault_id)).map_err(VaultError::ProofError)?;
                 let proof_id = system_api.create_proof(proof).map_err(|_| VaultError::CouldNotCreateProof)?;
                 Ok(ScryptoValue::from_value(&scrypto::resource::Proof(proof_id)))
             }
             "create_vault_proof_by_amount" => {
+                self.check_not_frozen()?;
                 let amount: Decimal =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let proof = self.create_proof_by_amount(amount, ResourceContainerId::Vault(vault_id)).map_err(VaultError::ProofError)?;
@@ -206,6 +293,7 @@ impl Vault {
                 Ok(ScryptoValue::from_value(&scrypto::resource::Proof(proof_id)))
             }
             "create_vault_proof_by_ids" => {
+                self.check_not_frozen()?;
                 let ids = scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let proof = self.create_proof_by_ids(&ids, ResourceContainerId::Vault(vault_id)).map_err(VaultError::ProofError)?;
                 let proof_id = system_api.create_proof(proof).map_err(|_| VaultError::CouldNotCreateProof)?;
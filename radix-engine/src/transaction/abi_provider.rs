@@ -1,14 +1,15 @@
-use sbor::Type;
 use scrypto::abi;
-use scrypto::buffer::*;
 use scrypto::crypto::hash;
 use scrypto::engine::types::*;
 use scrypto::rust::borrow::ToOwned;
+use scrypto::rust::collections::HashMap;
+use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
 use scrypto::rust::vec::Vec;
 
 use crate::engine::*;
 use crate::errors::*;
+use crate::fee::*;
 use crate::ledger::*;
 use crate::model::*;
 
@@ -67,11 +68,19 @@ impl AbiProvider for BasicAbiProvider {
         let transaction_hash = hash([]);
 
         // Start a process and run abi generator
-        let mut track = Track::new(&mut ledger, transaction_hash, Vec::new());
+        let mut track = Track::new(
+            &mut ledger,
+            transaction_hash,
+            Vec::new(),
+            DEFAULT_COST_UNIT_LIMIT,
+            Decimal::zero(),
+        );
         let mut proc = track.start_process(self.trace);
-        let output: (Type, Vec<abi::Function>, Vec<abi::Method>) = proc
+        let output = proc
             .call_abi(package_address, blueprint_name)
-            .and_then(|rtn| scrypto_decode(&rtn.raw).map_err(RuntimeError::AbiValidationError))?;
+            .and_then(|rtn| {
+                Package::decode_abi(&rtn.raw).map_err(RuntimeError::AbiValidationError)
+            })?;
 
         // Return ABI
         Ok(abi::Blueprint {
@@ -79,6 +88,7 @@ impl AbiProvider for BasicAbiProvider {
             blueprint_name: blueprint_name.to_owned(),
             functions: output.1,
             methods: output.2,
+            events: output.3,
         })
     }
 
@@ -94,3 +104,70 @@ impl AbiProvider for BasicAbiProvider {
         self.export_abi(component.package_address(), component.blueprint_name())
     }
 }
+
+/// Provides ABIs from exported blueprint definitions, such as the JSON printed by `resim export-abi`.
+pub struct StaticAbiProvider {
+    blueprints: HashMap<(PackageAddress, String), abi::Blueprint>,
+    components: HashMap<ComponentAddress, (PackageAddress, String)>,
+}
+
+impl StaticAbiProvider {
+    pub fn new() -> Self {
+        Self {
+            blueprints: HashMap::new(),
+            components: HashMap::new(),
+        }
+    }
+
+    pub fn with_blueprint(
+        &mut self,
+        package_address: PackageAddress,
+        blueprint: abi::Blueprint,
+    ) -> &mut Self {
+        self.blueprints.insert(
+            (package_address, blueprint.blueprint_name.clone()),
+            blueprint,
+        );
+        self
+    }
+
+    pub fn with_component(
+        &mut self,
+        component_address: ComponentAddress,
+        package_address: PackageAddress,
+        blueprint_name: &str,
+    ) -> &mut Self {
+        self.components.insert(
+            component_address,
+            (package_address, blueprint_name.to_owned()),
+        );
+        self
+    }
+}
+
+impl AbiProvider for StaticAbiProvider {
+    fn export_abi(
+        &self,
+        package_address: PackageAddress,
+        blueprint_name: &str,
+    ) -> Result<abi::Blueprint, RuntimeError> {
+        self.blueprints
+            .get(&(package_address, blueprint_name.to_owned()))
+            .cloned()
+            .ok_or(RuntimeError::BlueprintNotFound(
+                package_address,
+                blueprint_name.to_owned(),
+            ))
+    }
+
+    fn export_abi_by_component(
+        &self,
+        component_address: ComponentAddress,
+    ) -> Result<abi::Blueprint, RuntimeError> {
+        let (package_address, blueprint_name) = self
+            .components
+            .get(&component_address)
+            .ok_or(RuntimeError::ComponentNotFound(component_address))?;
+        self.export_abi(*package_address, blueprint_name)
+    }
+}
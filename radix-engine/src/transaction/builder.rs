@@ -1,6 +1,8 @@
 use sbor::describe::*;
 use sbor::*;
 use scrypto::buffer::*;
+use scrypto::component::{DepositMode, RoyaltyConfig};
+use scrypto::core::Expression;
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
 use scrypto::prelude::{AccessRuleNode, Burn, AccessRule, Mint, Withdraw};
@@ -24,21 +26,42 @@ use crate::transaction::*;
 
 /// Utility for building transaction.
 pub struct TransactionBuilder {
+    /// The header of the transaction.
+    header: TransactionHeader,
     /// ID validator for calculating transaction object id
     id_validator: IdValidator,
     /// Instructions generated.
     instructions: Vec<Instruction>,
+    /// Blobs referred to by the instructions.
+    blobs: Vec<Vec<u8>>,
 }
 
 impl TransactionBuilder {
     /// Starts a new transaction builder.
     pub fn new() -> Self {
         Self {
+            header: TransactionHeader::default(),
             id_validator: IdValidator::new(),
             instructions: Vec::new(),
+            blobs: Vec::new(),
         }
     }
 
+    /// Sets the header of the transaction.
+    pub fn header(&mut self, header: TransactionHeader) -> &mut Self {
+        self.header = header;
+        self
+    }
+
+    /// Attaches a blob to the transaction, and returns its hash.
+    pub fn add_blob(&mut self, blob: Vec<u8>) -> Hash {
+        let blob_hash = hash(&blob);
+        if !self.blobs.iter().any(|b| hash(b) == blob_hash) {
+            self.blobs.push(blob);
+        }
+        blob_hash
+    }
+
     /// Adds a raw instruction.
     pub fn add_instruction(
         &mut self,
@@ -101,7 +124,16 @@ impl TransactionBuilder {
             Instruction::CallMethodWithAllResources { .. } => {
                 self.id_validator.move_all_resources().unwrap();
             }
-            Instruction::PublishPackage { .. } | Instruction::Nonce { .. } => {}
+            Instruction::PublishPackage { .. }
+            | Instruction::PublishPackageWithUpgradeRule { .. }
+            | Instruction::UpgradePackage { .. }
+            | Instruction::MigrateComponent { .. }
+            | Instruction::SetPackageRoyaltyConfig { .. }
+            | Instruction::ClaimPackageRoyalty { .. }
+            | Instruction::SetPackageMetadata { .. }
+            | Instruction::SetComponentMetadata { .. }
+            | Instruction::RecallVault { .. }
+            | Instruction::Nonce { .. } => {}
         }
 
         self.instructions.push(inst);
@@ -407,8 +439,117 @@ impl TransactionBuilder {
 
     /// Publishes a package.
     pub fn publish_package(&mut self, code: &[u8]) -> &mut Self {
-        self.add_instruction(Instruction::PublishPackage {
-            code: code.to_vec(),
+        let code = self.add_blob(code.to_vec());
+        self.add_instruction(Instruction::PublishPackage { code }).0
+    }
+
+    /// Publishes a package which can be upgraded by whoever satisfies the given rule.
+    pub fn publish_package_with_upgrade_rule(
+        &mut self,
+        code: &[u8],
+        upgrade_rule: AccessRule,
+    ) -> &mut Self {
+        let code = self.add_blob(code.to_vec());
+        self.add_instruction(Instruction::PublishPackageWithUpgradeRule { code, upgrade_rule })
+            .0
+    }
+
+    /// Publishes a new version of a package.
+    ///
+    /// The upgrade rule of the package must be satisfied by the auth zone.
+    pub fn upgrade_package(&mut self, package_address: PackageAddress, code: &[u8]) -> &mut Self {
+        let code = self.add_blob(code.to_vec());
+        self.add_instruction(Instruction::UpgradePackage {
+            package_address,
+            code,
+        })
+        .0
+    }
+
+    /// Migrates a component to a newer version of its package, by calling the given
+    /// migrate function of that version.
+    pub fn migrate_component(
+        &mut self,
+        component_address: ComponentAddress,
+        package_address: PackageAddress,
+        function: &str,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::MigrateComponent {
+            component_address,
+            package_address,
+            function: function.into(),
+        })
+        .0
+    }
+
+    /// Sets the royalties charged to callers of a blueprint.
+    ///
+    /// The upgrade rule of the package must be satisfied by the auth zone.
+    pub fn set_package_royalty_config(
+        &mut self,
+        package_address: PackageAddress,
+        blueprint_name: &str,
+        royalty_config: RoyaltyConfig,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::SetPackageRoyaltyConfig {
+            package_address,
+            blueprint_name: blueprint_name.to_owned(),
+            royalty_config,
+        })
+        .0
+    }
+
+    /// Claims the royalties collected by a package onto the worktop.
+    ///
+    /// The upgrade rule of the package must be satisfied by the auth zone.
+    pub fn claim_package_royalty(&mut self, package_address: PackageAddress) -> &mut Self {
+        self.add_instruction(Instruction::ClaimPackageRoyalty { package_address })
+            .0
+    }
+
+    /// Replaces the metadata of a package.
+    ///
+    /// The upgrade rule of the package must be satisfied by the auth zone.
+    pub fn set_package_metadata(
+        &mut self,
+        package_address: PackageAddress,
+        metadata: HashMap<String, String>,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::SetPackageMetadata {
+            package_address,
+            metadata,
+        })
+        .0
+    }
+
+    /// Replaces the metadata of a component.
+    ///
+    /// The metadata update rule of the component must be satisfied by the auth zone.
+    pub fn set_component_metadata(
+        &mut self,
+        component_address: ComponentAddress,
+        metadata: HashMap<String, String>,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::SetComponentMetadata {
+            component_address,
+            metadata,
+        })
+        .0
+    }
+
+    /// Recalls resources from a vault of a component, and puts them on the worktop.
+    ///
+    /// The recall rule of the resource must be satisfied by the auth zone.
+    pub fn recall_vault(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        amount: Decimal,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::RecallVault {
+            component_address,
+            vault_id,
+            amount,
         })
         .0
     }
@@ -418,7 +559,11 @@ impl TransactionBuilder {
         let mut instructions = self.instructions.clone();
         instructions.push(Instruction::Nonce { nonce });
 
-        Transaction { instructions }
+        Transaction {
+            header: self.header.clone(),
+            instructions,
+            blobs: self.blobs.clone(),
+        }
     }
 
     /// Builds a transaction with no nonce
@@ -426,7 +571,9 @@ impl TransactionBuilder {
     /// Nonce can be later filled by a third party or wallet.
     pub fn build_with_no_nonce(&self) -> Transaction {
         Transaction {
+            header: self.header.clone(),
             instructions: self.instructions.clone(),
+            blobs: self.blobs.clone(),
         }
     }
 
@@ -615,6 +762,87 @@ impl TransactionBuilder {
         .0
     }
 
+    /// Creates a validator, which can be registered by the owner.
+    pub fn new_validator(&mut self, owner_rule: &AccessRule) -> &mut Self {
+        self.add_instruction(Instruction::CallFunction {
+            package_address: VALIDATOR_PACKAGE,
+            blueprint_name: "Validator".to_owned(),
+            function: "create".to_owned(),
+            args: vec![scrypto_encode(owner_rule)],
+        })
+        .0
+    }
+
+    /// Stakes a bucket of XRD to a validator, and puts the stake units on the worktop.
+    pub fn stake(&mut self, validator: ComponentAddress, bucket_id: BucketId) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: validator,
+            method: "stake".to_owned(),
+            args: vec![scrypto_encode(&scrypto::resource::Bucket(bucket_id))],
+        })
+        .0
+    }
+
+    /// Unstakes a bucket of stake units, and puts the unstake claim on the worktop.
+    pub fn unstake(&mut self, validator: ComponentAddress, bucket_id: BucketId) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: validator,
+            method: "unstake".to_owned(),
+            args: vec![scrypto_encode(&scrypto::resource::Bucket(bucket_id))],
+        })
+        .0
+    }
+
+    /// Locks a fee from the XRD vault of an account.
+    pub fn lock_fee(&mut self, amount: Decimal, account: ComponentAddress) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "lock_fee".to_owned(),
+            args: vec![scrypto_encode(&amount)],
+        })
+        .0
+    }
+
+    /// Changes which resources an account accepts deposits of.
+    pub fn set_account_deposit_mode(
+        &mut self,
+        account: ComponentAddress,
+        deposit_mode: &DepositMode,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "set_deposit_mode".to_owned(),
+            args: vec![scrypto_encode(deposit_mode)],
+        })
+        .0
+    }
+
+    /// Deposits a bucket into an account, or puts it back on the worktop if the account
+    /// doesn't accept the resource.
+    pub fn try_deposit_or_refund(
+        &mut self,
+        account: ComponentAddress,
+        bucket_id: BucketId,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "try_deposit_or_refund".to_owned(),
+            args: vec![scrypto_encode(&scrypto::resource::Bucket(bucket_id))],
+        })
+        .0
+    }
+
+    /// Deposits all the resources on worktop into an account, and puts back on the worktop
+    /// those which the account doesn't accept.
+    pub fn try_deposit_batch_or_refund(&mut self, account: ComponentAddress) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "try_deposit_batch_or_refund".to_owned(),
+            args: vec![scrypto_encode(&Expression::EntireWorktop)],
+        })
+        .0
+    }
+
     /// Withdraws resource from an account.
     pub fn withdraw_from_account(
         &mut self,
@@ -895,11 +1123,11 @@ fn parse_resource_specifier(input: &str) -> Result<ResourceSpecifier, ParseResou
         .map_err(|_| ParseResourceSpecifierError::InvalidResourceAddress(token.to_owned()))?;
 
     // parse non-fungible ids or amount
-    if tokens[0].starts_with('#') {
+    if tokens[0].starts_with(|c| matches!(c, '#' | '<' | '[' | '{')) {
         let mut ids = BTreeSet::<NonFungibleId>::new();
         for id in &tokens[..tokens.len() - 1] {
             ids.insert(
-                id[1..].parse().map_err(|_| {
+                id.parse().map_err(|_| {
                     ParseResourceSpecifierError::InvalidNonFungibleId(id.to_string())
                 })?,
             );
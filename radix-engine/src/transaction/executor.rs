@@ -1,3 +1,4 @@
+use scrypto::core::SNodeRef;
 use scrypto::crypto::hash;
 use scrypto::engine::types::*;
 use scrypto::resource::*;
@@ -8,6 +9,7 @@ use scrypto::{abi, rule, access_rule_node};
 
 use crate::engine::*;
 use crate::errors::*;
+use crate::fee::*;
 use crate::ledger::*;
 use crate::model::*;
 use crate::transaction::*;
@@ -16,6 +18,9 @@ use crate::transaction::*;
 pub struct TransactionExecutor<'l, L: SubstateStore> {
     substate_store: &'l mut L,
     trace: bool,
+    cost_unit_limit: u32,
+    cost_unit_price: Decimal,
+    network: Network,
 }
 
 impl<'l, L: SubstateStore> NonceProvider for TransactionExecutor<'l, L> {
@@ -54,7 +59,7 @@ impl<'l, L: SubstateStore> AbiProvider for TransactionExecutor<'l, L> {
             .substate_store
             .get_decoded_substate(&component.package_address())
             .map(|(package, _)| package)
-            .unwrap();
+            .ok_or(RuntimeError::PackageNotFound(component.package_address()))?;
         BasicAbiProvider::new(self.trace)
             .with_package(&component.package_address(), package)
             .export_abi(component.package_address(), component.blueprint_name())
@@ -66,9 +71,33 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         Self {
             substate_store,
             trace,
+            cost_unit_limit: DEFAULT_COST_UNIT_LIMIT,
+            cost_unit_price: Decimal::zero(),
+            network: Network::LocalSimulator,
         }
     }
 
+    /// Sets the maximum number of cost units a transaction can consume.
+    pub fn with_cost_unit_limit(mut self, cost_unit_limit: u32) -> Self {
+        self.cost_unit_limit = cost_unit_limit;
+        self
+    }
+
+    /// Sets the price of a cost unit, in XRD.
+    ///
+    /// Cost units are free by default; with a non-zero price, transactions have to lock fees
+    /// to pay for the cost units they consume.
+    pub fn with_cost_unit_price(mut self, cost_unit_price: Decimal) -> Self {
+        self.cost_unit_price = cost_unit_price;
+        self
+    }
+
+    /// Sets the network transactions are validated against.
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
     /// Returns an immutable reference to the ledger.
     pub fn substate_store(&self) -> &L {
         self.substate_store
@@ -94,6 +123,7 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         let receipt = self
             .validate_and_execute(
                 &TransactionBuilder::new()
+                    .header(TransactionHeader::new(self.substate_store.get_epoch()))
                     .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
                     .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
                         builder.new_account_with_resource(withdraw_auth, bucket_id)
@@ -125,6 +155,7 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         let receipt = self
             .validate_and_execute(
                 &TransactionBuilder::new()
+                    .header(TransactionHeader::new(self.substate_store.get_epoch()))
                     .publish_package(code.as_ref())
                     .build(self.get_nonce([]))
                     .sign([]),
@@ -153,32 +184,114 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         Ok(())
     }
 
+    /// Ends the current epoch, and returns the new one.
+    ///
+    /// The emission of the epoch is distributed to the registered validators before the
+    /// ledger moves to the next epoch. Nothing changes if the distribution fails.
+    pub fn next_epoch(&mut self) -> Result<u64, RuntimeError> {
+        let tx_hash = hash(self.substate_store.get_and_increase_nonce().to_le_bytes());
+        let mut track = Track::new(
+            self.substate_store,
+            tx_hash,
+            Vec::new(),
+            self.cost_unit_limit,
+            Decimal::zero(),
+        );
+        track.start_system_process(self.trace).invoke_snode(
+            SNodeRef::EpochManagerRef,
+            "next_epoch".to_string(),
+            vec![],
+        )?;
+        track.commit();
+
+        let epoch = self.substate_store.get_epoch() + 1;
+        self.substate_store.set_epoch(epoch);
+        Ok(epoch)
+    }
+
     pub fn validate_and_execute(
         &mut self,
         signed: &SignedTransaction,
     ) -> Result<Receipt, TransactionValidationError> {
-        let validated = signed.validate()?;
-        let receipt = self.execute(validated);
-        Ok(receipt)
+        let validated = signed.validate(self.network, self.substate_store.get_epoch())?;
+        self.execute(validated)
+    }
+
+    /// Runs a signed transaction against the current ledger without committing anything.
+    pub fn preview(
+        &mut self,
+        signed: &SignedTransaction,
+    ) -> Result<Receipt, TransactionValidationError> {
+        let validated = signed.validate(self.network, self.substate_store.get_epoch())?;
+        Ok(self.execute_internal(validated, false))
+    }
+
+    /// Runs an unsigned transaction against the current ledger without committing anything,
+    /// as if it were signed by the given public keys.
+    ///
+    /// The virtual badges of the assumed signers are put into the auth zone, but no signature
+    /// is checked.
+    pub fn preview_with_signers(
+        &mut self,
+        transaction: &Transaction,
+        signer_public_keys: Vec<PublicKey>,
+    ) -> Result<Receipt, TransactionValidationError> {
+        let validated = transaction.validate_with_signers(
+            self.network,
+            self.substate_store.get_epoch(),
+            signer_public_keys,
+        )?;
+        Ok(self.execute_internal(validated, false))
+    }
+
+    /// Executes a validated transaction and commits it, unless it is outside of its epoch range
+    /// or its intent has already been committed.
+    ///
+    /// The epoch range is checked again, as committed intents are only remembered until they
+    /// expire.
+    pub fn execute(
+        &mut self,
+        validated: ValidatedTransaction,
+    ) -> Result<Receipt, TransactionValidationError> {
+        validated
+            .header
+            .check_epoch(self.substate_store.get_epoch())?;
+        if self.substate_store.is_intent_committed(&validated.raw_hash) {
+            return Err(TransactionValidationError::IntentAlreadyCommitted(
+                validated.raw_hash,
+            ));
+        }
+        Ok(self.execute_internal(validated, true))
     }
 
-    pub fn execute(&mut self, validated: ValidatedTransaction) -> Receipt {
+    fn execute_internal(&mut self, validated: ValidatedTransaction, commit: bool) -> Receipt {
         #[cfg(not(feature = "alloc"))]
         let now = std::time::Instant::now();
 
+        let tip_percentage = validated.header.tip_percentage as u64;
+        let cost_unit_price =
+            self.cost_unit_price * Decimal::from(100 + tip_percentage) / Decimal::from(100u64);
         let mut track = Track::new(
             self.substate_store,
             validated.raw_hash.clone(),
             validated.signers.clone(),
+            self.cost_unit_limit,
+            cost_unit_price,
         );
-        let mut proc = track.start_process(self.trace);
+        let tx_base_fee = track.fee_table().tx_base_fee();
+        let mut error = track.consume_cost_units(tx_base_fee, "tx_base_fee").err();
 
         let txn_process = TransactionProcess::new(validated.clone());
         let mut txn_snode = SNodeState::Transaction(txn_process);
-        let error = match proc.run(&mut txn_snode, "execute".to_string(), vec![]) {
-            Ok(_) => None,
-            Err(e) => Some(e),
-        };
+        if error.is_none() {
+            let mut proc = track.start_process(self.trace);
+            error = proc
+                .run(&mut txn_snode, "execute".to_string(), vec![])
+                .err();
+        }
+        if error.is_none() {
+            error = track.check_fee_payment().err();
+        }
         let outputs = if let SNodeState::Transaction(txn_process) = txn_snode {
             txn_process.outputs().to_vec()
         } else {
@@ -190,17 +303,58 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         let new_component_addresses = track.new_component_addresses();
         let new_resource_addresses = track.new_resource_addresses();
         let logs = track.logs().clone();
-
-        // commit state updates
-        let commit_receipt = if error.is_none() {
-            let receipt = track.commit();
-            self.substate_store.increase_nonce();
-            Some(receipt)
+        let events = if error.is_none() {
+            track.events().clone()
         } else {
-            None
+            Vec::new()
         };
 
+        // commit state updates, or only the fee payment if the transaction failed
+        let (commit_receipt, state_changes, fee_summary) = if error.is_none() {
+            let fee_summary = track.settle_fees();
+            let state_changes = track.state_changes();
+            if commit {
+                let receipt = track.commit();
+                self.substate_store.increase_nonce();
+                (Some(receipt), state_changes, fee_summary)
+            } else {
+                (None, state_changes, fee_summary)
+            }
+        } else {
+            let locked_fees = track.locked_fees();
+            let fee_due = track.fee_due();
+            let locked = locked_fees.iter().map(|(_, _, amount)| *amount).sum();
+            let mut fee_summary = track.fee_summary(locked, Decimal::zero());
+
+            if locked_fees.is_empty() {
+                (None, StateChanges::new(), fee_summary)
+            } else {
+                let mut fee_track = Track::new(
+                    self.substate_store,
+                    validated.raw_hash.clone(),
+                    Vec::new(),
+                    self.cost_unit_limit,
+                    Decimal::zero(),
+                );
+                fee_summary.burned = fee_track.charge_committed_fees(&locked_fees, fee_due);
+                let state_changes = fee_track.state_changes();
+                if commit {
+                    let receipt = fee_track.commit();
+                    self.substate_store.increase_nonce();
+                    (Some(receipt), state_changes, fee_summary)
+                } else {
+                    (None, state_changes, fee_summary)
+                }
+            }
+        };
 
+        // remember the intent to reject replays until the transaction expires
+        if commit_receipt.is_some() {
+            self.substate_store.put_committed_intent(
+                &validated.raw_hash,
+                validated.header.end_epoch_exclusive,
+            );
+        }
 
         #[cfg(feature = "alloc")]
         let execution_time = None;
@@ -216,9 +370,12 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
             },
             outputs,
             logs,
+            events,
             new_package_addresses,
             new_component_addresses,
             new_resource_addresses,
+            state_changes,
+            fee_summary,
             execution_time,
         }
     }
@@ -4,7 +4,7 @@ mod error;
 mod executor;
 mod nonce_provider;
 
-pub use abi_provider::{AbiProvider, BasicAbiProvider};
+pub use abi_provider::{AbiProvider, BasicAbiProvider, StaticAbiProvider};
 pub use builder::TransactionBuilder;
 pub use error::{BuildArgsError, CallWithAbiError};
 pub use executor::TransactionExecutor;
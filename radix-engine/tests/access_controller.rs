@@ -0,0 +1,222 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::{AccessControllerError, Receipt};
+use scrypto::prelude::*;
+
+struct Setup {
+    controller: ComponentAddress,
+    account: ComponentAddress,
+    primary_pk: EcdsaPublicKey,
+    primary_sk: EcdsaPrivateKey,
+    recovery_pk: EcdsaPublicKey,
+    recovery_sk: EcdsaPrivateKey,
+}
+
+/// Creates an access controller with a confirmation delay of 10 epochs, and an account which
+/// requires its badge.
+fn setup(test_runner: &mut TestRunner) -> Setup {
+    let package = test_runner.publish_package("access_controller");
+    let (primary_pk, primary_sk) = test_runner.new_key_pair();
+    let (recovery_pk, recovery_sk) = test_runner.new_key_pair();
+    let primary = NonFungibleAddress::from_public_key(&primary_pk.into());
+    let recovery = NonFungibleAddress::from_public_key(&recovery_pk.into());
+
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "Custody",
+            "new_controller",
+            args![rule!(require(primary)), rule!(require(recovery)), 10u64],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let (controller, badge): (ComponentAddress, ResourceAddress) =
+        scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    let account = test_runner.new_account_with_auth_rule(&rule!(require(badge)));
+
+    Setup {
+        controller,
+        account,
+        primary_pk,
+        primary_sk,
+        recovery_pk,
+        recovery_sk,
+    }
+}
+
+fn withdraw_with_badge(
+    test_runner: &mut TestRunner,
+    setup: &Setup,
+    pk: EcdsaPublicKey,
+    sk: &EcdsaPrivateKey,
+) -> Receipt {
+    let (_, _, other_account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.controller, "create_proof", args![])
+        .withdraw_from_account(RADIX_TOKEN, setup.account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([sk]);
+    test_runner.validate_and_execute(&transaction)
+}
+
+fn call_recovery_method(
+    test_runner: &mut TestRunner,
+    setup: &Setup,
+    method: &str,
+    args: Vec<Vec<u8>>,
+    pk: EcdsaPublicKey,
+    sk: &EcdsaPrivateKey,
+) -> Receipt {
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.controller, method, args)
+        .build(test_runner.get_nonce([pk]))
+        .sign([sk]);
+    test_runner.validate_and_execute(&transaction)
+}
+
+#[test]
+fn only_primary_role_can_use_badge() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+
+    // Act
+    let unauthorized_receipt = withdraw_with_badge(
+        &mut test_runner,
+        &setup,
+        setup.recovery_pk,
+        &setup.recovery_sk,
+    );
+    let authorized_receipt = withdraw_with_badge(
+        &mut test_runner,
+        &setup,
+        setup.primary_pk,
+        &setup.primary_sk,
+    );
+
+    // Assert
+    let error = unauthorized_receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+    authorized_receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn recovery_takes_effect_only_after_confirmation_delay() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+    let (new_pk, new_sk) = test_runner.new_key_pair();
+    let new_primary = NonFungibleAddress::from_public_key(&new_pk.into());
+    test_runner.set_epoch(5);
+    call_recovery_method(
+        &mut test_runner,
+        &setup,
+        "initiate_recovery",
+        args![rule!(require(new_primary))],
+        setup.recovery_pk,
+        &setup.recovery_sk,
+    )
+    .result
+    .expect("Should be okay");
+
+    // Act
+    test_runner.set_epoch(14);
+    let early_receipt = call_recovery_method(
+        &mut test_runner,
+        &setup,
+        "confirm_recovery",
+        args![],
+        setup.recovery_pk,
+        &setup.recovery_sk,
+    );
+    test_runner.set_epoch(15);
+    let receipt = call_recovery_method(
+        &mut test_runner,
+        &setup,
+        "confirm_recovery",
+        args![],
+        setup.recovery_pk,
+        &setup.recovery_sk,
+    );
+
+    // Assert
+    assert_eq!(
+        early_receipt.result,
+        Err(RuntimeError::AccessControllerError(
+            AccessControllerError::RecoveryDelayNotElapsed(15)
+        ))
+    );
+    receipt.result.expect("Should be okay");
+    let error = withdraw_with_badge(
+        &mut test_runner,
+        &setup,
+        setup.primary_pk,
+        &setup.primary_sk,
+    )
+    .result
+    .expect_err("Should be an error");
+    assert_auth_error!(error);
+    withdraw_with_badge(&mut test_runner, &setup, new_pk, &new_sk)
+        .result
+        .expect("Should be okay");
+}
+
+#[test]
+fn primary_role_can_cancel_recovery() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+    let (new_pk, _) = test_runner.new_key_pair();
+    let new_primary = NonFungibleAddress::from_public_key(&new_pk.into());
+    call_recovery_method(
+        &mut test_runner,
+        &setup,
+        "initiate_recovery",
+        args![rule!(require(new_primary))],
+        setup.recovery_pk,
+        &setup.recovery_sk,
+    )
+    .result
+    .expect("Should be okay");
+
+    // Act
+    let cancel_receipt = call_recovery_method(
+        &mut test_runner,
+        &setup,
+        "cancel_recovery",
+        args![],
+        setup.primary_pk,
+        &setup.primary_sk,
+    );
+    test_runner.set_epoch(10);
+    let confirm_receipt = call_recovery_method(
+        &mut test_runner,
+        &setup,
+        "confirm_recovery",
+        args![],
+        setup.recovery_pk,
+        &setup.recovery_sk,
+    );
+
+    // Assert
+    cancel_receipt.result.expect("Should be okay");
+    assert_eq!(
+        confirm_receipt.result,
+        Err(RuntimeError::AccessControllerError(
+            AccessControllerError::NoPendingRecovery
+        ))
+    );
+}
@@ -0,0 +1,22 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Custody {}
+
+    impl Custody {
+        /// Creates a badge, and an access controller which holds it.
+        pub fn new_controller(
+            primary_rule: AccessRule,
+            recovery_rule: AccessRule,
+            confirmation_delay: u64,
+        ) -> (ComponentAddress, ResourceAddress) {
+            let badge = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(1);
+            let badge_address = badge.resource_address();
+            let controller =
+                AccessController::new(badge, primary_rule, recovery_rule, confirmation_delay);
+            (controller.address(), badge_address)
+        }
+    }
+}
@@ -29,6 +29,79 @@ fn can_withdraw_from_my_account() {
     assert!(receipt.result.is_ok());
 }
 
+#[test]
+fn withdrawal_should_be_reported_in_state_changes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert!(receipt.result.is_ok());
+    let state_changes = &receipt.state_changes;
+    assert_eq!(
+        state_changes.balance_change(account, RADIX_TOKEN),
+        Decimal::from(-1000000)
+    );
+    assert_eq!(
+        state_changes.balance_change(other_account, RADIX_TOKEN),
+        Decimal::from(1000000)
+    );
+}
+
+#[test]
+fn non_fungible_withdrawal_should_be_reported_in_state_changes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+    let resource_address = test_runner.create_non_fungible_resource(account);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(resource_address, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let vault_changes: Vec<&VaultChange> = receipt
+        .state_changes
+        .vault_changes
+        .iter()
+        .filter(|c| c.resource_address == resource_address)
+        .collect();
+    assert_eq!(vault_changes.len(), 2);
+    for change in vault_changes {
+        match &change.balance_change {
+            BalanceChange::NonFungible { added, removed } if change.component_address == account => {
+                assert!(added.is_empty());
+                assert!(!removed.is_empty());
+            }
+            BalanceChange::NonFungible { added, removed } => {
+                assert_eq!(change.component_address, other_account);
+                assert!(!added.is_empty());
+                assert!(removed.is_empty());
+            }
+            _ => panic!("Should be non-fungible balance change"),
+        }
+    }
+}
+
 #[test]
 fn can_withdraw_non_fungible_from_my_account() {
     // Arrange
@@ -122,3 +195,99 @@ fn test_account_balance() {
         ScryptoValue::from_value(&Decimal::from(1000000))
     );
 }
+
+#[test]
+fn cannot_deposit_into_account_denying_all() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, other_sk, other_account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .set_account_deposit_mode(other_account, &DepositMode::DenyAll)
+        .build(test_runner.get_nonce([other_pk]))
+        .sign([&other_sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(receipt.result, Err(RuntimeError::InvokeError));
+}
+
+#[test]
+fn try_deposit_or_refund_returns_resources_not_in_allow_list() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, other_sk, other_account) = test_runner.new_account();
+    let resource_address = test_runner.create_non_fungible_resource(account);
+    let mut allow_list = HashSet::new();
+    allow_list.insert(resource_address);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .set_account_deposit_mode(other_account, &DepositMode::AllowList(allow_list))
+        .build(test_runner.get_nonce([other_pk]))
+        .sign([&other_sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .withdraw_from_account(resource_address, account)
+        .try_deposit_batch_or_refund(other_account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .call_method(other_account, "balance", args![RADIX_TOKEN])
+        .call_method(other_account, "balance", args![resource_address])
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert_eq!(
+        receipt.outputs[4],
+        ScryptoValue::from_value(&Decimal::from(1000000))
+    );
+    assert_ne!(
+        receipt.outputs[5],
+        ScryptoValue::from_value(&Decimal::zero())
+    );
+}
+
+#[test]
+fn cannot_set_deposit_mode_of_other_account() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let (other_pk, other_sk, _) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .set_account_deposit_mode(account, &DepositMode::DenyAll)
+        .build(test_runner.get_nonce([other_pk]))
+        .sign([&other_sk]);
+
+    // Act
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be runtime error");
+    assert_auth_error!(error);
+}
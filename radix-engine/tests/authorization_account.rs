@@ -328,3 +328,52 @@ fn cannot_withdraw_from_my_any_xrd_auth_account_with_less_than_amount_of_proof()
     let error = receipt.result.expect_err("Should be an error");
     assert_auth_error!(error);
 }
+
+#[test]
+fn can_withdraw_from_my_ed25519_account_with_ed25519_key_sign() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let sk = Ed25519PrivateKey::from_bytes(&[1u8; 32]).unwrap();
+    let auth = NonFungibleAddress::from_public_key(&Signer::public_key(&sk));
+    let account = test_runner.new_account_with_auth_rule(&rule!(require(auth)));
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([])
+        .sign_with(&sk);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn cannot_withdraw_from_my_ed25519_account_with_ecdsa_key_of_same_bytes() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let ed25519_sk = Ed25519PrivateKey::from_bytes(&[1u8; 32]).unwrap();
+    let ecdsa_sk = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+    let auth = NonFungibleAddress::from_public_key(&Signer::public_key(&ed25519_sk));
+    let account = test_runner.new_account_with_auth_rule(&rule!(require(auth)));
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([ecdsa_sk.public_key()]))
+        .sign([&ecdsa_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
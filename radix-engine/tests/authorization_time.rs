@@ -0,0 +1,106 @@
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::*;
+use radix_engine::model::Receipt;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+fn withdraw_all(
+    executor: &mut TransactionExecutor<InMemorySubstateStore>,
+    account: ComponentAddress,
+    pk: EcdsaPublicKey,
+    sk: &EcdsaPrivateKey,
+) -> Receipt {
+    let (_, _, other_account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([sk]);
+    executor.validate_and_execute(&transaction).unwrap()
+}
+
+#[test]
+fn epoch_locked_account_can_be_withdrawn_from_only_from_the_given_epoch() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk) = executor.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let account = executor.new_account_with_auth_rule(&rule!(require(owner) && from_epoch(10)));
+    executor.substate_store_mut().set_epoch(9);
+
+    // Act
+    let locked_receipt = withdraw_all(&mut executor, account, pk, &sk);
+    executor.substate_store_mut().set_epoch(10);
+    let unlocked_receipt = withdraw_all(&mut executor, account, pk, &sk);
+
+    // Assert
+    let error = locked_receipt.result.expect_err("Should be an error");
+    if !matches!(error, RuntimeError::AuthorizationError { .. }) {
+        panic!("Expected auth error but got: {:?}", error);
+    }
+    unlocked_receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn time_limited_account_can_be_withdrawn_from_only_before_the_given_time() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk) = executor.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let account =
+        executor.new_account_with_auth_rule(&rule!(require(owner) && before_time(1_660_000_020)));
+    executor
+        .substate_store_mut()
+        .set_current_time(1_659_999_960);
+
+    // Act
+    let open_receipt = withdraw_all(&mut executor, account, pk, &sk);
+    executor
+        .substate_store_mut()
+        .set_current_time(1_660_000_020);
+    let expired_receipt = withdraw_all(&mut executor, account, pk, &sk);
+
+    // Assert
+    open_receipt.result.expect("Should be okay");
+    let error = expired_receipt.result.expect_err("Should be an error");
+    if !matches!(error, RuntimeError::AuthorizationError { .. }) {
+        panic!("Expected auth error but got: {:?}", error);
+    }
+}
+
+#[test]
+fn time_limit_within_a_minute_expires_at_the_start_of_that_minute() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk) = executor.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let account =
+        executor.new_account_with_auth_rule(&rule!(require(owner) && before_time(1_660_000_000)));
+    executor
+        .substate_store_mut()
+        .set_current_time(1_659_999_960);
+
+    // Act
+    let receipt = withdraw_all(&mut executor, account, pk, &sk);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    if !matches!(error, RuntimeError::AuthorizationError { .. }) {
+        panic!("Expected auth error but got: {:?}", error);
+    }
+}
+
+#[test]
+fn ledger_clock_is_rounded_down_to_the_minute() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+
+    // Act
+    ledger.set_current_time(1_660_000_059);
+
+    // Assert
+    assert_eq!(ledger.get_current_time(), 1_660_000_020);
+}
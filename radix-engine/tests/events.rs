@@ -0,0 +1,65 @@
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn emitted_events_should_be_recorded_in_order() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "events")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "EventTest", "emit_events", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    assert!(receipt.result.is_ok());
+    let names: Vec<&str> = receipt.events.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Deposited", "Status"]);
+    assert_eq!(receipt.events[0].actor.package_address(), &package);
+    assert_eq!(receipt.events[0].actor.blueprint_name(), "EventTest");
+}
+
+#[test]
+fn events_of_failed_transaction_should_be_discarded() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "events")))
+        .unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "EventTest", "emit_event_and_panic", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    assert!(receipt.result.is_err());
+    assert!(receipt.events.is_empty());
+}
+
+#[test]
+fn event_schemas_should_be_exported_in_abi() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "events")))
+        .unwrap();
+
+    // Act
+    let abi = executor.export_abi(package, "EventTest").unwrap();
+
+    // Assert
+    let names: Vec<&str> = abi.events.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["Deposited", "Status"]);
+}
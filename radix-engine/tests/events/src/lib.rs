@@ -0,0 +1,29 @@
+use scrypto::prelude::*;
+
+#[derive(TypeId, Encode, Decode, Describe)]
+pub struct Deposited {
+    pub amount: Decimal,
+}
+
+#[derive(TypeId, Encode, Decode, Describe)]
+pub enum Status {
+    Open,
+    Closed,
+}
+
+blueprint! {
+    #[events(Deposited, Status)]
+    struct EventTest {}
+
+    impl EventTest {
+        pub fn emit_events() {
+            Runtime::emit_event(Deposited { amount: Decimal::from(10) });
+            Runtime::emit_event(Status::Closed);
+        }
+
+        pub fn emit_event_and_panic() {
+            Runtime::emit_event(Status::Open);
+            panic!("Oops");
+        }
+    }
+}
@@ -0,0 +1,228 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::fee::CostUnitCounterError;
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::{TransactionHeader, VaultError};
+use scrypto::prelude::*;
+
+#[test]
+fn transaction_paying_locked_fee_should_succeed() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let component_address = test_runner.create_fee_component();
+    let mut test_runner = test_runner.with_cost_unit_price(dec!("0.001"));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "lock_fee", args![dec!("1000")])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let fee_summary = receipt.fee_summary;
+    assert_eq!(fee_summary.locked, dec!("1000"));
+    assert!(fee_summary.burned.is_positive());
+    assert_eq!(
+        fee_summary.burned,
+        fee_summary.cost_unit_price * fee_summary.cost_unit_consumed
+    );
+}
+
+#[test]
+fn tip_should_raise_cost_unit_price() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let component_address = test_runner.create_fee_component();
+    let mut test_runner = test_runner.with_cost_unit_price(dec!("0.001"));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .header(TransactionHeader {
+            tip_percentage: 50,
+            ..TransactionHeader::default()
+        })
+        .call_method(component_address, "lock_fee", args![dec!("1000")])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let fee_summary = receipt.fee_summary;
+    assert_eq!(fee_summary.cost_unit_price, dec!("0.0015"));
+    assert_eq!(
+        fee_summary.burned,
+        fee_summary.cost_unit_price * fee_summary.cost_unit_consumed
+    );
+}
+
+#[test]
+fn unused_locked_fee_should_be_refunded() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let component_address = test_runner.create_fee_component();
+    let mut test_runner = test_runner.with_cost_unit_price(dec!("0.001"));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "lock_fee", args![dec!("1000")])
+        .call_method(component_address, "xrd_balance", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt1 = test_runner.validate_and_execute(&transaction);
+    receipt1.result.expect("Should be okay");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "lock_fee", args![dec!("1000")])
+        .call_method(component_address, "xrd_balance", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt2 = test_runner.validate_and_execute(&transaction);
+    receipt2.result.expect("Should be okay");
+
+    // Assert
+    let balance1: Decimal = scrypto_decode(&receipt1.outputs[1].raw).unwrap();
+    let balance2: Decimal = scrypto_decode(&receipt2.outputs[1].raw).unwrap();
+    assert_eq!(balance2, balance1 - receipt1.fee_summary.burned);
+}
+
+#[test]
+fn transaction_without_locked_fee_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let component_address = test_runner.create_fee_component();
+    let mut test_runner = test_runner.with_cost_unit_price(dec!("0.001"));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "xrd_balance", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    match runtime_error {
+        RuntimeError::CostingError(CostUnitCounterError::SystemLoanNotRepaid { .. }) => {}
+        _ => panic!("Should be loan not repaid error but was {}", runtime_error),
+    }
+    assert!(receipt.commit_receipt.is_none());
+}
+
+#[test]
+fn failed_transaction_should_still_pay_locked_fee() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let component_address = test_runner.create_fee_component();
+    let mut test_runner = test_runner.with_cost_unit_price(dec!("0.001"));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "lock_fee_and_panic", args![dec!("1000")])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect_err("Should be runtime error");
+    assert!(receipt.commit_receipt.is_some());
+    assert!(receipt.fee_summary.burned.is_positive());
+}
+
+#[test]
+fn lock_fee_from_local_vault_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let component_address = test_runner.create_fee_component();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(
+            component_address,
+            "lock_fee_from_new_vault",
+            args![dec!("1000")],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    match runtime_error {
+        RuntimeError::VaultError(VaultError::CouldNotLockFee) => {}
+        _ => panic!(
+            "Should be could not lock fee error but was {}",
+            runtime_error
+        ),
+    }
+}
+
+#[test]
+fn runaway_loop_should_exceed_cost_unit_limit() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let component_address = test_runner.create_fee_component();
+    let mut test_runner = test_runner.with_cost_unit_limit(1_000_000);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "spin", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    match runtime_error {
+        RuntimeError::CostingError(CostUnitCounterError::LimitExceeded { .. })
+        | RuntimeError::WasmGasExhausted(CostUnitCounterError::LimitExceeded { .. }) => {}
+        _ => panic!("Should be limit exceeded error but was {}", runtime_error),
+    }
+    assert_eq!(receipt.fee_summary.cost_unit_limit, 1_000_000);
+}
+
+#[test]
+fn loop_without_engine_calls_should_exhaust_wasm_gas() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let component_address = test_runner.create_fee_component();
+    let mut test_runner = test_runner.with_cost_unit_limit(1_000_000);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "spin_without_engine_calls", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    match runtime_error {
+        RuntimeError::WasmGasExhausted(CostUnitCounterError::LimitExceeded { .. }) => {}
+        _ => panic!(
+            "Should be wasm gas exhausted error but was {}",
+            runtime_error
+        ),
+    }
+}
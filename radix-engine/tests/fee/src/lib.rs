@@ -0,0 +1,46 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Fee {
+        xrd: Vault,
+    }
+
+    impl Fee {
+        pub fn new(xrd: Bucket) -> ComponentAddress {
+            Self {
+                xrd: Vault::with_bucket(xrd),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn lock_fee(&mut self, amount: Decimal) {
+            self.xrd.lock_fee(amount);
+        }
+
+        pub fn lock_fee_and_panic(&mut self, amount: Decimal) {
+            self.xrd.lock_fee(amount);
+            panic!("Oops");
+        }
+
+        pub fn lock_fee_from_new_vault(&mut self, amount: Decimal) {
+            let mut vault = Vault::with_bucket(self.xrd.take(amount));
+            vault.lock_fee(amount);
+            self.xrd.put(vault.take_all());
+        }
+
+        pub fn spin(&self) {
+            loop {
+                Runtime::current_epoch();
+            }
+        }
+
+        pub fn spin_without_engine_calls(&self) {
+            loop {}
+        }
+
+        pub fn xrd_balance(&self) -> Decimal {
+            self.xrd.amount()
+        }
+    }
+}
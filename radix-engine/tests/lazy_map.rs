@@ -1,3 +1,7 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
 use radix_engine::errors::ResourceFailure;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
@@ -228,3 +232,170 @@ fn create_lazy_map_and_put() {
     // Assert
     assert!(receipt.result.is_ok());
 }
+
+#[test]
+fn can_remove_from_uncommitted_lazy_map() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("lazy_map");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "LazyMapOps", "remove_before_storing", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert!(receipt.result.is_ok());
+    let len: u32 = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(len, 1);
+}
+
+#[test]
+fn removed_entry_should_not_be_visible_in_later_transactions() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("lazy_map");
+    let component_address = test_runner.new_component(package, "LazyMapOps", "new", args![10u32]);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "remove", args![5u32])
+        .call_method(component_address, "remove", args![5u32])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt1 = test_runner.validate_and_execute(&transaction);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "contains_key", args![5u32])
+        .call_method(component_address, "len", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt2 = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert!(receipt1.result.is_ok());
+    let removed: Option<u32> = scrypto_decode(&receipt1.outputs[0].raw).unwrap();
+    let removed_again: Option<u32> = scrypto_decode(&receipt1.outputs[1].raw).unwrap();
+    assert_eq!(removed, Some(5));
+    assert_eq!(removed_again, None);
+    assert!(receipt2.result.is_ok());
+    let contains: bool = scrypto_decode(&receipt2.outputs[0].raw).unwrap();
+    let len: u32 = scrypto_decode(&receipt2.outputs[1].raw).unwrap();
+    assert!(!contains);
+    assert_eq!(len, 9);
+}
+
+#[test]
+fn can_iterate_lazy_map_in_pages() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("lazy_map");
+    let component_address = test_runner.new_component(package, "LazyMapOps", "new", args![10u32]);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "remove", args![3u32])
+        .call_method(component_address, "keys", args![4u32])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert!(receipt.result.is_ok());
+    let mut keys: Vec<u32> = scrypto_decode(&receipt.outputs[1].raw).unwrap();
+    keys.sort();
+    assert_eq!(keys, vec![0, 1, 2, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn reading_a_page_should_not_depend_on_lazy_map_size() {
+    // Arrange
+    let mut reads = Vec::new();
+    for size in [10u32, 50u32] {
+        let mut substate_store = InMemorySubstateStore::with_bootstrap();
+        let mut test_runner = TestRunner::new(&mut substate_store);
+        let package = test_runner.publish_package("lazy_map");
+        let component_address =
+            test_runner.new_component(package, "LazyMapOps", "new", args![size]);
+
+        // Act
+        let transaction = test_runner
+            .new_transaction_builder()
+            .call_method(component_address, "first_keys", args![2u32])
+            .build(test_runner.get_nonce([]))
+            .sign([]);
+        let receipt = test_runner.validate_and_execute(&transaction);
+
+        // Assert
+        assert!(receipt.result.is_ok());
+        let keys: Vec<u32> = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+        assert_eq!(keys.len(), 2);
+        reads.push(receipt.fee_summary.cost_unit_breakdown["read_substate"]);
+    }
+    assert_eq!(reads[0], reads[1]);
+}
+
+#[test]
+fn len_should_be_kept_across_transactions() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("lazy_map");
+    let component_address = test_runner.new_component(package, "LazyMapOps", "new", args![10u32]);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "remove", args![1u32])
+        .call_method(component_address, "remove", args![2u32])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    assert!(receipt.result.is_ok());
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "len", args![])
+        .call_method(component_address, "keys", args![3u32])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert!(receipt.result.is_ok());
+    let len: u32 = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    let keys: Vec<u32> = scrypto_decode(&receipt.outputs[1].raw).unwrap();
+    assert_eq!(len, 8);
+    assert_eq!(keys.len(), 8);
+}
+
+#[test]
+fn removing_entry_containing_lazy_map_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("lazy_map");
+    let component_address = test_runner.new_component(package, "LazyMapOps", "new", args![0u32]);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component_address, "remove_entry_with_lazy_map", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let runtime_error = receipt.result.expect_err("Should be runtime error");
+    match runtime_error {
+        RuntimeError::LazyMapRemoved(_) => {}
+        _ => panic!("Should be lazy map removed error but was {}", runtime_error),
+    }
+}
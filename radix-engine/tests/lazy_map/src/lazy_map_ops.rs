@@ -0,0 +1,80 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct LazyMapOps {
+        map: LazyMap<u32, u32>,
+        maps: LazyMap<u32, LazyMap<u32, u32>>,
+    }
+
+    impl LazyMapOps {
+        pub fn new(size: u32) -> ComponentAddress {
+            let map = LazyMap::new();
+            for i in 0..size {
+                map.insert(i, i);
+            }
+            LazyMapOps {
+                map,
+                maps: LazyMap::new(),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn remove_before_storing() -> u32 {
+            let map = LazyMap::new();
+            map.insert(1u32, 1u32);
+            map.insert(2u32, 2u32);
+            assert_eq!(map.remove(&1u32), Some(1u32));
+            assert_eq!(map.remove(&1u32), None);
+            assert!(!map.contains_key(&1u32));
+            assert!(map.contains_key(&2u32));
+            let len = map.len();
+            LazyMapOps {
+                map,
+                maps: LazyMap::new(),
+            }
+            .instantiate()
+            .globalize();
+            len
+        }
+
+        pub fn remove(&mut self, key: u32) -> Option<u32> {
+            self.map.remove(&key)
+        }
+
+        pub fn contains_key(&self, key: u32) -> bool {
+            self.map.contains_key(&key)
+        }
+
+        pub fn len(&self) -> u32 {
+            self.map.len()
+        }
+
+        pub fn keys(&self, page_size: u32) -> Vec<u32> {
+            let mut keys = Vec::new();
+            let mut start_after = None;
+            loop {
+                let page = self.map.entries(start_after.as_ref(), page_size);
+                if page.is_empty() {
+                    break;
+                }
+                start_after = page.last().map(|(k, _)| *k);
+                keys.extend(page.into_iter().map(|(k, _)| k));
+            }
+            keys
+        }
+
+        pub fn first_keys(&self, limit: u32) -> Vec<u32> {
+            self.map
+                .entries(None, limit)
+                .into_iter()
+                .map(|(k, _)| k)
+                .collect()
+        }
+
+        pub fn remove_entry_with_lazy_map(&mut self) {
+            self.maps.insert(1u32, LazyMap::new());
+            self.maps.remove(&1u32);
+        }
+    }
+}
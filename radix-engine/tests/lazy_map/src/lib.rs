@@ -1,3 +1,4 @@
 pub mod cyclic_map;
 pub mod lazy_map;
+pub mod lazy_map_ops;
 pub mod super_lazy_map;
@@ -0,0 +1,135 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use scrypto::prelude::*;
+
+fn new_named(
+    test_runner: &mut TestRunner,
+    owner: NonFungibleAddress,
+) -> (PackageAddress, ComponentAddress) {
+    let package =
+        test_runner.publish_package_with_upgrade_rule("metadata", rule!(require(owner.clone())));
+    let component = test_runner.new_component(package, "Named", "new", args![owner]);
+    (package, component)
+}
+
+#[test]
+fn component_metadata_is_set_on_instantiation() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, _) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let (package, component) = new_named(&mut test_runner, owner);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Named", "read_metadata", args![component])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let metadata: HashMap<String, String> = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(metadata.get("name"), Some(&"Named".to_owned()));
+    assert_eq!(
+        metadata.get("description"),
+        Some(&"A component with a name".to_owned())
+    );
+}
+
+#[test]
+fn component_can_read_its_own_metadata() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, _) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let (_, component) = new_named(&mut test_runner, owner);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(component, "name", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let name: String = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(name, "Named");
+}
+
+#[test]
+fn component_metadata_can_be_updated_by_satisfying_update_rule() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let (_, component) = new_named(&mut test_runner, owner);
+    let metadata = HashMap::from([("name".to_owned(), "Renamed".to_owned())]);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .set_component_metadata(component, metadata.clone())
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert_eq!(test_runner.component(component).metadata(), &metadata);
+}
+
+#[test]
+fn cannot_update_component_metadata_without_satisfying_update_rule() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, _) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let (_, component) = new_named(&mut test_runner, owner);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .set_component_metadata(component, HashMap::new())
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
+
+#[test]
+fn package_owner_can_update_package_metadata() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let (package, _) = new_named(&mut test_runner, owner);
+    let metadata = HashMap::from([("name".to_owned(), "Named Package".to_owned())]);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .set_package_metadata(package, metadata.clone())
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert_eq!(test_runner.package(package).metadata(), &metadata);
+}
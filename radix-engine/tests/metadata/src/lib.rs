@@ -0,0 +1,29 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Named {}
+
+    impl Named {
+        pub fn new(owner: NonFungibleAddress) -> ComponentAddress {
+            Self {}
+                .instantiate()
+                .metadata("name", "Named")
+                .metadata("description", "A component with a name")
+                .updateable_metadata(rule!(require(owner)))
+                .globalize()
+        }
+
+        pub fn read_metadata(component_address: ComponentAddress) -> HashMap<String, String> {
+            borrow_component!(component_address).metadata()
+        }
+
+        pub fn name(&self) -> String {
+            match Runtime::actor().actor() {
+                ScryptoActor::Component(component_address) => {
+                    Runtime::component_metadata(component_address)["name"].clone()
+                }
+                _ => panic!("Not a component"),
+            }
+        }
+    }
+}
@@ -91,6 +91,32 @@ blueprint! {
                 ])
         }
 
+        pub fn create_string_non_fungible() -> Bucket {
+            ResourceBuilder::new_non_fungible()
+                .id_type(NonFungibleIdType::String)
+                .metadata("name", "Katz's Sandwiches")
+                .initial_supply([(
+                    NonFungibleId::String("Pastrami".to_owned()),
+                    Sandwich {
+                        name: "Pastrami".to_owned(),
+                        available: true,
+                    },
+                )])
+        }
+
+        pub fn create_non_fungible_with_wrong_id_type() -> Bucket {
+            ResourceBuilder::new_non_fungible()
+                .id_type(NonFungibleIdType::String)
+                .metadata("name", "Katz's Sandwiches")
+                .initial_supply([(
+                    NonFungibleId::from_u32(1),
+                    Sandwich {
+                        name: "One".to_owned(),
+                        available: true,
+                    },
+                )])
+        }
+
         pub fn verify_does_not_exist(address: NonFungibleAddress) {
             assert_eq!(
                 borrow_resource_manager!(address.resource_address())
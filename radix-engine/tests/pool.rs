@@ -0,0 +1,222 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::PoolError;
+use scrypto::prelude::*;
+
+fn new_provider(test_runner: &mut TestRunner, manager_rule: AccessRule) -> ComponentAddress {
+    let package = test_runner.publish_package("pool");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Provider", "new", args![manager_rule])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    scrypto_decode(&receipt.outputs[0].raw).unwrap()
+}
+
+#[test]
+fn contributions_are_taken_in_the_ratio_of_the_reserves() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let provider = new_provider(&mut test_runner, rule!(deny_all));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "contribute", args![dec!("100"), dec!("50")])
+        .call_method(provider, "contribute", args![dec!("10"), dec!("10")])
+        .call_method(provider, "vault_amounts", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let first_units: Decimal = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    let second_units: Decimal = scrypto_decode(&receipt.outputs[1].raw).unwrap();
+    let amounts: (Decimal, Decimal) = scrypto_decode(&receipt.outputs[2].raw).unwrap();
+    assert_eq!(first_units, dec!("100"));
+    assert_eq!(second_units, dec!("10"));
+    assert_eq!(amounts, (dec!("110"), dec!("55")));
+}
+
+#[test]
+fn pool_units_are_minted_for_the_share_left_after_rounding() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let provider = new_provider(&mut test_runner, rule!(deny_all));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "contribute", args![dec!("100"), dec!("50")])
+        .call_method(provider, "contribute", args![dec!("2.5"), dec!("2")])
+        .call_method(provider, "vault_amounts", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let second_units: Decimal = scrypto_decode(&receipt.outputs[1].raw).unwrap();
+    let amounts: (Decimal, Decimal) = scrypto_decode(&receipt.outputs[2].raw).unwrap();
+    assert_eq!(second_units, dec!("2"));
+    assert_eq!(amounts, (dec!("102.5"), dec!("51")));
+}
+
+#[test]
+fn contributions_to_indivisible_reserve_are_taken_as_rounded() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let provider = new_provider(&mut test_runner, rule!(deny_all));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "contribute", args![dec!("70"), dec!("7")])
+        .call_method(provider, "contribute", args![dec!("30"), dec!("3")])
+        .call_method(provider, "vault_amounts", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let second_units: Decimal = scrypto_decode(&receipt.outputs[1].raw).unwrap();
+    let amounts: (Decimal, Decimal) = scrypto_decode(&receipt.outputs[2].raw).unwrap();
+    assert_eq!(second_units, dec!("19.99999999999999998"));
+    assert_eq!(amounts, (dec!("99.99999999999999997"), dec!("9")));
+}
+
+#[test]
+fn redeem_pays_out_share_of_reserves_rounded_down() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let provider = new_provider(&mut test_runner, rule!(deny_all));
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "contribute", args![dec!("110"), dec!("55")])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "redeem", args![dec!("55")])
+        .call_method(provider, "vault_amounts", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let redeemed: Vec<Decimal> = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    let amounts: (Decimal, Decimal) = scrypto_decode(&receipt.outputs[1].raw).unwrap();
+    assert_eq!(redeemed, vec![dec!("55"), dec!("27")]);
+    assert_eq!(amounts, (dec!("55"), dec!("28")));
+}
+
+#[test]
+fn first_contribution_must_include_every_resource() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let provider = new_provider(&mut test_runner, rule!(deny_all));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "contribute", args![dec!("100"), dec!("0")])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    if !matches!(
+        error,
+        RuntimeError::PoolError(PoolError::MissingContribution(_))
+    ) {
+        panic!("Expected missing contribution but got: {:?}", error);
+    }
+}
+
+#[test]
+fn cannot_contribute_resource_not_in_pool() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let provider = new_provider(&mut test_runner, rule!(deny_all));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "contribute_other", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    if !matches!(
+        error,
+        RuntimeError::PoolError(PoolError::ResourceNotInPool(_))
+    ) {
+        panic!("Expected resource not in pool but got: {:?}", error);
+    }
+}
+
+#[test]
+fn protected_methods_require_manager_rule() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk) = test_runner.new_key_pair();
+    let manager = NonFungibleAddress::from_public_key(&pk.into());
+    let provider = new_provider(&mut test_runner, rule!(require(manager)));
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "contribute", args![dec!("100"), dec!("50")])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "protected_withdraw", args![dec!("30")])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let unauthorized_receipt = test_runner.validate_and_execute(&transaction);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(provider, "protected_withdraw", args![dec!("30")])
+        .call_method(provider, "protected_deposit", args![dec!("10")])
+        .call_method(provider, "vault_amounts", args![])
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let authorized_receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = unauthorized_receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+    authorized_receipt.result.expect("Should be okay");
+    let amounts: (Decimal, Decimal) = scrypto_decode(&authorized_receipt.outputs[2].raw).unwrap();
+    assert_eq!(amounts, (dec!("80"), dec!("50")));
+}
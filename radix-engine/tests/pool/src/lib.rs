@@ -0,0 +1,92 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Provider {
+        pool: Pool,
+        a: Vault,
+        b: Vault,
+        pool_units: Vault,
+    }
+
+    impl Provider {
+        /// Creates two tokens, a pool of them and a provider holding all of their supply.
+        pub fn new(manager_rule: AccessRule) -> ComponentAddress {
+            let a = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_MAXIMUM)
+                .initial_supply(1000);
+            let b = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(1000);
+            let pool = Pool::new(vec![a.resource_address(), b.resource_address()], manager_rule);
+            let pool_units = Vault::new(pool.pool_unit_resource_address());
+
+            Self {
+                pool,
+                a: Vault::with_bucket(a),
+                b: Vault::with_bucket(b),
+                pool_units,
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        fn put(&mut self, bucket: Bucket) {
+            if bucket.resource_address() == self.a.resource_address() {
+                self.a.put(bucket)
+            } else {
+                self.b.put(bucket)
+            }
+        }
+
+        /// Contributes to the pool, and returns the amount of pool units minted.
+        pub fn contribute(&mut self, amount_a: Decimal, amount_b: Decimal) -> Decimal {
+            let buckets = vec![self.a.take(amount_a), self.b.take(amount_b)];
+            let (pool_units, change) = self.pool.contribute(buckets);
+            let amount = pool_units.amount();
+            self.pool_units.put(pool_units);
+            for bucket in change {
+                self.put(bucket);
+            }
+            amount
+        }
+
+        /// Contributes a resource which is not in the pool.
+        pub fn contribute_other(&mut self) {
+            let other = ResourceBuilder::new_fungible().initial_supply(10);
+            let (pool_units, change) = self.pool.contribute(vec![other]);
+            self.pool_units.put(pool_units);
+            for bucket in change {
+                self.put(bucket);
+            }
+        }
+
+        /// Redeems pool units, and returns the amounts of the resources received.
+        pub fn redeem(&mut self, amount: Decimal) -> Vec<Decimal> {
+            let buckets = self.pool.redeem(self.pool_units.take(amount));
+            let mut amounts = Vec::new();
+            for bucket in buckets {
+                amounts.push(bucket.amount());
+                self.put(bucket);
+            }
+            amounts
+        }
+
+        pub fn protected_deposit(&mut self, amount: Decimal) {
+            self.pool.protected_deposit(self.a.take(amount))
+        }
+
+        pub fn protected_withdraw(&mut self, amount: Decimal) {
+            let bucket = self.pool.protected_withdraw(self.a.resource_address(), amount);
+            self.a.put(bucket)
+        }
+
+        /// Returns the amounts of both resources in the pool.
+        pub fn vault_amounts(&self) -> (Decimal, Decimal) {
+            let amounts = self.pool.vault_amounts();
+            (
+                amounts[&self.a.resource_address()],
+                amounts[&self.b.resource_address()],
+            )
+        }
+    }
+}
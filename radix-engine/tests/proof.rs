@@ -257,6 +257,35 @@ fn can_create_proof_from_account_and_pass_on() {
     receipt.result.expect("Should be okay.");
 }
 
+#[test]
+fn can_pass_entire_auth_zone_as_proofs() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address =
+        test_runner.create_fungible_resource(100.into(), DIVISIBILITY_MAXIMUM, account);
+    let package_address = test_runner.publish_package("proof");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(resource_address, account)
+        .call_function(
+            package_address,
+            "VaultProof",
+            "receive_proofs",
+            args![Expression::EntireAuthZone],
+        )
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    println!("{:?}", receipt);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
 #[test]
 fn cant_move_restricted_proof() {
     // Arrange
@@ -68,6 +68,11 @@ blueprint! {
             // auto dropped here
         }
 
+        pub fn receive_proofs(proofs: Vec<Proof>) {
+            assert!(!proofs.is_empty());
+            // auto dropped here
+        }
+
         pub fn receive_proof_and_push_to_auth_zone(proof: Proof) {
             ComponentAuthZone::push(proof); // should fail here
         }
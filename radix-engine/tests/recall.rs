@@ -0,0 +1,216 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::VaultError;
+use scrypto::engine::types::VaultId;
+use scrypto::prelude::*;
+
+struct Setup {
+    issuer: ComponentAddress,
+    holder: ComponentAddress,
+    vault_id: VaultId,
+    regulator_pk: EcdsaPublicKey,
+    regulator_sk: EcdsaPrivateKey,
+}
+
+fn setup(test_runner: &mut TestRunner) -> Setup {
+    let package = test_runner.publish_package("recall");
+    let (regulator_pk, regulator_sk) = test_runner.new_key_pair();
+    let regulator = NonFungibleAddress::from_public_key(&regulator_pk.into());
+
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "Issuer", "new", args![regulator])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let (issuer, holder): (ComponentAddress, ComponentAddress) =
+        scrypto_decode(&receipt.outputs[0].raw).unwrap();
+
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(holder, "vault_id", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let vault_id: VaultId = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+
+    Setup {
+        issuer,
+        holder,
+        vault_id,
+        regulator_pk,
+        regulator_sk,
+    }
+}
+
+fn withdraw(
+    test_runner: &mut TestRunner,
+    holder: ComponentAddress,
+    account: ComponentAddress,
+) -> Result<(), RuntimeError> {
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(holder, "withdraw", args![dec!("10")])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    test_runner.validate_and_execute(&transaction).result
+}
+
+#[test]
+fn regulator_can_freeze_and_unfreeze_vault() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+    let (_, _, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.issuer, "freeze", args![setup.holder, setup.vault_id])
+        .call_method(setup.holder, "is_frozen", args![])
+        .build(test_runner.get_nonce([setup.regulator_pk]))
+        .sign([&setup.regulator_sk]);
+    let freeze_receipt = test_runner.validate_and_execute(&transaction);
+    let frozen_result = withdraw(&mut test_runner, setup.holder, account);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(
+            setup.issuer,
+            "unfreeze",
+            args![setup.holder, setup.vault_id],
+        )
+        .build(test_runner.get_nonce([setup.regulator_pk]))
+        .sign([&setup.regulator_sk]);
+    let unfreeze_receipt = test_runner.validate_and_execute(&transaction);
+    let unfrozen_result = withdraw(&mut test_runner, setup.holder, account);
+
+    // Assert
+    freeze_receipt.result.expect("Should be okay");
+    let is_frozen: bool = scrypto_decode(&freeze_receipt.outputs[1].raw).unwrap();
+    assert!(is_frozen);
+    assert_eq!(
+        frozen_result,
+        Err(RuntimeError::VaultError(VaultError::VaultFrozen))
+    );
+    unfreeze_receipt.result.expect("Should be okay");
+    unfrozen_result.expect("Should be okay");
+}
+
+#[test]
+fn cannot_create_proof_of_frozen_vault() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.issuer, "freeze", args![setup.holder, setup.vault_id])
+        .build(test_runner.get_nonce([setup.regulator_pk]))
+        .sign([&setup.regulator_sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.holder, "prove", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::VaultError(VaultError::VaultFrozen))
+    );
+}
+
+#[test]
+fn cannot_freeze_vault_without_satisfying_freeze_rule() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.issuer, "freeze", args![setup.holder, setup.vault_id])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
+
+#[test]
+fn regulator_can_recall_from_frozen_vault() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+    let (_, _, account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.issuer, "freeze", args![setup.holder, setup.vault_id])
+        .build(test_runner.get_nonce([setup.regulator_pk]))
+        .sign([&setup.regulator_sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .recall_vault(setup.holder, setup.vault_id, dec!("40"))
+        .call_method(
+            setup.issuer,
+            "recall",
+            args![setup.holder, setup.vault_id, dec!("20")],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .call_method(setup.holder, "balance", args![])
+        .build(test_runner.get_nonce([setup.regulator_pk]))
+        .sign([&setup.regulator_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let balance: Decimal = scrypto_decode(&receipt.outputs[3].raw).unwrap();
+    assert_eq!(balance, dec!("40"));
+}
+
+#[test]
+fn cannot_recall_without_satisfying_recall_rule() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+    let (_, _, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .recall_vault(setup.holder, setup.vault_id, dec!("40"))
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
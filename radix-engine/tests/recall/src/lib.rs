@@ -0,0 +1,78 @@
+use scrypto::engine::types::VaultId;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Issuer {
+        token: ResourceAddress,
+    }
+
+    impl Issuer {
+        /// Creates a regulated token, and a holder of all of its supply.
+        pub fn new(regulator: NonFungibleAddress) -> (ComponentAddress, ComponentAddress) {
+            let tokens = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .recallable(rule!(require(regulator.clone())), LOCKED)
+                .freezable(rule!(require(regulator)), LOCKED)
+                .initial_supply(100);
+            let token = tokens.resource_address();
+            let holder: ComponentAddress = scrypto_decode(&Runtime::call_function(
+                Runtime::package_address(),
+                "Holder",
+                "new",
+                args![tokens],
+            ))
+            .unwrap();
+
+            let issuer = Self { token }.instantiate().globalize();
+            (issuer, holder)
+        }
+
+        pub fn freeze(&self, holder: ComponentAddress, vault_id: VaultId) {
+            borrow_resource_manager!(self.token).freeze_vault(holder, vault_id)
+        }
+
+        pub fn unfreeze(&self, holder: ComponentAddress, vault_id: VaultId) {
+            borrow_resource_manager!(self.token).unfreeze_vault(holder, vault_id)
+        }
+
+        pub fn recall(&self, holder: ComponentAddress, vault_id: VaultId, amount: Decimal) -> Bucket {
+            borrow_resource_manager!(self.token).recall(holder, vault_id, amount)
+        }
+    }
+}
+
+blueprint! {
+    struct Holder {
+        tokens: Vault,
+    }
+
+    impl Holder {
+        pub fn new(tokens: Bucket) -> ComponentAddress {
+            Self {
+                tokens: Vault::with_bucket(tokens),
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn vault_id(&self) -> VaultId {
+            self.tokens.0
+        }
+
+        pub fn balance(&self) -> Decimal {
+            self.tokens.amount()
+        }
+
+        pub fn is_frozen(&self) -> bool {
+            self.tokens.is_frozen()
+        }
+
+        pub fn withdraw(&mut self, amount: Decimal) -> Bucket {
+            self.tokens.take(amount)
+        }
+
+        pub fn prove(&self) -> Proof {
+            self.tokens.create_proof()
+        }
+    }
+}
@@ -0,0 +1,140 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::ledger::InMemorySubstateStore;
+use scrypto::prelude::*;
+
+#[test]
+fn failed_try_call_should_be_rolled_back() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("rollback");
+    let rejecting_pool = test_runner.new_component(package, "Pool", "new", args![false]);
+    let accepting_pool = test_runner.new_component(package, "Pool", "new", args![true]);
+    let (_, _, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", args![])
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "Router",
+                "route",
+                args![
+                    vec![rejecting_pool, accepting_pool],
+                    scrypto::resource::Bucket(bucket_id)
+                ],
+            )
+        })
+        .call_method(rejecting_pool, "deposits", args![])
+        .call_method(accepting_pool, "deposits", args![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let deposits: Vec<u32> = receipt.outputs[3..5]
+        .iter()
+        .map(|output| scrypto_decode(&output.raw).unwrap())
+        .collect();
+    assert_eq!(deposits, vec![0, 1]);
+    let state_changes = receipt.state_changes;
+    assert!(state_changes
+        .balance_change(rejecting_pool, RADIX_TOKEN)
+        .is_zero());
+    assert!(state_changes
+        .balance_change(accepting_pool, RADIX_TOKEN)
+        .is_positive());
+}
+
+#[test]
+fn bucket_should_be_returned_when_all_try_calls_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("rollback");
+    let rejecting_pool = test_runner.new_component(package, "Pool", "new", args![false]);
+    let (_, _, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", args![])
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "Router",
+                "route",
+                args![vec![rejecting_pool], scrypto::resource::Bucket(bucket_id)],
+            )
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let state_changes = receipt.state_changes;
+    assert!(state_changes
+        .balance_change(rejecting_pool, RADIX_TOKEN)
+        .is_zero());
+    assert!(state_changes
+        .balance_change(account, RADIX_TOKEN)
+        .is_positive());
+}
+
+#[test]
+fn nested_try_call_should_only_roll_back_the_failed_call() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package = test_runner.publish_package("rollback");
+    let rejecting_pool = test_runner.new_component(package, "Pool", "new", args![false]);
+    let forwarding_pool = test_runner.new_component(package, "Pool", "new", args![true]);
+    let (_, _, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", args![])
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.call_function(
+                package,
+                "Router",
+                "route_through",
+                args![
+                    forwarding_pool,
+                    rejecting_pool,
+                    scrypto::resource::Bucket(bucket_id)
+                ],
+            )
+        })
+        .call_method(rejecting_pool, "deposits", args![])
+        .call_method(forwarding_pool, "deposits", args![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let deposits: Vec<u32> = receipt.outputs[3..5]
+        .iter()
+        .map(|output| scrypto_decode(&output.raw).unwrap())
+        .collect();
+    assert_eq!(deposits, vec![0, 1]);
+    let state_changes = receipt.state_changes;
+    assert!(state_changes
+        .balance_change(rejecting_pool, RADIX_TOKEN)
+        .is_zero());
+    assert!(state_changes
+        .balance_change(forwarding_pool, RADIX_TOKEN)
+        .is_positive());
+}
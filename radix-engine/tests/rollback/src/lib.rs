@@ -0,0 +1,72 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Pool {
+        vault: Vault,
+        deposits: u32,
+        accepting: bool,
+    }
+
+    impl Pool {
+        pub fn new(accepting: bool) -> ComponentAddress {
+            Self {
+                vault: Vault::new(RADIX_TOKEN),
+                deposits: 0,
+                accepting,
+            }
+            .instantiate()
+            .globalize()
+        }
+
+        pub fn deposit(&mut self, bucket: Bucket) {
+            self.vault.put(bucket);
+            self.deposits += 1;
+            assert!(self.accepting, "Deposit rejected");
+        }
+
+        pub fn deposits(&self) -> u32 {
+            self.deposits
+        }
+
+        /// Deposits into another pool, keeping the bucket if that pool rejects it.
+        pub fn forward(&mut self, pool: ComponentAddress, bucket: Bucket) {
+            self.deposits += 1;
+            let bucket_id = bucket.0;
+            if Runtime::try_call_method(pool, "deposit", args![Bucket(bucket_id)]).is_err() {
+                self.vault.put(Bucket(bucket_id));
+            }
+        }
+    }
+}
+
+blueprint! {
+    struct Router {}
+
+    impl Router {
+        /// Deposits into the first pool accepting the bucket, or returns the bucket if none does.
+        pub fn route(pools: Vec<ComponentAddress>, bucket: Bucket) -> Option<Bucket> {
+            let bucket_id = bucket.0;
+            for pool in pools {
+                if Runtime::try_call_method(pool, "deposit", args![Bucket(bucket_id)]).is_ok() {
+                    return None;
+                }
+            }
+            Some(Bucket(bucket_id))
+        }
+
+        /// Forwards the bucket to a pool through another pool, or returns it if that fails.
+        pub fn route_through(
+            forwarder: ComponentAddress,
+            pool: ComponentAddress,
+            bucket: Bucket,
+        ) -> Option<Bucket> {
+            let bucket_id = bucket.0;
+            if Runtime::try_call_method(forwarder, "forward", args![pool, Bucket(bucket_id)])
+                .is_ok()
+            {
+                return None;
+            }
+            Some(Bucket(bucket_id))
+        }
+    }
+}
@@ -0,0 +1,180 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use scrypto::prelude::*;
+
+struct RoyaltySetup {
+    fee_component: ComponentAddress,
+    package: PackageAddress,
+    service: ComponentAddress,
+    owner_public_key: EcdsaPublicKey,
+    owner_private_key: EcdsaPrivateKey,
+    account: ComponentAddress,
+}
+
+fn setup(test_runner: &mut TestRunner) -> RoyaltySetup {
+    let (pk, sk, account) = test_runner.new_account();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let fee_component = test_runner.create_fee_component();
+    let package =
+        test_runner.publish_package_with_upgrade_rule("royalty", rule!(require(owner.clone())));
+    let transaction = test_runner
+        .new_transaction_builder()
+        .set_package_royalty_config(
+            package,
+            "Service",
+            RoyaltyConfig::new().method("serve", dec!("1")),
+        )
+        .call_function(package, "Service", "new", args![owner])
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let service = receipt.new_component_addresses[0];
+
+    RoyaltySetup {
+        fee_component,
+        package,
+        service,
+        owner_public_key: pk,
+        owner_private_key: sk,
+        account,
+    }
+}
+
+fn royalties_of(test_runner: &TestRunner, setup: &RoyaltySetup) -> (Decimal, Decimal) {
+    (
+        test_runner.package(setup.package).royalty(),
+        test_runner.component(setup.service).royalty(),
+    )
+}
+
+#[test]
+fn calling_method_should_pay_package_and_component_royalties() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.fee_component, "lock_fee", args![dec!("10")])
+        .call_method(setup.service, "serve", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert_eq!(receipt.fee_summary.royalties, dec!("3"));
+    assert_eq!(royalties_of(&test_runner, &setup), (dec!("1"), dec!("2")));
+}
+
+#[test]
+fn royalties_not_covered_by_locked_fees_should_fail() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.fee_component, "lock_fee", args![dec!("2")])
+        .call_method(setup.service, "serve", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(receipt.result, Err(RuntimeError::RoyaltyNotPaid(dec!("3"))));
+    assert_eq!(
+        royalties_of(&test_runner, &setup),
+        (Decimal::zero(), Decimal::zero())
+    );
+}
+
+#[test]
+fn owner_can_claim_royalties() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.fee_component, "lock_fee", args![dec!("10")])
+        .call_method(setup.service, "serve", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .claim_package_royalty(setup.package)
+        .call_method(setup.service, "claim_royalty", args![])
+        .call_method_with_all_resources(setup.account, "deposit_batch")
+        .build(test_runner.get_nonce([setup.owner_public_key]))
+        .sign([&setup.owner_private_key]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert_eq!(
+        royalties_of(&test_runner, &setup),
+        (Decimal::zero(), Decimal::zero())
+    );
+}
+
+#[test]
+fn cannot_claim_royalties_without_owner_signature() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .claim_package_royalty(setup.package)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
+
+#[test]
+fn component_cannot_claim_royalties_of_another_component() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(
+            setup.service,
+            "claim_royalty_of",
+            args![setup.fee_component],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::RoyaltyNotClaimable(setup.fee_component))
+    );
+}
@@ -0,0 +1,34 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Service {}
+
+    impl Service {
+        pub fn new(owner: NonFungibleAddress) -> ComponentAddress {
+            let access_rules = AccessRules::new()
+                .method("claim_royalty", rule!(require(owner)))
+                .default(rule!(allow_all));
+
+            Self {}
+                .instantiate()
+                .add_access_check(access_rules)
+                .set_royalty_config(RoyaltyConfig::new().method("serve", dec!("2")))
+                .globalize()
+        }
+
+        pub fn serve(&self) -> u32 {
+            42
+        }
+
+        pub fn claim_royalty(&mut self) -> Bucket {
+            match Runtime::actor().actor() {
+                ScryptoActor::Component(addr) => borrow_component!(addr).claim_royalty(),
+                _ => panic!("Not a component"),
+            }
+        }
+
+        pub fn claim_royalty_of(&self, component: ComponentAddress) -> Bucket {
+            borrow_component!(component).claim_royalty()
+        }
+    }
+}
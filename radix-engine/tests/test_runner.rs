@@ -1,5 +1,5 @@
 use radix_engine::ledger::*;
-use radix_engine::model::{Component, Receipt, SignedTransaction};
+use radix_engine::model::{Component, Package, Receipt, SignedTransaction};
 use radix_engine::transaction::*;
 use scrypto::abi;
 use scrypto::prelude::*;
@@ -15,6 +15,20 @@ impl<'l> TestRunner<'l> {
         Self { executor }
     }
 
+    /// Sets the price of a cost unit, so that transactions have to lock fees.
+    pub fn with_cost_unit_price(self, cost_unit_price: Decimal) -> Self {
+        Self {
+            executor: self.executor.with_cost_unit_price(cost_unit_price),
+        }
+    }
+
+    /// Sets the maximum number of cost units a transaction can consume.
+    pub fn with_cost_unit_limit(self, cost_unit_limit: u32) -> Self {
+        Self {
+            executor: self.executor.with_cost_unit_limit(cost_unit_limit),
+        }
+    }
+
     pub fn new_transaction_builder(&self) -> TransactionBuilder {
         TransactionBuilder::new()
     }
@@ -50,10 +64,32 @@ impl<'l> TestRunner<'l> {
         self.executor.publish_package(&Self::compile(name)).unwrap()
     }
 
+    pub fn publish_package_with_upgrade_rule(
+        &mut self,
+        name: &str,
+        upgrade_rule: AccessRule,
+    ) -> PackageAddress {
+        let transaction = TransactionBuilder::new()
+            .publish_package_with_upgrade_rule(&Self::compile(name), upgrade_rule)
+            .build(self.executor.get_nonce([]))
+            .sign([]);
+        let receipt = self.executor.validate_and_execute(&transaction).unwrap();
+        receipt.result.expect("Should be okay");
+        receipt.new_package_addresses[0]
+    }
+
     pub fn compile(name: &str) -> Vec<u8> {
         compile_package!(format!("./tests/{}", name))
     }
 
+    pub fn package(&self, package_address: PackageAddress) -> Package {
+        self.executor
+            .substate_store()
+            .get_decoded_substate(&package_address)
+            .map(|(package, _)| package)
+            .unwrap()
+    }
+
     pub fn component(&self, component_address: ComponentAddress) -> Component {
         self.executor
             .substate_store()
@@ -82,6 +118,14 @@ impl<'l> TestRunner<'l> {
         self.executor.get_nonce(intended_signers)
     }
 
+    pub fn set_epoch(&mut self, epoch: u64) {
+        self.executor.substate_store_mut().set_epoch(epoch);
+    }
+
+    pub fn next_epoch(&mut self) -> u64 {
+        self.executor.next_epoch().unwrap()
+    }
+
     pub fn set_auth(
         &mut self,
         account: (&EcdsaPublicKey, &EcdsaPrivateKey, ComponentAddress),
@@ -229,6 +273,43 @@ impl<'l> TestRunner<'l> {
         receipt.new_resource_addresses[0]
     }
 
+    /// Calls a function which instantiates a component, and returns the new component.
+    pub fn new_component(
+        &mut self,
+        package_address: PackageAddress,
+        blueprint_name: &str,
+        function: &str,
+        args: Vec<Vec<u8>>,
+    ) -> ComponentAddress {
+        let transaction = TransactionBuilder::new()
+            .call_function(package_address, blueprint_name, function, args)
+            .build(self.executor.get_nonce([]))
+            .sign([]);
+        let receipt = self.executor.validate_and_execute(&transaction).unwrap();
+        receipt.result.expect("Should be okay");
+        receipt.new_component_addresses[0]
+    }
+
+    /// Creates a component holding free XRD, which transactions can lock fees from.
+    pub fn create_fee_component(&mut self) -> ComponentAddress {
+        let package = self.publish_package("fee");
+        let transaction = TransactionBuilder::new()
+            .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+            .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+                builder.call_function(
+                    package,
+                    "Fee",
+                    "new",
+                    vec![scrypto_encode(&scrypto::resource::Bucket(bucket_id))],
+                )
+            })
+            .build(self.executor.get_nonce([]))
+            .sign([]);
+        let receipt = self.executor.validate_and_execute(&transaction).unwrap();
+        receipt.result.expect("Should be okay");
+        receipt.new_component_addresses[0]
+    }
+
     pub fn instantiate_component(
         &mut self,
         package_address: PackageAddress,
@@ -0,0 +1,198 @@
+use radix_engine::errors::TransactionValidationError;
+use radix_engine::ledger::*;
+use radix_engine::model::{Network, TransactionHeader};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn replayed_transaction_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    receipt.result.expect("Should be okay");
+
+    // Act
+    let result = executor.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(TransactionValidationError::IntentAlreadyCommitted(
+            transaction.transaction.raw_hash()
+        ))
+    );
+}
+
+#[test]
+fn replayed_validated_transaction_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let validated = transaction.validate(Network::LocalSimulator, 0).unwrap();
+    let receipt = executor.execute(validated.clone()).unwrap();
+    receipt.result.expect("Should be okay");
+
+    // Act
+    let result = executor.execute(validated);
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(TransactionValidationError::IntentAlreadyCommitted(
+            transaction.transaction.raw_hash()
+        ))
+    );
+}
+
+#[test]
+fn committed_intent_should_be_pruned_once_expired() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .header(TransactionHeader {
+            end_epoch_exclusive: 10,
+            ..TransactionHeader::default()
+        })
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let intent_hash = transaction.transaction.raw_hash();
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    receipt.result.expect("Should be okay");
+    assert!(executor.substate_store().is_intent_committed(&intent_hash));
+
+    // Act
+    executor.substate_store_mut().set_epoch(10);
+    let result = executor.validate_and_execute(&transaction);
+
+    // Assert
+    assert!(!executor.substate_store().is_intent_committed(&intent_hash));
+    assert_eq!(
+        result.err(),
+        Some(TransactionValidationError::TransactionExpired(10))
+    );
+}
+
+#[test]
+fn validated_transaction_should_be_rejected_once_expired() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .header(TransactionHeader {
+            end_epoch_exclusive: 10,
+            ..TransactionHeader::default()
+        })
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let validated = transaction.validate(Network::LocalSimulator, 0).unwrap();
+    let receipt = executor.execute(validated.clone()).unwrap();
+    receipt.result.expect("Should be okay");
+
+    // Act
+    executor.substate_store_mut().set_epoch(10);
+    let result = executor.execute(validated);
+
+    // Assert
+    assert!(!executor
+        .substate_store()
+        .is_intent_committed(&transaction.transaction.raw_hash()));
+    assert_eq!(
+        result.err(),
+        Some(TransactionValidationError::TransactionExpired(10))
+    );
+}
+
+#[test]
+fn entire_worktop_expression_should_pass_all_resources() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (_, _, account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec![])
+        .call_method(account, "deposit_batch", args![Expression::EntireWorktop])
+        .build(executor.get_nonce([]))
+        .sign([]);
+
+    // Act
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert!(receipt
+        .state_changes
+        .balance_change(account, RADIX_TOKEN)
+        .is_positive());
+}
+
+#[test]
+fn preview_should_not_commit_anything() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (pk, sk, account) = executor.new_account();
+    let (_, _, other_account) = executor.new_account();
+    let nonce = executor.get_nonce([pk]);
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(nonce)
+        .sign([&sk]);
+
+    // Act
+    let receipt = executor.preview(&transaction).unwrap();
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert!(receipt.commit_receipt.is_none());
+    assert!(receipt.state_changes.balance_change(account, RADIX_TOKEN).is_negative());
+    assert_eq!(executor.get_nonce([pk]), nonce);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn preview_with_assumed_signers_should_pass_auth() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let (pk, _, account) = executor.new_account();
+    let (_, _, other_account) = executor.new_account();
+    let transaction = TransactionBuilder::new()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(executor.get_nonce([pk]));
+
+    // Act
+    let unsigned_receipt = executor
+        .preview_with_signers(&transaction, Vec::new())
+        .unwrap();
+    let receipt = executor
+        .preview_with_signers(&transaction, vec![pk.into()])
+        .unwrap();
+
+    // Assert
+    assert!(unsigned_receipt.result.is_err());
+    receipt.result.expect("Should be okay");
+    assert!(receipt.commit_receipt.is_none());
+}
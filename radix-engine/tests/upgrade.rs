@@ -0,0 +1,185 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::PackageError;
+use scrypto::prelude::*;
+
+#[test]
+fn upgraded_package_can_migrate_component() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let v1 = test_runner.publish_package_with_upgrade_rule("upgrade_v1", rule!(require(owner)));
+    let counter = test_runner.new_component(v1, "Counter", "new", args![true]);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(counter, "increment", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .upgrade_package(v1, &TestRunner::compile("upgrade_v2"))
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let v2 = receipt.new_package_addresses[0];
+    let transaction = test_runner
+        .new_transaction_builder()
+        .migrate_component(counter, v2, "migrate")
+        .call_method(counter, "count", args![])
+        .call_method(counter, "migrated", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let count: u64 = scrypto_decode(&receipt.outputs[1].raw).unwrap();
+    let migrated: bool = scrypto_decode(&receipt.outputs[2].raw).unwrap();
+    assert_eq!(count, 1);
+    assert!(migrated);
+    let package = test_runner.package(v2);
+    assert_eq!(package.version(), 2);
+    assert_eq!(package.predecessor(), Some(v1));
+    assert_eq!(test_runner.package(v1).successor(), Some(v2));
+}
+
+#[test]
+fn cannot_upgrade_package_without_satisfying_upgrade_rule() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, _) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let v1 = test_runner.publish_package_with_upgrade_rule("upgrade_v1", rule!(require(owner)));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .upgrade_package(v1, &TestRunner::compile("upgrade_v2"))
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
+
+#[test]
+fn cannot_upgrade_the_same_version_twice() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let v1 = test_runner.publish_package_with_upgrade_rule("upgrade_v1", rule!(require(owner)));
+    let transaction = test_runner
+        .new_transaction_builder()
+        .upgrade_package(v1, &TestRunner::compile("upgrade_v2"))
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let v2 = receipt.new_package_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .upgrade_package(v1, &TestRunner::compile("upgrade_v2"))
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::PackageError(PackageError::AlreadyUpgraded(
+            v1
+        )))
+    );
+    assert!(receipt.new_package_addresses.is_empty());
+    assert_eq!(test_runner.package(v1).successor(), Some(v2));
+}
+
+#[test]
+fn cannot_migrate_component_which_did_not_opt_in() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let v1 = test_runner.publish_package_with_upgrade_rule("upgrade_v1", rule!(require(owner)));
+    let counter = test_runner.new_component(v1, "Counter", "new", args![false]);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .upgrade_package(v1, &TestRunner::compile("upgrade_v2"))
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let v2 = receipt.new_package_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .migrate_component(counter, v2, "migrate")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::MigrationNotAllowed(counter))
+    );
+}
+
+#[test]
+fn migrate_function_cannot_be_called_as_a_method() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk) = test_runner.new_key_pair();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let v1 = test_runner.publish_package_with_upgrade_rule("upgrade_v1", rule!(require(owner)));
+    let counter = test_runner.new_component(v1, "Counter", "new", args![true]);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .upgrade_package(v1, &TestRunner::compile("upgrade_v2"))
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let v2 = receipt.new_package_addresses[0];
+    let transaction = test_runner
+        .new_transaction_builder()
+        .migrate_component(counter, v2, "migrate")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(counter, "migrate", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(receipt.result, Err(RuntimeError::InvokeError));
+}
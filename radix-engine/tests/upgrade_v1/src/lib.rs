@@ -0,0 +1,28 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Counter {
+        count: u32,
+    }
+
+    impl Counter {
+        pub fn new(migratable: bool) -> ComponentAddress {
+
+            let mut component = Self { count: 0 }
+                .instantiate()
+                .add_access_check(AccessRules::new().default(rule!(allow_all)));
+            if migratable {
+                component = component.migratable(rule!(allow_all));
+            }
+            component.globalize()
+        }
+
+        pub fn increment(&mut self) {
+            self.count += 1;
+        }
+
+        pub fn count(&self) -> u32 {
+            self.count
+        }
+    }
+}
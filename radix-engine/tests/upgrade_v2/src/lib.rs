@@ -0,0 +1,50 @@
+use scrypto::prelude::*;
+
+/// The state of a counter instantiated by the first version of the package.
+#[derive(TypeId, Decode)]
+pub struct CounterV1 {
+    count: u32,
+}
+
+blueprint! {
+    struct Counter {
+        count: u64,
+        migrated: bool,
+    }
+
+    impl Counter {
+        pub fn new(migratable: bool) -> ComponentAddress {
+
+            let mut component = Self {
+                count: 0,
+                migrated: false,
+            }
+            .instantiate()
+            .add_access_check(AccessRules::new().default(rule!(allow_all)));
+            if migratable {
+                component = component.migratable(rule!(allow_all));
+            }
+            component.globalize()
+        }
+
+        #[migrate]
+        pub fn migrate(old: CounterV1) -> Self {
+            Self {
+                count: old.count as u64,
+                migrated: true,
+            }
+        }
+
+        pub fn increment(&mut self) {
+            self.count += 1;
+        }
+
+        pub fn count(&self) -> u64 {
+            self.count
+        }
+
+        pub fn migrated(&self) -> bool {
+            self.migrated
+        }
+    }
+}
@@ -0,0 +1,221 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::{Receipt, ValidatorError, UNSTAKE_DELAY};
+use scrypto::prelude::*;
+
+struct Setup {
+    validator: ComponentAddress,
+    account: ComponentAddress,
+    pk: EcdsaPublicKey,
+    sk: EcdsaPrivateKey,
+}
+
+/// Creates a validator owned by the key of a new account.
+fn setup(test_runner: &mut TestRunner) -> Setup {
+    let (pk, sk, account) = test_runner.new_account();
+    let owner = NonFungibleAddress::from_public_key(&pk.into());
+    let transaction = test_runner
+        .new_transaction_builder()
+        .new_validator(&rule!(require(owner)))
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+
+    Setup {
+        validator: receipt.new_component_addresses[0],
+        account,
+        pk,
+        sk,
+    }
+}
+
+fn call_validator(test_runner: &mut TestRunner, setup: &Setup, method: &str) -> Receipt {
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.validator, method, args![])
+        .build(test_runner.get_nonce([setup.pk]))
+        .sign([&setup.sk]);
+    test_runner.validate_and_execute(&transaction)
+}
+
+fn stake(test_runner: &mut TestRunner, setup: &Setup, amount: Decimal) -> Receipt {
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(amount, RADIX_TOKEN, setup.account)
+        .take_from_worktop(RADIX_TOKEN, |builder, bucket_id| {
+            builder.stake(setup.validator, bucket_id)
+        })
+        .call_method_with_all_resources(setup.account, "deposit_batch")
+        .build(test_runner.get_nonce([setup.pk]))
+        .sign([&setup.sk]);
+    test_runner.validate_and_execute(&transaction)
+}
+
+fn stake_amount(test_runner: &mut TestRunner, setup: &Setup) -> Decimal {
+    let receipt = call_validator(test_runner, setup, "get_stake_amount");
+    scrypto_decode(&receipt.outputs[0].raw).unwrap()
+}
+
+fn resource_address(test_runner: &mut TestRunner, setup: &Setup, getter: &str) -> ResourceAddress {
+    let receipt = call_validator(test_runner, setup, getter);
+    scrypto_decode(&receipt.outputs[0].raw).unwrap()
+}
+
+#[test]
+fn registered_validators_share_the_emission_by_stake() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let first = setup(&mut test_runner);
+    let second = setup(&mut test_runner);
+    let unregistered = setup(&mut test_runner);
+    for setup in [&first, &second] {
+        call_validator(&mut test_runner, setup, "register")
+            .result
+            .expect("Should be okay");
+    }
+    stake(&mut test_runner, &first, dec!("300"))
+        .result
+        .expect("Should be okay");
+    stake(&mut test_runner, &second, dec!("100"))
+        .result
+        .expect("Should be okay");
+    stake(&mut test_runner, &unregistered, dec!("100"))
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let epoch = test_runner.next_epoch();
+
+    // Assert
+    assert_eq!(epoch, 1);
+    assert_eq!(stake_amount(&mut test_runner, &first), dec!("1050"));
+    assert_eq!(stake_amount(&mut test_runner, &second), dec!("350"));
+    assert_eq!(stake_amount(&mut test_runner, &unregistered), dec!("100"));
+}
+
+#[test]
+fn stake_units_are_worth_their_share_of_the_stake() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+    call_validator(&mut test_runner, &setup, "register")
+        .result
+        .expect("Should be okay");
+    stake(&mut test_runner, &setup, dec!("1000"))
+        .result
+        .expect("Should be okay");
+    test_runner.next_epoch();
+
+    // Act
+    let receipt = stake(&mut test_runner, &setup, dec!("1000"));
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    let stake_units = resource_address(&mut test_runner, &setup, "get_stake_unit_resource_address");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.account, "balance", args![stake_units])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let balance: Decimal = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(balance, dec!("1500"));
+}
+
+#[test]
+fn unstaked_xrd_can_be_claimed_after_the_delay() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+    stake(&mut test_runner, &setup, dec!("100"))
+        .result
+        .expect("Should be okay");
+    let stake_units = resource_address(&mut test_runner, &setup, "get_stake_unit_resource_address");
+    let unstake_claims = resource_address(
+        &mut test_runner,
+        &setup,
+        "get_unstake_claim_resource_address",
+    );
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(stake_units, setup.account)
+        .take_from_worktop(stake_units, |builder, bucket_id| {
+            builder.unstake(setup.validator, bucket_id)
+        })
+        .call_method_with_all_resources(setup.account, "deposit_batch")
+        .build(test_runner.get_nonce([setup.pk]))
+        .sign([&setup.sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+    let claim = |test_runner: &mut TestRunner| {
+        let transaction = test_runner
+            .new_transaction_builder()
+            .withdraw_from_account(unstake_claims, setup.account)
+            .take_from_worktop(unstake_claims, |builder, bucket_id| {
+                builder.call_method(
+                    setup.validator,
+                    "claim_xrd",
+                    args![scrypto::resource::Bucket(bucket_id)],
+                )
+            })
+            .call_method_with_all_resources(setup.account, "deposit_batch")
+            .build(test_runner.get_nonce([setup.pk]))
+            .sign([&setup.sk]);
+        test_runner.validate_and_execute(&transaction)
+    };
+
+    // Act
+    let early_receipt = claim(&mut test_runner);
+    for _ in 0..UNSTAKE_DELAY {
+        test_runner.next_epoch();
+    }
+    let receipt = claim(&mut test_runner);
+
+    // Assert
+    assert_eq!(
+        early_receipt.result,
+        Err(RuntimeError::ValidatorError(
+            ValidatorError::UnstakeNotUnlocked(UNSTAKE_DELAY)
+        ))
+    );
+    receipt.result.expect("Should be okay");
+    assert_eq!(stake_amount(&mut test_runner, &setup), Decimal::zero());
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.account, "balance", args![RADIX_TOKEN])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let balance: Decimal = scrypto_decode(&receipt.outputs[0].raw).unwrap();
+    assert_eq!(balance, dec!("1000000"));
+}
+
+#[test]
+fn only_owner_can_register_validator() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let setup = setup(&mut test_runner);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(setup.validator, "register", args![])
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
@@ -18,6 +18,8 @@ pub struct Blueprint {
     pub blueprint_name: String,
     pub functions: Vec<Function>,
     pub methods: Vec<Method>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub events: Vec<Event>,
 }
 
 /// Represents a function.
@@ -39,6 +41,27 @@ pub struct Method {
     pub output: Type,
 }
 
+/// Represents an event.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct Event {
+    pub name: String,
+    pub schema: Type,
+}
+
+impl Event {
+    /// Creates an event named after the struct or enum described by the schema.
+    pub fn from_schema(schema: Type) -> Option<Self> {
+        match &schema {
+            Type::Struct { name, .. } | Type::Enum { name, .. } => Some(Self {
+                name: name.clone(),
+                schema,
+            }),
+            _ => None,
+        }
+    }
+}
+
 /// Whether a method is going to change the component state.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -24,6 +24,8 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     let bp_impl = &bp.implementation;
     let bp_ident = &bp_strut.ident;
     let bp_items = &bp_impl.items;
+    let bp_items_without_markers: Vec<ImplItem> =
+        bp_items.iter().map(strip_migrate_attribute).collect();
     let bp_name = bp_ident.to_string();
     trace!("Blueprint name: {}", bp_name);
 
@@ -50,7 +52,7 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
             pub struct #bp_ident #bp_fields #bp_semi_token
 
             impl #bp_ident {
-                #(#bp_items)*
+                #(#bp_items_without_markers)*
             }
 
             impl ::scrypto::component::ComponentState for #bp_ident {
@@ -98,21 +100,68 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     };
     trace!("Generated dispatcher: \n{}", quote! { #output_dispatcher });
 
+    // Migrate functions get their own export, so that they can only be reached through a
+    // component migration and never by a method call.
+    let migration_ident = format_ident!("{}_migrate", bp_ident);
+    let (migration_guards, migration_bodies) = generate_migration_dispatcher(bp_ident, bp_items)?;
+    let output_migration = if migration_guards.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            #[no_mangle]
+            pub extern "C" fn #migration_ident() -> *mut u8 {
+                // Set up panic hook
+                ::scrypto::misc::set_up_panic_hook();
+
+                // Set up component and resource subsystems;
+                ::scrypto::component::init_component_system(::scrypto::component::ComponentSystem::new());
+                ::scrypto::resource::init_resource_system(::scrypto::resource::ResourceSystem::new());
+
+                // Retrieve call data
+                let calldata: ::scrypto::engine::api::GetCallDataOutput = ::scrypto::engine::call_engine(
+                    ::scrypto::engine::api::GET_CALL_DATA,
+                    ::scrypto::engine::api::GetCallDataInput {},
+                );
+
+                // Dispatch the migration
+                let rtn;
+                match calldata.function.as_str() {
+                    #( #migration_guards => #migration_bodies )*
+                    _ => {
+                        panic!("Migrate function not found")
+                    }
+                }
+
+                // Return
+                ::scrypto::buffer::scrypto_wrap(rtn)
+            }
+        }
+    };
+    trace!(
+        "Generated migration dispatcher: \n{}",
+        quote! { #output_migration }
+    );
+
     let abi_ident = format_ident!("{}_abi", bp_ident);
     let (abi_functions, abi_methods) = generate_abi(bp_ident, bp_items)?;
+    let event_types = parse_event_types(&bp_strut.attrs)?;
     let output_abi = quote! {
         #[no_mangle]
         pub extern "C" fn #abi_ident() -> *mut u8 {
             use ::sbor::{Describe, Type};
-            use ::scrypto::abi::{Function, Method};
+            use ::scrypto::abi::{Event, Function, Method};
             use ::scrypto::rust::borrow::ToOwned;
             use ::scrypto::rust::vec;
             use ::scrypto::rust::vec::Vec;
 
             let functions: Vec<Function> = vec![ #(#abi_functions),* ];
             let methods: Vec<Method> = vec![ #(#abi_methods),* ];
+            let events: Vec<Event> = vec![ #(
+                Event::from_schema(<#event_types>::describe())
+                    .expect("Event must be a struct or an enum")
+            ),* ];
             let schema: Type = blueprint::#bp_ident::describe();
-            let output = (schema, functions, methods);
+            let output = (schema, functions, methods, events);
 
             // serialize the output
             let output_bytes = ::scrypto::buffer::scrypto_encode_for_radix_engine(&output);
@@ -133,6 +182,8 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
 
         #output_dispatcher
 
+        #output_migration
+
         #output_abi
 
         #output_stubs
@@ -159,6 +210,11 @@ fn generate_dispatcher(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr
                 let fn_name = &m.sig.ident.to_string();
                 let fn_ident = &m.sig.ident;
 
+                // Migrate functions are dispatched by the migration export only
+                if is_migrate(m) {
+                    continue;
+                }
+
                 let mut args: Vec<Expr> = vec![];
                 let mut stmts: Vec<Stmt> = vec![];
                 let mut get_state: Option<Stmt> = None;
@@ -247,6 +303,110 @@ fn generate_dispatcher(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr
     Ok((arm_guards, arm_bodies))
 }
 
+// Parses the `#[migrate]` functions in an `Impl` and returns the arm guards and bodies
+// used for call matching in the migration export.
+fn generate_migration_dispatcher(
+    bp_ident: &Ident,
+    items: &[ImplItem],
+) -> Result<(Vec<Expr>, Vec<Expr>)> {
+    let mut arm_guards = Vec::<Expr>::new();
+    let mut arm_bodies = Vec::<Expr>::new();
+
+    for item in items {
+        if let ImplItem::Method(ref m) = item {
+            if let Visibility::Public(_) = &m.vis {
+                if !is_migrate(m) {
+                    continue;
+                }
+                let fn_name = &m.sig.ident.to_string();
+                let stmts = generate_migrate_stmts(bp_ident, m)?;
+                arm_guards.push(parse_quote! { #fn_name });
+                arm_bodies.push(Expr::Block(ExprBlock {
+                    attrs: vec![],
+                    label: None,
+                    block: Block {
+                        brace_token: Brace {
+                            span: Span::call_site(),
+                        },
+                        stmts,
+                    },
+                }));
+            }
+        }
+    }
+
+    Ok((arm_guards, arm_bodies))
+}
+
+// Generates the statements of a `#[migrate]` function, which reads the component state
+// as left by the previous version of the blueprint and replaces it with the returned one.
+fn generate_migrate_stmts(bp_ident: &Ident, m: &ImplItemMethod) -> Result<Vec<Stmt>> {
+    let fn_ident = &m.sig.ident;
+    let old_state_type = match m.sig.inputs.iter().collect::<Vec<&FnArg>>().as_slice() {
+        [FnArg::Typed(t)] => &t.ty,
+        _ => {
+            return Err(Error::new(
+                m.sig.inputs.span(),
+                "A migrate function must take the previous state as its only input.",
+            ))
+        }
+    };
+    let returns_self = match &m.sig.output {
+        ReturnType::Type(_, t) => {
+            replace_self_with(t, &bp_ident.to_string()) == parse_quote! { #bp_ident }
+        }
+        ReturnType::Default => false,
+    };
+    if !returns_self {
+        return Err(Error::new(
+            m.sig.output.span(),
+            "A migrate function must return `Self`.",
+        ));
+    }
+
+    Ok(vec![
+        parse_quote! {
+            let arg0 = ::scrypto::buffer::scrypto_decode::<::scrypto::component::ComponentAddress>(&calldata.args[0])
+            .unwrap();
+        },
+        parse_quote! {
+            let old_state: #old_state_type = borrow_component!(arg0).get_state_as();
+        },
+        parse_quote! {
+            ::scrypto::borrow_component!(arg0).put_state(blueprint::#bp_ident::#fn_ident(old_state));
+        },
+        parse_quote! {
+            rtn = ::scrypto::buffer::scrypto_encode_for_radix_engine(&());
+        },
+    ])
+}
+
+fn is_migrate(m: &ImplItemMethod) -> bool {
+    m.attrs.iter().any(|attr| attr.path.is_ident("migrate"))
+}
+
+fn strip_migrate_attribute(item: &ImplItem) -> ImplItem {
+    let mut item = item.clone();
+    if let ImplItem::Method(ref mut m) = item {
+        m.attrs.retain(|attr| !attr.path.is_ident("migrate"));
+    }
+    item
+}
+
+// Parses the types listed in an `#[events(..)]` attribute on the blueprint struct.
+fn parse_event_types(attrs: &[Attribute]) -> Result<Vec<Type>> {
+    let mut event_types = Vec::new();
+    for attr in attrs {
+        if attr.path.is_ident("events") {
+            let types = attr.parse_args_with(
+                punctuated::Punctuated::<Type, Token![,]>::parse_terminated,
+            )?;
+            event_types.extend(types);
+        }
+    }
+    Ok(event_types)
+}
+
 // Parses function items of an `Impl` and returns ABI of functions.
 fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr>, Vec<Expr>)> {
     let mut functions = Vec::<Expr>::new();
@@ -258,6 +418,12 @@ fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr>, Vec<
             ImplItem::Method(ref m) => {
                 if let Visibility::Public(_) = &m.vis {
                     let name = m.sig.ident.to_string();
+
+                    // Migrate functions can't be called as methods
+                    if is_migrate(m) {
+                        continue;
+                    }
+
                     let mut mutability = None;
                     let mut inputs = vec![];
                     for input in &m.sig.inputs {
@@ -340,6 +506,11 @@ fn generate_stubs(bp_ident: &Ident, items: &[ImplItem]) -> Result<TokenStream> {
         match item {
             ImplItem::Method(ref m) => {
                 if let Visibility::Public(_) = &m.vis {
+                    // Migrate functions can only be called through a component migration
+                    if is_migrate(m) {
+                        continue;
+                    }
+
                     let ident = &m.sig.ident;
                     let name = ident.to_string();
                     let mut mutable = None;
@@ -475,6 +646,47 @@ mod tests {
         handle_blueprint(input).unwrap();
     }
 
+    #[test]
+    #[should_panic]
+    fn test_migrate_without_previous_state_should_fail() {
+        let input = TokenStream::from_str(
+            "struct Test {a: u32} impl Test { #[migrate] pub fn migrate() -> Self { Self { a: 0 } } }",
+        )
+        .unwrap();
+        handle_blueprint(input).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_is_only_dispatched_by_migration_export() {
+        let input = TokenStream::from_str(
+            "struct Test {a: u32} impl Test { #[migrate] pub fn migrate(old: u32) -> Self { Self { a: old } } }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(output.contains("fn Test_migrate"));
+        assert_eq!(output.matches("\"migrate\" =>").count(), 1);
+    }
+
+    #[test]
+    fn test_events() {
+        let input = TokenStream::from_str(
+            "#[events(Deposited, Withdrawn)] struct Test {a: u32} impl Test { pub fn x(&self) -> u32 { self.a } }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap();
+
+        let events = quote! {
+            let events: Vec<Event> = vec![
+                Event::from_schema(<Deposited>::describe())
+                    .expect("Event must be a struct or an enum"),
+                Event::from_schema(<Withdrawn>::describe())
+                    .expect("Event must be a struct or an enum")
+            ];
+        };
+        assert!(output.to_string().contains(&events.to_string()));
+    }
+
     #[test]
     fn test_blueprint() {
         let input = TokenStream::from_str(
@@ -538,7 +750,7 @@ mod tests {
                 #[no_mangle]
                 pub extern "C" fn Test_abi() -> *mut u8 {
                     use ::sbor::{Describe, Type};
-                    use ::scrypto::abi::{Function, Method};
+                    use ::scrypto::abi::{Event, Function, Method};
                     use ::scrypto::rust::borrow::ToOwned;
                     use ::scrypto::rust::vec;
                     use ::scrypto::rust::vec::Vec;
@@ -549,8 +761,9 @@ mod tests {
                         inputs: vec![],
                         output: <u32>::describe(),
                     }];
+                    let events: Vec<Event> = vec![];
                     let schema: Type = blueprint::Test::describe();
-                    let output = (schema, functions, methods);
+                    let output = (schema, functions, methods, events);
                     let output_bytes = ::scrypto::buffer::scrypto_encode_for_radix_engine(&output);
                     ::scrypto::buffer::scrypto_wrap(output_bytes)
                 }
@@ -323,6 +323,8 @@ fn get_native_type(ty: &des::Type) -> Result<(Type, Vec<Item>)> {
                 "Hash" => "::scrypto::crypto::Hash",
                 "EcdsaPublicKey" => "::scrypto::crypto::EcdsaPublicKey",
                 "EcdsaSignature" => "::scrypto::crypto::EcdsaSignature",
+                "Ed25519PublicKey" => "::scrypto::crypto::Ed25519PublicKey",
+                "Ed25519Signature" => "::scrypto::crypto::Ed25519Signature",
                 "Decimal" => "::scrypto::math::Decimal",
                 "Bucket" => "::scrypto::resource::Bucket",
                 "Proof" => "::scrypto::resource::Proof",
@@ -330,6 +332,7 @@ fn get_native_type(ty: &des::Type) -> Result<(Type, Vec<Item>)> {
                 "NonFungibleId" => "::scrypto::resource::NonFungibleId",
                 "NonFungibleAddress" => "::scrypto::resource::NonFungibleAddress",
                 "ResourceAddress" => "::scrypto::resource::ResourceAddress",
+                "Expression" => "::scrypto::core::Expression",
                 "ProofRule" => "::scrypto::resource::ProofRule",
                 "AuthRule" => "::scrypto::resource::AuthRule",
                 _ => {
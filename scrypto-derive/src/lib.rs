@@ -40,6 +40,20 @@ use proc_macro::TokenStream;
 ///     }
 /// }
 /// ```
+///
+/// A new version of a package can migrate existing components with a function marked
+/// `#[migrate]`, which takes the state left by the previous version and returns the new one.
+/// It can't be called as a method, only through a migration of a component which opted in
+/// with `migratable`.
+///
+/// ```ignore
+/// #[migrate]
+/// pub fn migrate(old: CounterV1) -> Self {
+///     Self {
+///         count: old.count as u64
+///     }
+/// }
+/// ```
 #[proc_macro]
 pub fn blueprint(input: TokenStream) -> TokenStream {
     blueprint::handle_blueprint(proc_macro2::TokenStream::from(input))
@@ -0,0 +1,100 @@
+use sbor::*;
+
+use crate::args;
+use crate::buffer::scrypto_decode;
+use crate::component::ComponentAddress;
+use crate::core::SNodeRef;
+use crate::engine::{api::*, call_engine};
+use crate::resource::{AccessRule, Bucket, Proof};
+use crate::rust::string::ToString;
+
+/// A native component which holds a badge, and lets a primary role use it.
+///
+/// A recovery role can replace the primary rule, once a delay in epochs has passed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, TypeId, Encode, Decode, Describe)]
+pub struct AccessController(pub ComponentAddress);
+
+impl AccessController {
+    /// Creates an access controller which holds the given badge.
+    ///
+    /// A recovery can be confirmed `confirmation_delay` epochs after it is initiated.
+    pub fn new(
+        badge: Bucket,
+        primary_rule: AccessRule,
+        recovery_rule: AccessRule,
+        confirmation_delay: u64,
+    ) -> Self {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::AccessControllerStatic,
+            function: "create".to_string(),
+            args: args![badge, primary_rule, recovery_rule, confirmation_delay],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        Self(scrypto_decode(&output.rtn).unwrap())
+    }
+
+    /// Returns the address of the access controller component.
+    pub fn address(&self) -> ComponentAddress {
+        self.0
+    }
+
+    /// Creates a proof of the badge.
+    ///
+    /// The primary rule must be satisfied.
+    pub fn create_proof(&self) -> Proof {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::AccessControllerRef(self.0),
+            function: "create_proof".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Proposes a new primary rule, replacing any pending proposal.
+    ///
+    /// The recovery rule must be satisfied.
+    pub fn initiate_recovery(&self, primary_rule: AccessRule) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::AccessControllerRef(self.0),
+            function: "initiate_recovery".to_string(),
+            args: args![primary_rule],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Replaces the primary rule by the pending proposal, once its delay has passed.
+    ///
+    /// The recovery rule must be satisfied.
+    pub fn confirm_recovery(&self) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::AccessControllerRef(self.0),
+            function: "confirm_recovery".to_string(),
+            args: args![],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Cancels the pending recovery.
+    ///
+    /// The primary rule must be satisfied.
+    pub fn cancel_recovery(&self) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::AccessControllerRef(self.0),
+            function: "cancel_recovery".to_string(),
+            args: args![],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Returns the proposed primary rule, and the epoch from which it can be confirmed.
+    pub fn pending_recovery(&self) -> Option<(AccessRule, u64)> {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::AccessControllerRef(self.0),
+            function: "get_pending_recovery".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+}
@@ -1,15 +1,18 @@
 use sbor::*;
 
+use crate::args;
 use crate::buffer::*;
 use crate::component::*;
 use crate::core::*;
 use crate::engine::{api::*, call_engine};
 use crate::misc::*;
-use crate::resource::AccessRules;
+use crate::resource::{AccessRule, AccessRules, Bucket};
 use crate::rust::borrow::ToOwned;
+use crate::rust::collections::HashMap;
 use crate::rust::fmt;
 use crate::rust::str::FromStr;
 use crate::rust::string::String;
+use crate::rust::string::ToString;
 use crate::rust::vec::Vec;
 use crate::types::*;
 
@@ -17,6 +20,10 @@ pub struct LocalComponent {
     blueprint_name: String,
     state: Vec<u8>,
     access_rules_list: Vec<AccessRules>,
+    royalty_config: RoyaltyConfig,
+    metadata: HashMap<String, String>,
+    metadata_update_rule: AccessRule,
+    migration_rule: Option<AccessRule>,
 }
 
 impl LocalComponent {
@@ -25,6 +32,10 @@ impl LocalComponent {
             blueprint_name,
             state,
             access_rules_list: Vec::new(),
+            royalty_config: RoyaltyConfig::new(),
+            metadata: HashMap::new(),
+            metadata_update_rule: AccessRule::DenyAll,
+            migration_rule: None,
         }
     }
 
@@ -33,11 +44,46 @@ impl LocalComponent {
         self
     }
 
+    /// Sets the royalties charged to callers of the component's methods.
+    pub fn set_royalty_config(mut self, royalty_config: RoyaltyConfig) -> Self {
+        self.royalty_config = royalty_config;
+        self
+    }
+
+    /// Adds a component metadata, such as `name`, `description`, `icon_url` or
+    /// `dapp_definition`.
+    pub fn metadata<K: AsRef<str>, V: AsRef<str>>(mut self, name: K, value: V) -> Self {
+        self.metadata
+            .insert(name.as_ref().to_owned(), value.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the rule which must be satisfied to update the component metadata.
+    ///
+    /// The metadata can't be updated by default.
+    pub fn updateable_metadata(mut self, update_rule: AccessRule) -> Self {
+        self.metadata_update_rule = update_rule;
+        self
+    }
+
+    /// Allows the component to be migrated to a newer version of its package, by anyone
+    /// satisfying the given rule.
+    ///
+    /// Components can't be migrated by default.
+    pub fn migratable(mut self, migration_rule: AccessRule) -> Self {
+        self.migration_rule = Some(migration_rule);
+        self
+    }
+
     pub fn globalize(self) -> ComponentAddress {
         let input = CreateComponentInput {
             blueprint_name: self.blueprint_name,
             state: self.state,
             access_rules_list: self.access_rules_list,
+            royalty_config: self.royalty_config,
+            metadata: self.metadata,
+            metadata_update_rule: self.metadata_update_rule,
+            migration_rule: self.migration_rule,
         };
         let output: CreateComponentOutput = call_engine(CREATE_COMPONENT, input);
         output.component_address
@@ -76,6 +122,17 @@ impl Component {
         scrypto_decode(&output.state).unwrap()
     }
 
+    /// Returns the state of this component, decoded as the given type.
+    ///
+    /// This is how a migrate function reads the state written by a previous version of
+    /// its blueprint.
+    pub fn get_state_as<T: Decode>(&self) -> T {
+        let input = GetComponentStateInput {};
+        let output: GetComponentStateOutput = call_engine(GET_COMPONENT_STATE, input);
+
+        scrypto_decode(&output.state).unwrap()
+    }
+
     /// Updates the state of this component.
     pub fn put_state<T: ComponentState>(&self, state: T) {
         let input = PutComponentStateInput {
@@ -84,6 +141,45 @@ impl Component {
         let _: PutComponentStateOutput = call_engine(PUT_COMPONENT_STATE, input);
     }
 
+    /// Takes the royalties collected by this component.
+    ///
+    /// Can only be called by the component itself, typically from an access-protected method.
+    pub fn claim_royalty(&self) -> Bucket {
+        let input = ClaimComponentRoyaltyInput {
+            component_address: self.0,
+        };
+        let output: ClaimComponentRoyaltyOutput = call_engine(CLAIM_COMPONENT_ROYALTY, input);
+
+        Bucket(output.bucket_id)
+    }
+
+    /// Returns the metadata associated with this component.
+    pub fn metadata(&self) -> HashMap<String, String> {
+        Runtime::component_metadata(self.0)
+    }
+
+    /// Updates the component metadata.
+    ///
+    /// The metadata update rule of the component must be satisfied.
+    pub fn update_metadata(&self, new_metadata: HashMap<String, String>) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentRef(self.0),
+            function: "update_metadata".to_string(),
+            args: args![new_metadata],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Replaces the rule which must be satisfied to update the component metadata.
+    pub fn set_metadata_update_rule(&self, update_rule: AccessRule) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentRef(self.0),
+            function: "set_metadata_update_rule".to_string(),
+            args: args![update_rule],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
     /// Returns the package ID of this component.
     pub fn package_address(&self) -> PackageAddress {
         let input = GetComponentInfoInput {
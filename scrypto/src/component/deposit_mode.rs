@@ -0,0 +1,29 @@
+use sbor::*;
+
+use crate::resource::ResourceAddress;
+use crate::rust::collections::HashSet;
+
+/// Which resources an account accepts deposits of.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode, Describe)]
+pub enum DepositMode {
+    /// Accepts deposits of any resource.
+    AcceptAll,
+    /// Rejects deposits of any resource.
+    DenyAll,
+    /// Accepts deposits of the listed resources only.
+    AllowList(HashSet<ResourceAddress>),
+    /// Accepts deposits of any resource except the listed ones.
+    DenyList(HashSet<ResourceAddress>),
+}
+
+impl DepositMode {
+    /// Returns whether deposits of the given resource are accepted.
+    pub fn accepts(&self, resource_address: &ResourceAddress) -> bool {
+        match self {
+            DepositMode::AcceptAll => true,
+            DepositMode::DenyAll => false,
+            DepositMode::AllowList(resources) => resources.contains(resource_address),
+            DepositMode::DenyList(resources) => !resources.contains(resource_address),
+        }
+    }
+}
@@ -54,6 +54,61 @@ impl<K: Encode + Decode, V: Encode + Decode> LazyMap<K, V> {
         };
         let _: PutLazyMapEntryOutput = call_engine(PUT_LAZY_MAP_ENTRY, input);
     }
+
+    /// Removes a key from this map, returning the value at the key if the key was previously in the map.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let input = RemoveLazyMapEntryInput {
+            lazy_map_id: self.id,
+            key: scrypto_encode(key),
+        };
+        let output: RemoveLazyMapEntryOutput = call_engine(REMOVE_LAZY_MAP_ENTRY, input);
+
+        output.value.map(|v| scrypto_decode(&v).unwrap())
+    }
+
+    /// Returns true if this map contains a value for the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        let input = GetLazyMapEntryInput {
+            lazy_map_id: self.id,
+            key: scrypto_encode(key),
+        };
+        let output: GetLazyMapEntryOutput = call_engine(GET_LAZY_MAP_ENTRY, input);
+
+        output.value.is_some()
+    }
+
+    /// Returns up to `limit` entries whose key comes after `start_after`, ordered by encoded key.
+    ///
+    /// To read the next page, pass the last key of the current page as `start_after`.
+    pub fn entries(&self, start_after: Option<&K>, limit: u32) -> Vec<(K, V)> {
+        let input = GetLazyMapEntriesInput {
+            lazy_map_id: self.id,
+            start_after: start_after.map(|k| scrypto_encode(k)),
+            limit,
+        };
+        let output: GetLazyMapEntriesOutput = call_engine(GET_LAZY_MAP_ENTRIES, input);
+
+        output
+            .entries
+            .into_iter()
+            .map(|(k, v)| (scrypto_decode(&k).unwrap(), scrypto_decode(&v).unwrap()))
+            .collect()
+    }
+
+    /// Returns the number of entries in this map.
+    pub fn len(&self) -> u32 {
+        let input = GetLazyMapLenInput {
+            lazy_map_id: self.id,
+        };
+        let output: GetLazyMapLenOutput = call_engine(GET_LAZY_MAP_LEN, input);
+
+        output.len
+    }
+
+    /// Returns true if this map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 //========
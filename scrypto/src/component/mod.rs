@@ -1,11 +1,21 @@
+mod access_controller;
 mod component;
+mod deposit_mode;
 mod lazy_map;
 mod package;
+mod pool;
+mod royalty_config;
 mod system;
+mod validator;
 
+pub use access_controller::AccessController;
 pub use component::{
     Component, ComponentAddress, ComponentState, LocalComponent, ParseComponentAddressError,
 };
+pub use deposit_mode::DepositMode;
 pub use lazy_map::{LazyMap, ParseLazyMapError};
 pub use package::{Package, PackageAddress, ParsePackageAddressError};
+pub use pool::Pool;
+pub use royalty_config::RoyaltyConfig;
 pub use system::{component_system, init_component_system, ComponentSystem};
+pub use validator::Validator;
@@ -0,0 +1,113 @@
+use sbor::*;
+
+use crate::args;
+use crate::buffer::scrypto_decode;
+use crate::component::ComponentAddress;
+use crate::core::SNodeRef;
+use crate::engine::{api::*, call_engine};
+use crate::math::Decimal;
+use crate::resource::{AccessRule, Bucket, ResourceAddress};
+use crate::rust::collections::HashMap;
+use crate::rust::string::ToString;
+use crate::rust::vec::Vec;
+
+/// A native pool of fungible resources, which mints pool units to contributors.
+///
+/// The pool unit math is implemented by the engine. A pool may hold any number of resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, TypeId, Encode, Decode, Describe)]
+pub struct Pool(pub ComponentAddress);
+
+impl Pool {
+    /// Creates a pool of the given resources.
+    ///
+    /// The manager rule guards the protected deposit and withdraw methods.
+    pub fn new(resource_addresses: Vec<ResourceAddress>, manager_rule: AccessRule) -> Self {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PoolStatic,
+            function: "create".to_string(),
+            args: args![resource_addresses, manager_rule],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        Self(scrypto_decode(&output.rtn).unwrap())
+    }
+
+    /// Returns the address of the pool component.
+    pub fn address(&self) -> ComponentAddress {
+        self.0
+    }
+
+    /// Contributes resources to the pool.
+    ///
+    /// Returns the minted pool units, and the change for every contributed resource.
+    pub fn contribute(&self, buckets: Vec<Bucket>) -> (Bucket, Vec<Bucket>) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PoolRef(self.0),
+            function: "contribute".to_string(),
+            args: args![buckets],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Redeems pool units for their share of every resource in the pool.
+    pub fn redeem(&self, pool_units: Bucket) -> Vec<Bucket> {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PoolRef(self.0),
+            function: "redeem".to_string(),
+            args: args![pool_units],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Deposits resources without minting pool units, e.g. to distribute fees.
+    ///
+    /// The manager rule of the pool must be satisfied.
+    pub fn protected_deposit(&self, bucket: Bucket) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PoolRef(self.0),
+            function: "protected_deposit".to_string(),
+            args: args![bucket],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Withdraws resources without burning pool units.
+    ///
+    /// The manager rule of the pool must be satisfied.
+    pub fn protected_withdraw<A: Into<Decimal>>(
+        &self,
+        resource_address: ResourceAddress,
+        amount: A,
+    ) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PoolRef(self.0),
+            function: "protected_withdraw".to_string(),
+            args: args![resource_address, amount.into()],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Returns the amount of every resource in the pool.
+    pub fn vault_amounts(&self) -> HashMap<ResourceAddress, Decimal> {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PoolRef(self.0),
+            function: "get_vault_amounts".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Returns the address of the pool unit resource.
+    pub fn pool_unit_resource_address(&self) -> ResourceAddress {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PoolRef(self.0),
+            function: "get_pool_unit_resource_address".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+}
@@ -0,0 +1,48 @@
+use crate::math::Decimal;
+use crate::rust::collections::hash_map::Iter;
+use crate::rust::collections::HashMap;
+use crate::rust::string::String;
+use crate::rust::string::ToString;
+use sbor::*;
+
+/// Royalties charged to callers of a blueprint or component, in XRD
+#[derive(Debug, Clone, PartialEq, Describe, TypeId, Encode, Decode)]
+pub struct RoyaltyConfig {
+    method_royalties: HashMap<String, Decimal>,
+    default_royalty: Decimal,
+}
+
+impl RoyaltyConfig {
+    pub fn new() -> Self {
+        Self {
+            method_royalties: HashMap::new(),
+            default_royalty: Decimal::zero(),
+        }
+    }
+
+    pub fn get(&self, method_name: &str) -> Decimal {
+        self.method_royalties
+            .get(method_name)
+            .cloned()
+            .unwrap_or(self.default_royalty)
+    }
+
+    pub fn get_default(&self) -> Decimal {
+        self.default_royalty
+    }
+
+    pub fn method(mut self, method_name: &str, amount: Decimal) -> Self {
+        self.method_royalties
+            .insert(method_name.to_string(), amount);
+        self
+    }
+
+    pub fn default(mut self, amount: Decimal) -> Self {
+        self.default_royalty = amount;
+        self
+    }
+
+    pub fn iter(&self) -> Iter<'_, String, Decimal> {
+        self.method_royalties.iter()
+    }
+}
@@ -3,9 +3,11 @@ use crate::buffer::*;
 use crate::component::*;
 use crate::core::SNodeRef;
 use crate::engine::{api::*, call_engine};
-use crate::prelude::AccessRules;
+use crate::prelude::{AccessRule, AccessRules};
+use crate::resource::Bucket;
 use crate::rust::borrow::ToOwned;
 use crate::rust::collections::*;
+use crate::rust::string::String;
 use crate::rust::string::ToString;
 use crate::rust::vec::Vec;
 
@@ -56,6 +58,99 @@ impl ComponentSystem {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Publishes a package which can be upgraded by whoever satisfies the given rule.
+    pub fn publish_package_with_upgrade_rule(
+        &mut self,
+        code: &[u8],
+        upgrade_rule: AccessRule,
+    ) -> PackageAddress {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PackageStatic,
+            function: "publish_with_upgrade_rule".to_string(),
+            args: args![code.to_vec(), upgrade_rule],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Publishes a new version of a package, and returns the address of the new version.
+    pub fn upgrade_package(&mut self, package_address: PackageAddress, code: &[u8]) -> PackageAddress {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PackageStatic,
+            function: "upgrade".to_string(),
+            args: args![package_address, code.to_vec()],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Sets the royalties charged to callers of a blueprint, as the package owner.
+    pub fn set_package_royalty_config(
+        &mut self,
+        package_address: PackageAddress,
+        blueprint_name: &str,
+        royalty_config: RoyaltyConfig,
+    ) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PackageStatic,
+            function: "set_royalty_config".to_string(),
+            args: args![package_address, blueprint_name.to_string(), royalty_config],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Takes the royalties collected by a package, as the package owner.
+    pub fn claim_package_royalty(&mut self, package_address: PackageAddress) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PackageStatic,
+            function: "claim_royalty".to_string(),
+            args: args![package_address],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Returns the metadata associated with a package.
+    pub fn get_package_metadata(&self, package_address: PackageAddress) -> HashMap<String, String> {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PackageStatic,
+            function: "get_metadata".to_string(),
+            args: args![package_address],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Updates the metadata of a package, as the package owner.
+    pub fn set_package_metadata(
+        &mut self,
+        package_address: PackageAddress,
+        metadata: HashMap<String, String>,
+    ) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::PackageStatic,
+            function: "set_metadata".to_string(),
+            args: args![package_address, metadata],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Migrates a component to a newer version of its package, by calling the migrate function
+    /// of that version.
+    pub fn migrate_component(
+        &mut self,
+        component_address: ComponentAddress,
+        package_address: PackageAddress,
+        function: &str,
+    ) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::MigrateComponent(component_address, package_address),
+            function: function.to_string(),
+            args: args![],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
     /// Instantiates a component.
     pub fn instantiate_component<T: ComponentState>(
         &mut self,
@@ -67,6 +162,10 @@ impl ComponentSystem {
             blueprint_name: blueprint_name.to_owned(),
             state: scrypto_encode(&state),
             access_rules_list: authorization,
+            royalty_config: RoyaltyConfig::new(),
+            metadata: HashMap::new(),
+            metadata_update_rule: AccessRule::DenyAll,
+            migration_rule: None,
         };
         let output: CreateComponentOutput = call_engine(CREATE_COMPONENT, input);
 
@@ -0,0 +1,128 @@
+use sbor::*;
+
+use crate::args;
+use crate::buffer::scrypto_decode;
+use crate::component::ComponentAddress;
+use crate::core::SNodeRef;
+use crate::engine::{api::*, call_engine};
+use crate::math::Decimal;
+use crate::resource::{AccessRule, Bucket, ResourceAddress};
+use crate::rust::string::ToString;
+
+/// A native validator, which XRD holders stake to in return for liquid stake units.
+///
+/// Registered validators receive a share of the XRD emitted at the end of every epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, TypeId, Encode, Decode, Describe)]
+pub struct Validator(pub ComponentAddress);
+
+impl Validator {
+    /// Creates a validator.
+    ///
+    /// The owner rule guards the registration of the validator.
+    pub fn new(owner_rule: AccessRule) -> Self {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ValidatorStatic,
+            function: "create".to_string(),
+            args: args![owner_rule],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        Self(scrypto_decode(&output.rtn).unwrap())
+    }
+
+    /// Returns the address of the validator component.
+    pub fn address(&self) -> ComponentAddress {
+        self.0
+    }
+
+    /// Adds the validator to the set which receives emissions.
+    ///
+    /// The owner rule of the validator must be satisfied.
+    pub fn register(&self) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ValidatorRef(self.0),
+            function: "register".to_string(),
+            args: args![],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Removes the validator from the set which receives emissions.
+    ///
+    /// The owner rule of the validator must be satisfied.
+    pub fn unregister(&self) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ValidatorRef(self.0),
+            function: "unregister".to_string(),
+            args: args![],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Stakes XRD, in return for stake units.
+    pub fn stake(&self, xrd: Bucket) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ValidatorRef(self.0),
+            function: "stake".to_string(),
+            args: args![xrd],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Burns stake units, in return for an unstake claim on their share of the stake.
+    pub fn unstake(&self, stake_units: Bucket) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ValidatorRef(self.0),
+            function: "unstake".to_string(),
+            args: args![stake_units],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Burns unstake claims, in return for their XRD.
+    ///
+    /// Every claim must have reached the epoch from which it is unlocked.
+    pub fn claim_xrd(&self, unstake_claims: Bucket) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ValidatorRef(self.0),
+            function: "claim_xrd".to_string(),
+            args: args![unstake_claims],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Returns the amount of XRD staked to the validator, emissions included.
+    pub fn stake_amount(&self) -> Decimal {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ValidatorRef(self.0),
+            function: "get_stake_amount".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Returns the address of the stake unit resource.
+    pub fn stake_unit_resource_address(&self) -> ResourceAddress {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ValidatorRef(self.0),
+            function: "get_stake_unit_resource_address".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Returns the address of the unstake claim resource.
+    pub fn unstake_claim_resource_address(&self) -> ResourceAddress {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ValidatorRef(self.0),
+            function: "get_unstake_claim_resource_address".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+}
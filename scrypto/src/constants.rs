@@ -25,3 +25,33 @@ pub const RADIX_TOKEN: ResourceAddress = ResourceAddress([
 pub const ECDSA_TOKEN: ResourceAddress = ResourceAddress([
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5,
 ]);
+
+/// The Ed25519 virtual resource address.
+pub const ED25519_TOKEN: ResourceAddress = ResourceAddress([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6,
+]);
+
+/// The package of native pool components, which has no code.
+pub const POOL_PACKAGE: PackageAddress = PackageAddress([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7,
+]);
+
+/// The package of native access controller components, which has no code.
+pub const ACCESS_CONTROLLER_PACKAGE: PackageAddress = PackageAddress([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 8,
+]);
+
+/// The package of native validator components, which has no code.
+pub const VALIDATOR_PACKAGE: PackageAddress = PackageAddress([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9,
+]);
+
+/// The system virtual resource address, a proof of which is only given to epoch changes.
+pub const SYSTEM_TOKEN: ResourceAddress = ResourceAddress([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 10,
+]);
+
+/// The epoch manager component, which keeps the set of registered validators.
+pub const EPOCH_MANAGER: ComponentAddress = ComponentAddress([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 11,
+]);
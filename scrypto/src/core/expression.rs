@@ -0,0 +1,90 @@
+use sbor::*;
+
+use crate::rust::fmt;
+use crate::rust::str::FromStr;
+use crate::rust::string::String;
+use crate::rust::vec::Vec;
+use crate::types::*;
+
+/// Represents a manifest expression, which is resolved into a value when the transaction runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Expression {
+    /// All the resources on the worktop, as a `Vec<Bucket>`.
+    EntireWorktop,
+
+    /// All the proofs in the auth zone, as a `Vec<Proof>`.
+    EntireAuthZone,
+}
+
+impl Expression {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EntireWorktop => "ENTIRE_WORKTOP",
+            Self::EntireAuthZone => "ENTIRE_AUTH_ZONE",
+        }
+    }
+}
+
+//========
+// error
+//========
+
+/// Represents an error when parsing expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseExpressionError {
+    UnknownExpression(String),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseExpressionError {}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Display for ParseExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+//========
+// binary
+//========
+
+impl TryFrom<&[u8]> for Expression {
+    type Error = ParseExpressionError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        match slice {
+            b"ENTIRE_WORKTOP" => Ok(Self::EntireWorktop),
+            b"ENTIRE_AUTH_ZONE" => Ok(Self::EntireAuthZone),
+            _ => Err(ParseExpressionError::UnknownExpression(
+                String::from_utf8_lossy(slice).into_owned(),
+            )),
+        }
+    }
+}
+
+impl Expression {
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.name().as_bytes().to_vec()
+    }
+}
+
+scrypto_type!(Expression, ScryptoType::Expression, Vec::new());
+
+//======
+// text
+//======
+
+impl FromStr for Expression {
+    type Err = ParseExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.as_bytes())
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.name())
+    }
+}
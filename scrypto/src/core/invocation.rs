@@ -1,3 +1,4 @@
+use crate::component::{ComponentAddress, PackageAddress};
 use crate::core::ScryptoActor;
 use crate::engine::types::{BucketId, ProofId, VaultId};
 use crate::resource::ResourceAddress;
@@ -17,4 +18,24 @@ pub enum SNodeRef {
     ProofRef(ProofId),
     Proof(ProofId),
     VaultRef(VaultId),
+    /// A vault of a global component, for the recall and freeze methods of its resource.
+    ComponentVaultRef(ComponentAddress, VaultId),
+    /// Runs a migrate function of a newer package version against a component.
+    MigrateComponent(ComponentAddress, PackageAddress),
+    /// Native functions of a component, such as metadata updates.
+    ComponentRef(ComponentAddress),
+    /// Functions which create native pools.
+    PoolStatic,
+    /// Methods of a native pool.
+    PoolRef(ComponentAddress),
+    /// Functions which create native access controllers.
+    AccessControllerStatic,
+    /// Methods of a native access controller.
+    AccessControllerRef(ComponentAddress),
+    /// Functions which create native validators.
+    ValidatorStatic,
+    /// Methods of a native validator.
+    ValidatorRef(ComponentAddress),
+    /// Methods of the epoch manager.
+    EpochManagerRef,
 }
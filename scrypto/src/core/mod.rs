@@ -1,10 +1,12 @@
 mod actor;
+mod expression;
 mod invocation;
 mod level;
 mod logger;
 mod runtime;
 
 pub use actor::{ScryptoActor, ScryptoActorInfo};
+pub use expression::{Expression, ParseExpressionError};
 pub use invocation::SNodeRef;
 pub use level::Level;
 pub use logger::Logger;
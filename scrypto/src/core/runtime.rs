@@ -1,8 +1,16 @@
+use sbor::{Describe, Encode};
+
+use crate::abi::Event;
+use crate::args;
+use crate::buffer::{scrypto_decode, scrypto_encode};
 use crate::component::*;
 use crate::core::*;
 use crate::crypto::*;
 use crate::engine::{api::*, call_engine};
 use crate::rust::borrow::ToOwned;
+use crate::rust::collections::HashMap;
+use crate::rust::string::String;
+use crate::rust::string::ToString;
 use crate::rust::vec::Vec;
 
 /// The transaction runtime.
@@ -69,6 +77,40 @@ impl Runtime {
         output.rtn
     }
 
+    /// Invokes a method on a component, rolling back all its state updates if it fails.
+    ///
+    /// On failure, the buckets passed as arguments are returned to the caller and the error
+    /// message is returned. Proofs can't be passed, and running out of cost units still aborts
+    /// the transaction.
+    pub fn try_call_method<S: AsRef<str>>(
+        component_address: ComponentAddress,
+        method: S,
+        args: Vec<Vec<u8>>,
+    ) -> Result<Vec<u8>, String> {
+        let input = TryInvokeSNodeInput {
+            snode_ref: SNodeRef::Scrypto(ScryptoActor::Component(component_address)),
+            function: method.as_ref().to_owned(),
+            args,
+        };
+        let output: TryInvokeSNodeOutput = call_engine(TRY_INVOKE_SNODE, input);
+
+        output.rtn
+    }
+
+    /// Returns the metadata of a component.
+    ///
+    /// The component isn't borrowed, so a component can read its own metadata.
+    pub fn component_metadata(component_address: ComponentAddress) -> HashMap<String, String> {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentRef(component_address),
+            function: "get_metadata".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Returns the transaction hash.
     pub fn transaction_hash() -> Hash {
         let input = GetTransactionHashInput {};
@@ -82,4 +124,28 @@ impl Runtime {
         let output: GetCurrentEpochOutput = call_engine(GET_CURRENT_EPOCH, input);
         output.current_epoch
     }
+
+    /// Returns the ledger time, in seconds since the Unix epoch.
+    ///
+    /// The ledger clock is rounded down to the minute.
+    pub fn current_time() -> u64 {
+        let input = GetCurrentTimeInput {};
+        let output: GetCurrentTimeOutput = call_engine(GET_CURRENT_TIME, input);
+        output.current_time
+    }
+
+    /// Emits an event, which is named after its type.
+    ///
+    /// The event type must be a struct or an enum, and should be listed in the
+    /// `#[events(..)]` attribute of the blueprint so its schema is exported in the ABI.
+    pub fn emit_event<T: Encode + Describe>(event: T) {
+        let name = Event::from_schema(T::describe())
+            .expect("Event must be a struct or an enum")
+            .name;
+        let input = EmitEventInput {
+            name,
+            data: scrypto_encode(&event),
+        };
+        let _: EmitEventOutput = call_engine(EMIT_EVENT, input);
+    }
 }
@@ -0,0 +1,224 @@
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer, Verifier};
+use sbor::*;
+
+use crate::rust::borrow::ToOwned;
+use crate::rust::fmt;
+use crate::rust::str::FromStr;
+use crate::rust::string::String;
+use crate::rust::vec::Vec;
+use crate::types::{scrypto_type, ScryptoType};
+
+/// Represents an ED25519 public key.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519PublicKey(PublicKey);
+
+/// Represents an ED25519 signature.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519Signature(Signature);
+
+/// Ed25519 signature verifier.
+pub struct Ed25519Verifier;
+
+/// Represents an ED25519 private key.
+///
+/// **Warning: ** This may be removed as whether signing capability should be provided by
+/// Scrypto crypto library is controversial.
+pub struct Ed25519PrivateKey(SecretKey);
+
+impl Ed25519PrivateKey {
+    pub const LENGTH: usize = 32;
+
+    pub fn public_key(&self) -> Ed25519PublicKey {
+        Ed25519PublicKey(PublicKey::from(&self.0))
+    }
+
+    pub fn sign(&self, msg: &[u8]) -> Ed25519Signature {
+        // The secret key is not `Copy`, so the key pair is rebuilt from bytes.
+        let keypair = Keypair {
+            secret: SecretKey::from_bytes(self.0.as_bytes()).unwrap(),
+            public: PublicKey::from(&self.0),
+        };
+        Ed25519Signature(keypair.sign(msg))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.as_bytes().to_vec()
+    }
+
+    pub fn from_bytes(slice: &[u8]) -> Result<Self, ()> {
+        if slice.len() != Ed25519PrivateKey::LENGTH {
+            return Err(());
+        }
+        Ok(Self(SecretKey::from_bytes(slice).map_err(|_| ())?))
+    }
+}
+
+impl Ed25519PublicKey {
+    pub const LENGTH: usize = 32;
+}
+
+impl Ed25519Signature {
+    pub const LENGTH: usize = 64;
+}
+
+impl Ed25519Verifier {
+    pub fn verify(msg: &[u8], pk: &Ed25519PublicKey, sig: &Ed25519Signature) -> bool {
+        pk.0.verify(msg, &sig.0).is_ok()
+    }
+}
+
+//======
+// error
+//======
+
+/// Represents an error when parsing ED25519 public key from hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEd25519PublicKeyError {
+    InvalidHex(String),
+    InvalidLength(usize),
+    InvalidKey,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseEd25519PublicKeyError {}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Display for ParseEd25519PublicKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Represents an error when parsing ED25519 signature from hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEd25519SignatureError {
+    InvalidHex(String),
+    InvalidLength(usize),
+    InvalidSignature,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseEd25519SignatureError {}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Display for ParseEd25519SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+//======
+// binary
+//======
+
+impl TryFrom<&[u8]> for Ed25519PublicKey {
+    type Error = ParseEd25519PublicKeyError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        if slice.len() != Ed25519PublicKey::LENGTH {
+            return Err(ParseEd25519PublicKeyError::InvalidLength(slice.len()));
+        }
+
+        let pk = PublicKey::from_bytes(slice).map_err(|_| ParseEd25519PublicKeyError::InvalidKey)?;
+        Ok(Ed25519PublicKey(pk))
+    }
+}
+
+impl Ed25519PublicKey {
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.as_bytes().to_vec()
+    }
+}
+
+scrypto_type!(Ed25519PublicKey, ScryptoType::Ed25519PublicKey, Vec::new());
+
+impl TryFrom<&[u8]> for Ed25519Signature {
+    type Error = ParseEd25519SignatureError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        if slice.len() != Ed25519Signature::LENGTH {
+            return Err(ParseEd25519SignatureError::InvalidLength(slice.len()));
+        }
+
+        let signature =
+            Signature::try_from(slice).map_err(|_| ParseEd25519SignatureError::InvalidSignature)?;
+        Ok(Ed25519Signature(signature))
+    }
+}
+
+impl Ed25519Signature {
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_bytes().to_vec()
+    }
+}
+
+scrypto_type!(Ed25519Signature, ScryptoType::Ed25519Signature, Vec::new());
+
+//======
+// text
+//======
+
+impl FromStr for Ed25519PublicKey {
+    type Err = ParseEd25519PublicKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes =
+            hex::decode(s).map_err(|_| ParseEd25519PublicKeyError::InvalidHex(s.to_owned()))?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl fmt::Display for Ed25519PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", hex::encode(self.to_vec()))
+    }
+}
+
+impl fmt::Debug for Ed25519PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
+
+impl FromStr for Ed25519Signature {
+    type Err = ParseEd25519SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes =
+            hex::decode(s).map_err(|_| ParseEd25519SignatureError::InvalidHex(s.to_owned()))?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl fmt::Display for Ed25519Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", hex::encode(self.to_vec()))
+    }
+}
+
+impl fmt::Debug for Ed25519Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        // RFC 8032, test 1
+        let test_sk = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+        let test_pk = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+        let test_signature = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+        let sk = Ed25519PrivateKey::from_bytes(&hex::decode(test_sk).unwrap()).unwrap();
+        let pk = Ed25519PublicKey::from_str(test_pk).unwrap();
+        let sig = Ed25519Signature::from_str(test_signature).unwrap();
+
+        assert_eq!(sk.public_key(), pk);
+        assert_eq!(sk.sign(&[]), sig);
+        assert!(Ed25519Verifier::verify(&[], &pk, &sig));
+        assert!(!Ed25519Verifier::verify(&[1u8], &pk, &sig));
+    }
+}
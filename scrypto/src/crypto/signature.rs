@@ -0,0 +1,132 @@
+use sbor::*;
+
+use crate::crypto::*;
+use crate::rust::vec::Vec;
+
+/// A signature scheme supported for transaction signing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureScheme {
+    EcdsaP256,
+    Ed25519,
+}
+
+/// Represents a public key of any supported signature scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypeId, Encode, Decode, Describe)]
+pub enum PublicKey {
+    EcdsaP256(EcdsaPublicKey),
+    Ed25519(Ed25519PublicKey),
+}
+
+/// Represents a signature of any supported signature scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypeId, Encode, Decode, Describe)]
+pub enum Signature {
+    EcdsaP256(EcdsaSignature),
+    Ed25519(Ed25519Signature),
+}
+
+/// Something which can sign messages, typically a private key.
+pub trait Signer {
+    fn public_key(&self) -> PublicKey;
+
+    fn sign(&self, msg: &[u8]) -> Signature;
+}
+
+impl PublicKey {
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            PublicKey::EcdsaP256(_) => SignatureScheme::EcdsaP256,
+            PublicKey::Ed25519(_) => SignatureScheme::Ed25519,
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        match self {
+            PublicKey::EcdsaP256(pk) => pk.to_vec(),
+            PublicKey::Ed25519(pk) => pk.to_vec(),
+        }
+    }
+}
+
+impl From<EcdsaPublicKey> for PublicKey {
+    fn from(public_key: EcdsaPublicKey) -> Self {
+        PublicKey::EcdsaP256(public_key)
+    }
+}
+
+impl From<Ed25519PublicKey> for PublicKey {
+    fn from(public_key: Ed25519PublicKey) -> Self {
+        PublicKey::Ed25519(public_key)
+    }
+}
+
+impl From<EcdsaSignature> for Signature {
+    fn from(signature: EcdsaSignature) -> Self {
+        Signature::EcdsaP256(signature)
+    }
+}
+
+impl From<Ed25519Signature> for Signature {
+    fn from(signature: Ed25519Signature) -> Self {
+        Signature::Ed25519(signature)
+    }
+}
+
+impl Signer for EcdsaPrivateKey {
+    fn public_key(&self) -> PublicKey {
+        EcdsaPrivateKey::public_key(self).into()
+    }
+
+    fn sign(&self, msg: &[u8]) -> Signature {
+        EcdsaPrivateKey::sign(self, msg).into()
+    }
+}
+
+impl Signer for Ed25519PrivateKey {
+    fn public_key(&self) -> PublicKey {
+        Ed25519PrivateKey::public_key(self).into()
+    }
+
+    fn sign(&self, msg: &[u8]) -> Signature {
+        Ed25519PrivateKey::sign(self, msg).into()
+    }
+}
+
+/// Verifies a signature of any supported scheme.
+pub struct SignatureVerifier;
+
+impl SignatureVerifier {
+    /// Returns true if the signature is valid for the message and the public key.
+    ///
+    /// A public key and a signature of different schemes never verify.
+    pub fn verify(msg: &[u8], pk: &PublicKey, sig: &Signature) -> bool {
+        match (pk, sig) {
+            (PublicKey::EcdsaP256(pk), Signature::EcdsaP256(sig)) => {
+                EcdsaVerifier::verify(msg, pk, sig)
+            }
+            (PublicKey::Ed25519(pk), Signature::Ed25519(sig)) => {
+                Ed25519Verifier::verify(msg, pk, sig)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mismatched_schemes_should_not_verify() {
+        let ecdsa_sk = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let ed25519_sk = Ed25519PrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let msg = b"hello";
+
+        let ecdsa_sig = Signer::sign(&ecdsa_sk, msg);
+        let ed25519_sig = Signer::sign(&ed25519_sk, msg);
+
+        assert!(SignatureVerifier::verify(msg, &Signer::public_key(&ecdsa_sk), &ecdsa_sig));
+        assert!(SignatureVerifier::verify(msg, &Signer::public_key(&ed25519_sk), &ed25519_sig));
+        assert!(!SignatureVerifier::verify(msg, &Signer::public_key(&ecdsa_sk), &ed25519_sig));
+        assert!(!SignatureVerifier::verify(msg, &Signer::public_key(&ed25519_sk), &ecdsa_sig));
+    }
+}
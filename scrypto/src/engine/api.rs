@@ -1,8 +1,9 @@
 use crate::core::SNodeRef;
 use sbor::*;
-use scrypto::prelude::{AccessRule, AccessRules};
+use scrypto::prelude::{AccessRule, AccessRules, RoyaltyConfig};
 
 use crate::engine::types::*;
+use crate::rust::collections::HashMap;
 use crate::rust::string::String;
 use crate::rust::vec::Vec;
 
@@ -20,6 +21,8 @@ pub const GET_COMPONENT_INFO: u32 = 0x11;
 pub const GET_COMPONENT_STATE: u32 = 0x12;
 /// Update component state
 pub const PUT_COMPONENT_STATE: u32 = 0x13;
+/// Claim the royalties collected by a component
+pub const CLAIM_COMPONENT_ROYALTY: u32 = 0x14;
 
 /// Create a lazy map
 pub const CREATE_LAZY_MAP: u32 = 0x20;
@@ -27,11 +30,19 @@ pub const CREATE_LAZY_MAP: u32 = 0x20;
 pub const GET_LAZY_MAP_ENTRY: u32 = 0x21;
 /// Insert a key-value pair into a lazy map
 pub const PUT_LAZY_MAP_ENTRY: u32 = 0x22;
+/// Remove an entry from a lazy map
+pub const REMOVE_LAZY_MAP_ENTRY: u32 = 0x23;
+/// Retrieve a page of entries from a lazy map
+pub const GET_LAZY_MAP_ENTRIES: u32 = 0x24;
+/// Retrieve the number of entries in a lazy map
+pub const GET_LAZY_MAP_LEN: u32 = 0x25;
 
 /// Create an empty vault
 pub const CREATE_EMPTY_VAULT: u32 = 0x40;
 
 pub const INVOKE_SNODE: u32 = 0x70;
+/// Invoke an snode, rolling back its state updates if it fails
+pub const TRY_INVOKE_SNODE: u32 = 0x71;
 
 /// Log a message
 pub const EMIT_LOG: u32 = 0xf0;
@@ -48,6 +59,10 @@ pub const GET_ACTOR: u32 = 0xf5;
 
 /// Check that an access rule is satisfied
 pub const CHECK_ACCESS_RULE: u32 = 0xf6;
+/// Emit an event
+pub const EMIT_EVENT: u32 = 0xf7;
+/// Retrieve the ledger time
+pub const GET_CURRENT_TIME: u32 = 0xf8;
 
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct InvokeSNodeInput {
@@ -61,6 +76,19 @@ pub struct InvokeSNodeOutput {
     pub rtn: Vec<u8>,
 }
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct TryInvokeSNodeInput {
+    pub snode_ref: SNodeRef,
+    pub function: String,
+    pub args: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct TryInvokeSNodeOutput {
+    /// The return value, or the error message if the invocation failed
+    pub rtn: Result<Vec<u8>, String>,
+}
+
 //==========
 // component
 //==========
@@ -70,6 +98,10 @@ pub struct CreateComponentInput {
     pub blueprint_name: String,
     pub state: Vec<u8>,
     pub access_rules_list: Vec<AccessRules>,
+    pub royalty_config: RoyaltyConfig,
+    pub metadata: HashMap<String, String>,
+    pub metadata_update_rule: AccessRule,
+    pub migration_rule: Option<AccessRule>,
 }
 
 #[derive(Debug, TypeId, Encode, Decode)]
@@ -104,6 +136,16 @@ pub struct PutComponentStateInput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct PutComponentStateOutput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ClaimComponentRoyaltyInput {
+    pub component_address: ComponentAddress,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ClaimComponentRoyaltyOutput {
+    pub bucket_id: BucketId,
+}
+
 //==========
 // LazyMap
 //==========
@@ -137,6 +179,41 @@ pub struct PutLazyMapEntryInput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct PutLazyMapEntryOutput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct RemoveLazyMapEntryInput {
+    pub lazy_map_id: LazyMapId,
+    pub key: Vec<u8>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct RemoveLazyMapEntryOutput {
+    pub value: Option<Vec<u8>>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetLazyMapEntriesInput {
+    pub lazy_map_id: LazyMapId,
+    /// Only entries whose encoded key is strictly greater than this are returned.
+    pub start_after: Option<Vec<u8>>,
+    pub limit: u32,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetLazyMapEntriesOutput {
+    /// Entries ordered by encoded key.
+    pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetLazyMapLenInput {
+    pub lazy_map_id: LazyMapId,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetLazyMapLenOutput {
+    pub len: u32,
+}
+
 //==========
 // vault
 //==========
@@ -164,6 +241,15 @@ pub struct EmitLogInput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct EmitLogOutput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct EmitEventInput {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct EmitEventOutput {}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct GetCallDataInput {}
 
@@ -181,6 +267,14 @@ pub struct GetCurrentEpochOutput {
     pub current_epoch: u64,
 }
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetCurrentTimeInput {}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct GetCurrentTimeOutput {
+    pub current_time: u64,
+}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct GetTransactionHashInput {}
 
@@ -8,11 +8,18 @@ pub use crate::core::ScryptoActorInfo;
 pub use crate::crypto::EcdsaPrivateKey;
 pub use crate::crypto::EcdsaPublicKey;
 pub use crate::crypto::EcdsaSignature;
+pub use crate::crypto::Ed25519PrivateKey;
+pub use crate::crypto::Ed25519PublicKey;
+pub use crate::crypto::Ed25519Signature;
+pub use crate::crypto::PublicKey;
+pub use crate::crypto::Signature;
+pub use crate::crypto::Signer;
 pub use crate::crypto::Hash;
 pub use crate::math::Decimal;
 pub use crate::resource::MintParams;
 pub use crate::resource::NonFungibleAddress;
 pub use crate::resource::NonFungibleId;
+pub use crate::resource::NonFungibleIdType;
 pub use crate::resource::ResourceAddress;
 pub use crate::resource::ResourceType;
 
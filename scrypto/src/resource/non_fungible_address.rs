@@ -1,5 +1,7 @@
 use sbor::*;
 
+use crate::constants::*;
+use crate::crypto::*;
 use crate::misc::*;
 use crate::resource::*;
 use crate::rust::borrow::ToOwned;
@@ -24,6 +26,18 @@ impl NonFungibleAddress {
         }
     }
 
+    /// Returns the address of the virtual badge of a transaction signer.
+    pub fn from_public_key(public_key: &PublicKey) -> Self {
+        let resource_address = match public_key {
+            PublicKey::EcdsaP256(_) => ECDSA_TOKEN,
+            PublicKey::Ed25519(_) => ED25519_TOKEN,
+        };
+        Self::new(
+            resource_address,
+            NonFungibleId::from_bytes(public_key.to_vec()),
+        )
+    }
+
     /// Returns the resource address.
     pub fn resource_address(&self) -> ResourceAddress {
         self.resource_address
@@ -122,7 +136,7 @@ mod tests {
 
     #[test]
     fn test_non_fungible_address_codec() {
-        let expected = "030000000000000000000000000000000000000000000000000005046ff03b949241ce1dadd43519e6960e0a85b41a69a05c328103aa2bce1594ca163c4f753a55bf01dc53f6c0b0c7eee78b40c6ff7d25a96e2282b989cef71c144a";
+        let expected = "03000000000000000000000000000000000000000000000000000502046ff03b949241ce1dadd43519e6960e0a85b41a69a05c328103aa2bce1594ca163c4f753a55bf01dc53f6c0b0c7eee78b40c6ff7d25a96e2282b989cef71c144a";
         let private_key = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
         let public_key = private_key.public_key();
         let auth_address =
@@ -133,4 +147,20 @@ mod tests {
         assert_eq!(s1, expected);
         assert_eq!(s2, expected);
     }
+
+    #[test]
+    fn test_virtual_badge_round_trip() {
+        let public_key = EcdsaPrivateKey::from_bytes(&[1u8; 32])
+            .unwrap()
+            .public_key();
+        let auth_address = NonFungibleAddress::from_public_key(&PublicKey::EcdsaP256(public_key));
+        assert_eq!(
+            NonFungibleAddress::try_from(auth_address.to_vec().as_slice()),
+            Ok(auth_address.clone())
+        );
+        assert_eq!(
+            scrypto_decode::<NonFungibleAddress>(&scrypto_encode(&auth_address)),
+            Ok(auth_address)
+        );
+    }
 }
@@ -1,36 +1,82 @@
 use sbor::*;
 
+use crate::resource::*;
 use crate::rust::borrow::ToOwned;
 use crate::rust::fmt;
 use crate::rust::str::FromStr;
 use crate::rust::string::String;
 use crate::rust::string::ToString;
+use crate::rust::vec;
 use crate::rust::vec::Vec;
 use crate::types::*;
 
-/// Represents a key for a non-fungible resource
+/// The maximum length of a minted string or bytes non-fungible ID, enough for a public key.
+const MAX_LENGTH: usize = 128;
+
+/// Represents a key for a non-fungible resource.
+///
+/// The kind of the ID has to match the `NonFungibleIdType` of the resource.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct NonFungibleId(Vec<u8>);
+pub enum NonFungibleId {
+    U64(u64),
+    String(String),
+    Bytes(Vec<u8>),
+    UUID(u128),
+}
 
 impl NonFungibleId {
     /// Creates a non-fungible ID from some uuid.
     pub fn random() -> Self {
-        Self(crate::core::Runtime::generate_uuid().to_be_bytes().to_vec())
+        Self::UUID(crate::core::Runtime::generate_uuid())
     }
 
     /// Creates a non-fungible ID from an arbitrary byte array.
     pub fn from_bytes(v: Vec<u8>) -> Self {
-        Self(v)
+        Self::Bytes(v)
     }
 
     /// Creates a non-fungible ID from a `u32` number.
     pub fn from_u32(u: u32) -> Self {
-        Self(u.to_be_bytes().to_vec())
+        Self::U64(u.into())
     }
 
     /// Creates a non-fungible ID from a `u64` number.
     pub fn from_u64(u: u64) -> Self {
-        Self(u.to_be_bytes().to_vec())
+        Self::U64(u)
+    }
+
+    /// Returns the kind of this ID.
+    pub fn id_type(&self) -> NonFungibleIdType {
+        match self {
+            Self::U64(_) => NonFungibleIdType::U64,
+            Self::String(_) => NonFungibleIdType::String,
+            Self::Bytes(_) => NonFungibleIdType::Bytes,
+            Self::UUID(_) => NonFungibleIdType::UUID,
+        }
+    }
+
+    /// Checks that this ID can be minted.
+    ///
+    /// Strings must be made of alphanumeric characters and underscores, and both strings and
+    /// byte arrays must be between 1 and 128 bytes long.
+    ///
+    /// This is not checked when decoding an ID, as virtual badges use the public key of a
+    /// signer, which is never minted.
+    pub fn validate(&self) -> Result<(), ParseNonFungibleIdError> {
+        let len = match self {
+            Self::String(s) => {
+                if !s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    return Err(ParseNonFungibleIdError::InvalidString(s.clone()));
+                }
+                s.len()
+            }
+            Self::Bytes(b) => b.len(),
+            Self::U64(_) | Self::UUID(_) => return Ok(()),
+        };
+        if len == 0 || len > MAX_LENGTH {
+            return Err(ParseNonFungibleIdError::InvalidLength(len));
+        }
+        Ok(())
     }
 }
 
@@ -41,7 +87,13 @@ impl NonFungibleId {
 /// Represents an error when decoding non-fungible id.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseNonFungibleIdError {
+    InvalidIdType(u8),
+    InvalidLength(usize),
+    InvalidU64(String),
+    InvalidString(String),
     InvalidHex(String),
+    InvalidUuid(String),
+    InvalidFormat(String),
 }
 
 #[cfg(not(feature = "alloc"))]
@@ -62,13 +114,44 @@ impl TryFrom<&[u8]> for NonFungibleId {
     type Error = ParseNonFungibleIdError;
 
     fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
-        Ok(Self(slice.to_vec()))
+        let (id_type, data) = slice
+            .split_first()
+            .ok_or(ParseNonFungibleIdError::InvalidLength(0))?;
+        let id = match id_type {
+            0 => {
+                let bytes = data
+                    .try_into()
+                    .map_err(|_| ParseNonFungibleIdError::InvalidLength(data.len()))?;
+                Self::U64(u64::from_be_bytes(bytes))
+            }
+            1 => Self::String(
+                String::from_utf8(data.to_vec())
+                    .map_err(|_| ParseNonFungibleIdError::InvalidString(hex::encode(data)))?,
+            ),
+            2 => Self::Bytes(data.to_vec()),
+            3 => {
+                let bytes = data
+                    .try_into()
+                    .map_err(|_| ParseNonFungibleIdError::InvalidLength(data.len()))?;
+                Self::UUID(u128::from_be_bytes(bytes))
+            }
+            _ => return Err(ParseNonFungibleIdError::InvalidIdType(*id_type)),
+        };
+        Ok(id)
     }
 }
 
 impl NonFungibleId {
     pub fn to_vec(&self) -> Vec<u8> {
-        self.0.clone()
+        let (id_type, mut data) = match self {
+            Self::U64(u) => (0u8, u.to_be_bytes().to_vec()),
+            Self::String(s) => (1u8, s.as_bytes().to_vec()),
+            Self::Bytes(b) => (2u8, b.clone()),
+            Self::UUID(u) => (3u8, u.to_be_bytes().to_vec()),
+        };
+        let mut vec = vec![id_type];
+        vec.append(&mut data);
+        vec
     }
 }
 
@@ -78,19 +161,73 @@ scrypto_type!(NonFungibleId, ScryptoType::NonFungibleId, Vec::new());
 // text
 //======
 
+fn strip_delimiters(s: &str, open: char, close: char) -> Option<&str> {
+    s.strip_prefix(open).and_then(|s| s.strip_suffix(close))
+}
+
+fn parse_uuid(s: &str) -> Option<u128> {
+    let groups: Vec<&str> = s.split('-').collect();
+    let lengths: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+    if lengths != [8, 4, 4, 4, 12] {
+        return None;
+    }
+
+    let mut uuid = 0u128;
+    for group in groups {
+        if !group.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        uuid = (uuid << (4 * group.len())) | u128::from_str_radix(group, 16).ok()?;
+    }
+    Some(uuid)
+}
+
 impl FromStr for NonFungibleId {
     type Err = ParseNonFungibleIdError;
 
+    /// Parses the textual form of an ID: `#42#`, `<Ticket_1>`, `[c0ffee]` or
+    /// `{f81d4fae-7dec-11d0-a765-00a0c91e6bf6}`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes =
-            hex::decode(s).map_err(|_| ParseNonFungibleIdError::InvalidHex(s.to_owned()))?;
-        Self::try_from(bytes.as_slice())
+        let id = if let Some(u) = strip_delimiters(s, '#', '#') {
+            if u.is_empty() || !u.chars().all(|c| c.is_ascii_digit()) {
+                return Err(ParseNonFungibleIdError::InvalidU64(s.to_owned()));
+            }
+            Self::U64(
+                u.parse()
+                    .map_err(|_| ParseNonFungibleIdError::InvalidU64(s.to_owned()))?,
+            )
+        } else if let Some(string) = strip_delimiters(s, '<', '>') {
+            Self::String(string.to_owned())
+        } else if let Some(bytes) = strip_delimiters(s, '[', ']') {
+            Self::Bytes(
+                hex::decode(bytes)
+                    .map_err(|_| ParseNonFungibleIdError::InvalidHex(s.to_owned()))?,
+            )
+        } else if let Some(uuid) = strip_delimiters(s, '{', '}') {
+            Self::UUID(parse_uuid(uuid).ok_or(ParseNonFungibleIdError::InvalidUuid(s.to_owned()))?)
+        } else {
+            return Err(ParseNonFungibleIdError::InvalidFormat(s.to_owned()));
+        };
+        Ok(id)
     }
 }
 
 impl fmt::Display for NonFungibleId {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{}", hex::encode(&self.0))
+        match self {
+            Self::U64(u) => write!(f, "#{}#", u),
+            Self::String(s) => write!(f, "<{}>", s),
+            Self::Bytes(b) => write!(f, "[{}]", hex::encode(b)),
+            Self::UUID(u) => write!(
+                f,
+                "{{{:08x}-{:04x}-{:04x}-{:04x}-{:012x}}}",
+                u >> 96,
+                (u >> 80) & 0xffff,
+                (u >> 64) & 0xffff,
+                (u >> 48) & 0xffff,
+                u & 0xffff_ffff_ffff
+            ),
+        }
     }
 }
 
@@ -108,16 +245,77 @@ mod tests {
     #[test]
     fn test_non_fungible_id_string_rep() {
         assert_eq!(
-            NonFungibleId::from_str("3575").unwrap(),
+            NonFungibleId::from_str("#5#").unwrap(),
+            NonFungibleId::from_u64(5)
+        );
+        assert_eq!(
+            NonFungibleId::from_str("<Ticket_1>").unwrap(),
+            NonFungibleId::String("Ticket_1".to_owned())
+        );
+        assert_eq!(
+            NonFungibleId::from_str("[3575]").unwrap(),
             NonFungibleId::from_bytes(vec![53u8, 117u8])
         );
         assert_eq!(
-            NonFungibleId::from_str("00000005").unwrap(),
-            NonFungibleId::from_u32(5)
+            NonFungibleId::from_str("{f81d4fae-7dec-11d0-a765-00a0c91e6bf6}").unwrap(),
+            NonFungibleId::UUID(0xf81d4fae_7dec_11d0_a765_00a0c91e6bf6)
+        );
+        for s in [
+            "#5#",
+            "<Ticket_1>",
+            "[3575]",
+            "{f81d4fae-7dec-11d0-a765-00a0c91e6bf6}",
+        ] {
+            assert_eq!(NonFungibleId::from_str(s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_invalid_non_fungible_id_string_rep() {
+        assert!(matches!(
+            NonFungibleId::from_str("3575"),
+            Err(ParseNonFungibleIdError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            NonFungibleId::from_str("#-5#"),
+            Err(ParseNonFungibleIdError::InvalidU64(_))
+        ));
+        assert!(matches!(
+            NonFungibleId::from_str("{f81d4fae7dec11d0a76500a0c91e6bf6}"),
+            Err(ParseNonFungibleIdError::InvalidUuid(_))
+        ));
+    }
+
+    #[test]
+    fn test_non_fungible_id_validation() {
+        assert!(matches!(
+            NonFungibleId::from_str("<Ticket 1>").unwrap().validate(),
+            Err(ParseNonFungibleIdError::InvalidString(_))
+        ));
+        assert_eq!(
+            NonFungibleId::from_str("[]").unwrap().validate(),
+            Err(ParseNonFungibleIdError::InvalidLength(0))
+        );
+        assert_eq!(
+            NonFungibleId::from_bytes(vec![0u8; 129]).validate(),
+            Err(ParseNonFungibleIdError::InvalidLength(129))
         );
+        assert_eq!(NonFungibleId::from_bytes(vec![0u8; 65]).validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_non_fungible_id_binary_rep() {
+        for id in [
+            NonFungibleId::from_u64(5),
+            NonFungibleId::String("Ticket_1".to_owned()),
+            NonFungibleId::from_bytes(vec![53u8, 117u8]),
+            NonFungibleId::UUID(u128::MAX),
+        ] {
+            assert_eq!(NonFungibleId::try_from(id.to_vec().as_slice()), Ok(id));
+        }
         assert_eq!(
-            NonFungibleId::from_str("0000000000000005").unwrap(),
-            NonFungibleId::from_u64(5)
+            NonFungibleId::try_from([4u8, 0u8].as_slice()),
+            Err(ParseNonFungibleIdError::InvalidIdType(4))
         );
     }
 }
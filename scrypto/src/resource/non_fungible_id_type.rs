@@ -0,0 +1,17 @@
+use sbor::*;
+
+/// Represents the kind of the IDs of a non-fungible resource.
+#[derive(Debug, Clone, Copy, TypeId, Encode, Decode, Describe, Eq, PartialEq)]
+pub enum NonFungibleIdType {
+    /// IDs are `u64` numbers, like `#42#`.
+    U64,
+
+    /// IDs are strings of alphanumeric characters and underscores, like `<Ticket_1>`.
+    String,
+
+    /// IDs are byte arrays, like `[c0ffee]`.
+    Bytes,
+
+    /// IDs are UUIDs, like `{f81d4fae-7dec-11d0-a765-00a0c91e6bf6}`.
+    UUID,
+}
@@ -154,6 +154,20 @@ pub enum ProofRule {
     CountOf(SoftCount, SoftResourceOrNonFungibleList),
     AllOf(SoftResourceOrNonFungibleList),
     AnyOf(SoftResourceOrNonFungibleList),
+    /// Satisfied from the given epoch on.
+    FromEpoch(u64),
+    /// Satisfied until the given epoch, exclusive.
+    BeforeEpoch(u64),
+    /// Satisfied from the given ledger time on, in seconds since the Unix epoch.
+    ///
+    /// The ledger clock is only precise to the minute, so a time within a minute is reached at
+    /// the start of the next one.
+    FromTime(u64),
+    /// Satisfied until the given ledger time, exclusive, in seconds since the Unix epoch.
+    ///
+    /// The ledger clock is only precise to the minute, so a time within a minute already stops
+    /// being satisfied at the start of that minute.
+    BeforeTime(u64),
 }
 
 // FIXME: describe types with cycles
@@ -265,6 +279,22 @@ where
     ProofRule::AmountOf(amount.into(), resource.into())
 }
 
+pub fn from_epoch(epoch: u64) -> ProofRule {
+    ProofRule::FromEpoch(epoch)
+}
+
+pub fn before_epoch(epoch: u64) -> ProofRule {
+    ProofRule::BeforeEpoch(epoch)
+}
+
+pub fn from_time(timestamp: u64) -> ProofRule {
+    ProofRule::FromTime(timestamp)
+}
+
+pub fn before_time(timestamp: u64) -> ProofRule {
+    ProofRule::BeforeTime(timestamp)
+}
+
 // TODO: Move this logic into preprocessor. It probably needs to be implemented as a procedural macro.
 #[macro_export]
 macro_rules! access_and_or {
@@ -20,6 +20,7 @@ pub struct FungibleResourceBuilder {
 }
 
 pub struct NonFungibleResourceBuilder {
+    id_type: NonFungibleIdType,
     metadata: HashMap<String, String>,
     authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
 }
@@ -103,6 +104,16 @@ impl FungibleResourceBuilder {
         self
     }
 
+    pub fn recallable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Recall, (method_auth, mutability));
+        self
+    }
+
+    pub fn freezable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Freeze, (method_auth, mutability));
+        self
+    }
+
     /// Creates resource with the given initial supply.
     ///
     /// # Example
@@ -140,11 +151,20 @@ impl FungibleResourceBuilder {
 impl NonFungibleResourceBuilder {
     pub fn new() -> Self {
         Self {
+            id_type: NonFungibleIdType::U64,
             metadata: HashMap::new(),
             authorization: HashMap::new(),
         }
     }
 
+    /// Set the kind of the non-fungible IDs.
+    ///
+    /// IDs are `u64` numbers by default; minting an ID of any other kind will fail.
+    pub fn id_type(&mut self, id_type: NonFungibleIdType) -> &mut Self {
+        self.id_type = id_type;
+        self
+    }
+
     /// Adds a resource metadata.
     ///
     /// If a previous attribute with the same name has been set, it will be overwritten.
@@ -194,6 +214,16 @@ impl NonFungibleResourceBuilder {
         self
     }
 
+    pub fn recallable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Recall, (method_auth, mutability));
+        self
+    }
+
+    pub fn freezable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Freeze, (method_auth, mutability));
+        self
+    }
+
     pub fn updateable_non_fungible_data(
         &mut self,
         method_auth: AccessRule,
@@ -211,8 +241,8 @@ impl NonFungibleResourceBuilder {
     /// let bucket = ResourceBuilder::new_non_fungible()
     ///     .metadata("name", "TestNonFungible")
     ///     .initial_supply([
-    ///         (NftKey::from(1u128), "immutable_part", "mutable_part"),
-    ///         (NftKey::from(2u128), "another_immutable_part", "another_mutable_part"),
+    ///         (NonFungibleId::from_u64(1), "immutable_part", "mutable_part"),
+    ///         (NonFungibleId::from_u64(2), "another_immutable_part", "another_mutable_part"),
     ///     ]);
     /// ```
     pub fn initial_supply<T, V>(&self, entries: T) -> Bucket
@@ -237,7 +267,9 @@ impl NonFungibleResourceBuilder {
         }
 
         resource_system().new_resource(
-            ResourceType::NonFungible,
+            ResourceType::NonFungible {
+                id_type: self.id_type,
+            },
             self.metadata.clone(),
             authorization,
             mint_params,
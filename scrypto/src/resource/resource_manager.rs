@@ -2,8 +2,9 @@ use sbor::*;
 
 use crate::args;
 use crate::buffer::scrypto_decode;
+use crate::component::ComponentAddress;
 use crate::core::SNodeRef;
-use crate::engine::{api::*, call_engine};
+use crate::engine::{api::*, call_engine, types::VaultId};
 use crate::math::*;
 use crate::misc::*;
 use crate::resource::*;
@@ -24,6 +25,8 @@ pub enum ResourceMethod {
     Deposit,
     UpdateMetadata,
     UpdateNonFungibleData,
+    Recall,
+    Freeze,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TypeId, Encode, Decode, Describe)]
@@ -211,6 +214,82 @@ impl ResourceManager {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    pub fn set_recallable(&self, recall_auth: AccessRule) -> () {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Recall, "update", recall_auth],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    pub fn lock_recallable(&self) -> () {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Recall, "lock"],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    pub fn set_freezable(&self, freeze_auth: AccessRule) -> () {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Freeze, "update", freeze_auth],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    pub fn lock_freezable(&self) -> () {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Freeze, "lock"],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Recalls resources from a vault of a component, even if the vault is frozen.
+    pub fn recall<T: Into<Decimal>>(
+        &self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        amount: T,
+    ) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentVaultRef(component_address, vault_id),
+            function: "recall_vault".to_string(),
+            args: args![amount.into()],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Blocks withdrawals from and deposits into a vault of a component.
+    pub fn freeze_vault(&self, component_address: ComponentAddress, vault_id: VaultId) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentVaultRef(component_address, vault_id),
+            function: "freeze_vault".to_string(),
+            args: args![],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Lifts the freeze of a vault of a component.
+    pub fn unfreeze_vault(&self, component_address: ComponentAddress, vault_id: VaultId) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ComponentVaultRef(component_address, vault_id),
+            function: "unfreeze_vault".to_string(),
+            args: args![],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
     /// Returns the metadata associated with this resource.
     pub fn metadata(&self) -> HashMap<String, String> {
         let input = InvokeSNodeInput {
@@ -64,6 +64,21 @@ impl Vault {
         bucket
     }
 
+    /// Locks some amount of XRD in this vault as transaction fee.
+    ///
+    /// The locked amount is taken out of the vault; whatever is not consumed by the
+    /// transaction is refunded once it completes.
+    pub fn lock_fee<A: Into<Decimal>>(&mut self, amount: A) {
+        let amount: Decimal = amount.into();
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "lock_fee".to_string(),
+            args: args![amount],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Takes all resource stored in this vault.
     pub fn take_all(&mut self) -> Bucket {
         self.take(self.amount())
@@ -159,6 +174,17 @@ impl Vault {
         self.amount() == 0.into()
     }
 
+    /// Checks if this vault has been frozen by the resource issuer.
+    pub fn is_frozen(&self) -> bool {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "is_vault_frozen".to_string(),
+            args: vec![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Returns all the non-fungible ids contained.
     ///
     /// # Panics
@@ -54,6 +54,8 @@ pub enum ScryptoType {
     Hash,
     EcdsaPublicKey,
     EcdsaSignature,
+    Ed25519PublicKey,
+    Ed25519Signature,
 
     // math
     Decimal,
@@ -65,16 +67,21 @@ pub enum ScryptoType {
     NonFungibleId,
     NonFungibleAddress,
     ResourceAddress,
+
+    // core
+    Expression,
 }
 
 // Need to update `scrypto-derive/src/import.rs` after changing the table below
-const MAPPING: [(ScryptoType, u8, &str); 13] = [
+const MAPPING: [(ScryptoType, u8, &str); 16] = [
     (ScryptoType::PackageAddress, 0x80, "PackageAddress"),
     (ScryptoType::ComponentAddress, 0x81, "ComponentAddress"),
     (ScryptoType::LazyMap, 0x82, "LazyMap"),
     (ScryptoType::Hash, 0x90, "Hash"),
     (ScryptoType::EcdsaPublicKey, 0x91, "EcdsaPublicKey"),
     (ScryptoType::EcdsaSignature, 0x93, "EcdsaSignature"),
+    (ScryptoType::Ed25519PublicKey, 0x92, "Ed25519PublicKey"),
+    (ScryptoType::Ed25519Signature, 0x94, "Ed25519Signature"),
     (ScryptoType::Decimal, 0xa1, "Decimal"),
     (ScryptoType::Bucket, 0xb1, "Bucket"),
     (ScryptoType::Proof, 0xb2, "Proof"),
@@ -82,6 +89,7 @@ const MAPPING: [(ScryptoType, u8, &str); 13] = [
     (ScryptoType::NonFungibleId, 0xb4, "NonFungibleId"),
     (ScryptoType::NonFungibleAddress, 0xb5, "NonFungibleAddress"),
     (ScryptoType::ResourceAddress, 0xb6, "ResourceAddress"),
+    (ScryptoType::Expression, 0xc0, "Expression"),
 ];
 
 impl ScryptoType {
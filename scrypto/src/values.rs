@@ -4,6 +4,7 @@ use sbor::path::{MutableSborPath, SborPath};
 
 use crate::buffer::*;
 use crate::component::*;
+use crate::core::{Expression, ParseExpressionError};
 use crate::crypto::*;
 use crate::engine::types::*;
 use crate::math::*;
@@ -39,6 +40,7 @@ pub struct ScryptoValue {
     pub proof_ids: HashMap<ProofId, SborPath>,
     pub vault_ids: HashSet<VaultId>,
     pub lazy_map_ids: HashSet<LazyMapId>,
+    pub expressions: Vec<(Expression, SborPath)>,
 }
 
 impl ScryptoValue {
@@ -58,6 +60,7 @@ impl ScryptoValue {
             proof_ids: checker.proofs.drain().map(|(e, path)| (e.0, path)).collect(),
             vault_ids: checker.vaults.iter().map(|e| e.0).collect(),
             lazy_map_ids: checker.lazy_maps.iter().map(|e| e.id).collect(),
+            expressions: checker.expressions,
         })
     }
 
@@ -139,6 +142,7 @@ pub struct ScryptoCustomValueChecker {
     pub proofs: HashMap<Proof, SborPath>,
     pub vaults: HashSet<Vault>,
     pub lazy_maps: HashSet<LazyMap<(), ()>>,
+    pub expressions: Vec<(Expression, SborPath)>,
 }
 
 /// Represents an error when validating a Scrypto-specific value.
@@ -153,12 +157,15 @@ pub enum ScryptoCustomValueCheckError {
     InvalidHash(ParseHashError),
     InvalidEcdsaPublicKey(ParseEcdsaPublicKeyError),
     InvalidEcdsaSignature(ParseEcdsaSignatureError),
+    InvalidEd25519PublicKey(ParseEd25519PublicKeyError),
+    InvalidEd25519Signature(ParseEd25519SignatureError),
     InvalidBucket(ParseBucketError),
     InvalidProof(ParseProofError),
     InvalidLazyMap(ParseLazyMapError),
     InvalidVault(ParseVaultError),
     InvalidNonFungibleId(ParseNonFungibleIdError),
     InvalidNonFungibleAddress(ParseNonFungibleAddressError),
+    InvalidExpression(ParseExpressionError),
     DuplicateIds,
 }
 
@@ -169,6 +176,7 @@ impl ScryptoCustomValueChecker {
             proofs: HashMap::new(),
             vaults: HashSet::new(),
             lazy_maps: HashSet::new(),
+            expressions: Vec::new(),
         }
     }
 }
@@ -204,6 +212,14 @@ impl CustomValueVisitor for ScryptoCustomValueChecker {
                 EcdsaSignature::try_from(data)
                     .map_err(ScryptoCustomValueCheckError::InvalidEcdsaSignature)?;
             }
+            ScryptoType::Ed25519PublicKey => {
+                Ed25519PublicKey::try_from(data)
+                    .map_err(ScryptoCustomValueCheckError::InvalidEd25519PublicKey)?;
+            }
+            ScryptoType::Ed25519Signature => {
+                Ed25519Signature::try_from(data)
+                    .map_err(ScryptoCustomValueCheckError::InvalidEd25519Signature)?;
+            }
             ScryptoType::Decimal => {
                 Decimal::try_from(data).map_err(ScryptoCustomValueCheckError::InvalidDecimal)?;
             }
@@ -238,6 +254,11 @@ impl CustomValueVisitor for ScryptoCustomValueChecker {
                 ResourceAddress::try_from(data)
                     .map_err(ScryptoCustomValueCheckError::InvalidResourceAddress)?;
             }
+            ScryptoType::Expression => {
+                let expression = Expression::try_from(data)
+                    .map_err(ScryptoCustomValueCheckError::InvalidExpression)?;
+                self.expressions.push((expression, path.clone().into()));
+            }
         }
         Ok(())
     }
@@ -458,6 +479,18 @@ impl ScryptoValueFormatter {
                     EcdsaSignature::try_from(data).unwrap()
                 )
             }
+            ScryptoType::Ed25519PublicKey => {
+                format!(
+                    "Ed25519PublicKey(\"{}\")",
+                    Ed25519PublicKey::try_from(data).unwrap()
+                )
+            }
+            ScryptoType::Ed25519Signature => {
+                format!(
+                    "Ed25519Signature(\"{}\")",
+                    Ed25519Signature::try_from(data).unwrap()
+                )
+            }
             ScryptoType::Bucket => {
                 let bucket = Bucket::try_from(data).unwrap();
                 if let Some(name) = bucket_ids.get(&bucket.0) {
@@ -487,6 +520,10 @@ impl ScryptoValueFormatter {
                 "ResourceAddress(\"{}\")",
                 ResourceAddress::try_from(data).unwrap()
             ),
+            ScryptoType::Expression => format!(
+                "Expression(\"{}\")",
+                Expression::try_from(data).unwrap()
+            ),
         }
     }
 }
@@ -511,4 +548,12 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn should_collect_expressions() {
+        let value = ScryptoValue::from_value(&(1u32, Expression::EntireWorktop));
+        assert_eq!(value.expressions.len(), 1);
+        assert_eq!(value.expressions[0].0, Expression::EntireWorktop);
+        assert!(value.expressions[0].1 == SborPath::new(vec![1]));
+    }
 }
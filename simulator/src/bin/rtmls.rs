@@ -0,0 +1,5 @@
+use simulator::rtmls;
+
+pub fn main() -> Result<(), rtmls::Error> {
+    rtmls::run()
+}
@@ -3,7 +3,7 @@ use colored::*;
 use radix_engine::ledger::*;
 use radix_engine::model::*;
 use scrypto::engine::types::*;
-use scrypto::rust::collections::HashSet;
+use scrypto::rust::collections::{HashMap, HashSet};
 use scrypto::values::*;
 use std::collections::VecDeque;
 
@@ -40,6 +40,7 @@ pub fn dump_package<T: SubstateStore, O: std::io::Write>(
                 "Code size".green().bold(),
                 b.code().len()
             );
+            dump_metadata(b.metadata(), output);
             Ok(())
         }
         None => Err(DisplayError::PackageNotFound),
@@ -72,6 +73,8 @@ pub fn dump_component<T: SubstateStore + QueryableSubstateStore, O: std::io::Wri
                 c.blueprint_name()
             );
 
+            dump_metadata(c.metadata(), output);
+
             writeln!(output, "{}", "Authorization".green().bold());
             for (_, auth) in c.authorization().iter().identify_last() {
                 for (last, (k, v)) in auth.iter().identify_last() {
@@ -154,7 +157,7 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
             .unwrap();
         writeln!(
             output,
-            "{} {{ amount: {}, resource address: {}{}{} }}",
+            "{} {{ amount: {}, resource address: {}{}{}{} }}",
             list_item_prefix(last),
             amount,
             resource_address,
@@ -168,8 +171,12 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
                 .get("symbol")
                 .map(|symbol| format!(", symbol: \"{}\"", symbol))
                 .unwrap_or(String::new()),
+            if vault.is_frozen() { ", frozen" } else { "" },
         );
-        if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
+        if matches!(
+            resource_manager.resource_type(),
+            ResourceType::NonFungible { .. }
+        ) {
             let ids = vault.total_ids().unwrap();
             for (inner_last, id) in ids.iter().identify_last() {
                 let non_fungible: Option<NonFungible> = substate_store
@@ -215,21 +222,7 @@ pub fn dump_resource_manager<T: SubstateStore, O: std::io::Write>(
                 "Resource Type".green().bold(),
                 r.resource_type()
             );
-            writeln!(
-                output,
-                "{}: {}",
-                "Metadata".green().bold(),
-                r.metadata().len()
-            );
-            for (last, e) in r.metadata().iter().identify_last() {
-                writeln!(
-                    output,
-                    "{} {}: {}",
-                    list_item_prefix(last),
-                    e.0.green().bold(),
-                    e.1
-                );
-            }
+            dump_metadata(r.metadata(), output);
             writeln!(
                 output,
                 "{}: {}",
@@ -241,3 +234,16 @@ pub fn dump_resource_manager<T: SubstateStore, O: std::io::Write>(
         None => Err(DisplayError::ResourceManagerNotFound),
     }
 }
+
+fn dump_metadata<O: std::io::Write>(metadata: &HashMap<String, String>, output: &mut O) {
+    writeln!(output, "{}: {}", "Metadata".green().bold(), metadata.len());
+    for (last, e) in metadata.iter().identify_last() {
+        writeln!(
+            output,
+            "{} {}: {}",
+            list_item_prefix(last),
+            e.0.green().bold(),
+            e.1
+        );
+    }
+}
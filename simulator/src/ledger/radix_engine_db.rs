@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use radix_engine::ledger::*;
-use rocksdb::{DBWithThreadMode, Direction, IteratorMode, SingleThreaded, DB};
+use rocksdb::{DBWithThreadMode, Direction, IteratorMode, Options, SingleThreaded, DB};
 use sbor::{Decode, Encode};
 use scrypto::buffer::*;
 use scrypto::engine::types::*;
@@ -23,6 +23,12 @@ impl RadixEngineDB {
         ledger
     }
 
+    /// Opens an existing ledger without writing to it, so that it can be read alongside `resim`.
+    pub fn read_only(root: PathBuf) -> Result<Self, rocksdb::Error> {
+        let db = DB::open_for_read_only(&Options::default(), root.as_path(), false)?;
+        Ok(Self { db })
+    }
+
     pub fn list_packages(&self) -> Vec<PackageAddress> {
         let start = &scrypto_encode(&PackageAddress([0; 26]));
         let end = &scrypto_encode(&PackageAddress([255; 26]));
@@ -65,6 +71,39 @@ impl RadixEngineDB {
     fn write(&self, key: &[u8], value: &[u8]) {
         self.db.put(key, value).unwrap();
     }
+
+    fn intent_key(intent_hash: &Hash) -> Vec<u8> {
+        let mut id = scrypto_encode(&"intent");
+        id.extend(intent_hash.to_vec());
+        id
+    }
+
+    /// The key of an intent in the index by expiry epoch, which is ordered by epoch.
+    fn intent_expiry_key(expiry_epoch: u64, intent_hash: &Hash) -> Vec<u8> {
+        let mut id = scrypto_encode(&"intent_expiry");
+        id.extend(expiry_epoch.to_be_bytes());
+        id.extend(intent_hash.to_vec());
+        id
+    }
+
+    fn prune_committed_intents(&self, epoch: u64) {
+        let prefix = scrypto_encode(&"intent_expiry");
+        let mut iter = self
+            .db
+            .iterator(IteratorMode::From(&prefix, Direction::Forward));
+        while let Some((key, _)) = iter.next() {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            let (expiry_epoch, intent_hash) = key[prefix.len()..].split_at(8);
+            if u64::from_be_bytes(expiry_epoch.try_into().unwrap()) > epoch {
+                break;
+            }
+            let intent_hash = Hash::try_from(intent_hash).unwrap();
+            self.db.delete(Self::intent_key(&intent_hash)).unwrap();
+            self.db.delete(key).unwrap();
+        }
+    }
 }
 
 impl QueryableSubstateStore for RadixEngineDB {
@@ -73,24 +112,10 @@ impl QueryableSubstateStore for RadixEngineDB {
         component_address: ComponentAddress,
         lazy_map_id: &LazyMapId,
     ) -> HashMap<Vec<u8>, Vec<u8>> {
-        let mut id = scrypto_encode(&component_address);
-        id.extend(scrypto_encode(lazy_map_id));
-        let key_size = id.len();
-
-        let mut iter = self
-            .db
-            .iterator(IteratorMode::From(&id, Direction::Forward));
-        let mut items = HashMap::new();
-        while let Some((key, value)) = iter.next() {
-            if !key.starts_with(&id) {
-                break;
-            }
-
-            let local_key = key.split_at(key_size).1.to_vec();
-            let substate: Substate = scrypto_decode(&value.to_vec()).unwrap();
-            items.insert(local_key, substate.value);
-        }
-        items
+        self.get_grand_child_substates(&component_address, lazy_map_id, None, usize::MAX)
+            .into_iter()
+            .map(|(key, substate)| (key, substate.value))
+            .collect()
     }
 }
 
@@ -116,6 +141,46 @@ impl SubstateStore for RadixEngineDB {
         self.write(&id, &scrypto_encode(&substate));
     }
 
+    fn remove_child_substate<T: Encode>(&mut self, address: &T, key: &[u8]) {
+        let mut id = scrypto_encode(address);
+        id.extend(key.to_vec());
+        self.db.delete(id).unwrap();
+    }
+
+    fn get_child_substates_with_prefix<T: Encode>(
+        &self,
+        address: &T,
+        prefix: &[u8],
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(Vec<u8>, Substate)> {
+        let mut id = scrypto_encode(address);
+        id.extend(prefix.to_vec());
+        let key_size = id.len();
+        let mut start = id.clone();
+        if let Some(start_after) = start_after {
+            start.extend(start_after.to_vec());
+        }
+
+        let mut iter = self
+            .db
+            .iterator(IteratorMode::From(&start, Direction::Forward));
+        let mut items = Vec::new();
+        while let Some((key, value)) = iter.next() {
+            if !key.starts_with(&id) || items.len() == limit {
+                break;
+            }
+            if start_after.is_some() && key.as_ref() == start.as_slice() {
+                continue;
+            }
+
+            let local_key = key.split_at(key_size).1.to_vec();
+            let substate: Substate = scrypto_decode(&value.to_vec()).unwrap();
+            items.push((local_key, substate));
+        }
+        items
+    }
+
     fn get_epoch(&self) -> u64 {
         let id = scrypto_encode(&"epoch");
         self.read(&id)
@@ -126,7 +191,31 @@ impl SubstateStore for RadixEngineDB {
     fn set_epoch(&mut self, epoch: u64) {
         let id = scrypto_encode(&"epoch");
         let value = scrypto_encode(&epoch);
-        self.write(&id, &value)
+        self.write(&id, &value);
+        self.prune_committed_intents(epoch);
+    }
+
+    fn get_current_time(&self) -> u64 {
+        let id = scrypto_encode(&"current_time");
+        self.read(&id)
+            .map(|v| scrypto_decode(&v).unwrap())
+            .unwrap_or(0)
+    }
+
+    fn set_current_time(&mut self, timestamp: u64) {
+        let id = scrypto_encode(&"current_time");
+        let value = scrypto_encode(&round_to_clock_precision(timestamp));
+        self.write(&id, &value);
+    }
+
+    fn is_intent_committed(&self, intent_hash: &Hash) -> bool {
+        self.read(&Self::intent_key(intent_hash)).is_some()
+    }
+
+    fn put_committed_intent(&mut self, intent_hash: &Hash, expiry_epoch: u64) {
+        let value = scrypto_encode(&expiry_epoch);
+        self.write(&Self::intent_key(intent_hash), &value);
+        self.write(&Self::intent_expiry_key(expiry_epoch, intent_hash), &[]);
     }
 
     fn get_nonce(&self) -> u64 {
@@ -4,6 +4,8 @@ pub mod ledger;
 pub mod resim;
 /// Radix transaction manifest compiler CLI.
 pub mod rtmc;
+/// Radix transaction manifest language server.
+pub mod rtmls;
 /// Scrypto CLI.
 pub mod scrypto;
 /// Utility functions.
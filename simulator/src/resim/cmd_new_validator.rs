@@ -0,0 +1,42 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+use crate::resim::*;
+
+/// Create a validator, owned by the default account's key
+#[derive(Parser, Debug)]
+pub struct NewValidator {
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    signing_keys: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl NewValidator {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let public_key = get_default_private_key()?.public_key();
+        let owner_badge =
+            NonFungibleAddress::new(ECDSA_TOKEN, NonFungibleId::from_bytes(public_key.to_vec()));
+
+        let transaction = TransactionBuilder::new()
+            .new_validator(&rule!(require(owner_badge)))
+            .build_with_no_nonce();
+        process_transaction(
+            &mut executor,
+            transaction,
+            &self.signing_keys,
+            &self.manifest,
+            out,
+        )
+    }
+}
@@ -0,0 +1,25 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+
+use crate::resim::*;
+
+/// End the current epoch, distributing the emission to the registered validators
+#[derive(Parser, Debug)]
+pub struct NextEpoch {
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl NextEpoch {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let epoch = executor
+            .next_epoch()
+            .map_err(Error::TransactionExecutionError)?;
+
+        writeln!(out, "Current epoch set to {}!", epoch).map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -15,6 +15,10 @@ pub struct Run {
     #[clap(short, long)]
     signing_keys: Option<String>,
 
+    /// The paths to the blobs referred to by the manifest
+    #[clap(short, long)]
+    blob: Vec<PathBuf>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
@@ -34,8 +38,18 @@ impl Run {
         let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
         let manifest = std::fs::read_to_string(&self.path).map_err(Error::IOError)?;
         let pre_processed_manifest = Self::pre_process_manifest(&manifest);
+        let mut blobs = Vec::new();
+        for path in &self.blob {
+            blobs.push(std::fs::read(path).map_err(Error::IOError)?);
+        }
         let transaction =
-            transaction_manifest::compile(&pre_processed_manifest).map_err(Error::CompileError)?;
+            transaction_manifest::compile(&pre_processed_manifest, blobs).map_err(|e| {
+                eprint!(
+                    "{}",
+                    transaction_manifest::render_error(&pre_processed_manifest, &e)
+                );
+                Error::CompileError(e)
+            })?;
         process_transaction(&mut executor, transaction, &self.signing_keys, &None, out)
     }
 }
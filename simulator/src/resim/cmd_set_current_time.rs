@@ -0,0 +1,21 @@
+use clap::Parser;
+use radix_engine::ledger::SubstateStore;
+
+use crate::resim::*;
+
+/// Set the current time of the ledger clock
+#[derive(Parser, Debug)]
+pub struct SetCurrentTime {
+    /// The new time, in seconds since the Unix epoch
+    timestamp: u64,
+}
+
+impl SetCurrentTime {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        ledger.set_current_time(self.timestamp);
+
+        writeln!(out, "Current time set!").map_err(Error::IOError)?;
+        Ok(())
+    }
+}
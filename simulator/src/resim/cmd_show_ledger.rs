@@ -31,6 +31,12 @@ impl ShowLedger {
                 .map_err(Error::IOError)?;
         }
 
+        writeln!(out, "{}: {}", "Current Epoch".green().bold(), ledger.get_epoch())
+            .map_err(Error::IOError)?;
+
+        writeln!(out, "{}: {}", "Current Time".green().bold(), ledger.get_current_time())
+            .map_err(Error::IOError)?;
+
         writeln!(out, "{}: {}", "Nonce".green().bold(), ledger.get_nonce())
             .map_err(Error::IOError)?;
         Ok(())
@@ -8,10 +8,13 @@ mod cmd_new_badge_fixed;
 mod cmd_new_badge_mutable;
 mod cmd_new_token_fixed;
 mod cmd_new_token_mutable;
+mod cmd_new_validator;
+mod cmd_next_epoch;
 mod cmd_publish;
 mod cmd_reset;
 mod cmd_run;
 mod cmd_set_current_epoch;
+mod cmd_set_current_time;
 mod cmd_set_default_account;
 mod cmd_show;
 mod cmd_show_configs;
@@ -30,10 +33,13 @@ pub use cmd_new_badge_fixed::*;
 pub use cmd_new_badge_mutable::*;
 pub use cmd_new_token_fixed::*;
 pub use cmd_new_token_mutable::*;
+pub use cmd_new_validator::*;
+pub use cmd_next_epoch::*;
 pub use cmd_publish::*;
 pub use cmd_reset::*;
 pub use cmd_run::*;
 pub use cmd_set_current_epoch::*;
+pub use cmd_set_current_time::*;
 pub use cmd_set_default_account::*;
 pub use cmd_show::*;
 pub use cmd_show_configs::*;
@@ -84,10 +90,13 @@ pub enum Command {
     NewBadgeMutable(NewBadgeMutable),
     NewTokenFixed(NewTokenFixed),
     NewTokenMutable(NewTokenMutable),
+    NewValidator(NewValidator),
+    NextEpoch(NextEpoch),
     Publish(Publish),
     Reset(Reset),
     Run(Run),
     SetCurrentEpoch(SetCurrentEpoch),
+    SetCurrentTime(SetCurrentTime),
     SetDefaultAccount(SetDefaultAccount),
     ShowConfigs(ShowConfigs),
     ShowLedger(ShowLedger),
@@ -111,10 +120,13 @@ pub fn run() -> Result<(), Error> {
         Command::NewBadgeMutable(cmd) => cmd.run(&mut out),
         Command::NewTokenFixed(cmd) => cmd.run(&mut out),
         Command::NewTokenMutable(cmd) => cmd.run(&mut out),
+        Command::NewValidator(cmd) => cmd.run(&mut out),
+        Command::NextEpoch(cmd) => cmd.run(&mut out),
         Command::Publish(cmd) => cmd.run(&mut out),
         Command::Reset(cmd) => cmd.run(&mut out),
         Command::Run(cmd) => cmd.run(&mut out),
         Command::SetCurrentEpoch(cmd) => cmd.run(&mut out),
+        Command::SetCurrentTime(cmd) => cmd.run(&mut out),
         Command::SetDefaultAccount(cmd) => cmd.run(&mut out),
         Command::ShowConfigs(cmd) => cmd.run(&mut out),
         Command::ShowLedger(cmd) => cmd.run(&mut out),
@@ -136,7 +148,15 @@ pub fn process_transaction<L: SubstateStore, O: std::io::Write>(
                 Ok(())
             } else {
                 let manifest = decompile(&transaction).map_err(Error::DecompileError)?;
-                fs::write(path, manifest).map_err(Error::IOError)
+                fs::write(path, manifest).map_err(Error::IOError)?;
+
+                // blobs are written next to the manifest, named after their hashes
+                for blob in &transaction.blobs {
+                    let blob_path = path.with_file_name(format!("{}.blob", hash(blob)));
+                    fs::write(&blob_path, blob).map_err(Error::IOError)?;
+                    writeln!(out, "Blob: {}", blob_path.display()).map_err(Error::IOError)?;
+                }
+                Ok(())
             }
         }
         None => {
@@ -147,6 +167,7 @@ pub fn process_transaction<L: SubstateStore, O: std::io::Write>(
                 .collect::<Vec<EcdsaPublicKey>>();
             let nonce = executor.get_nonce(&pks);
             transaction.add_nonce(nonce);
+            transaction.header = TransactionHeader::new(executor.substate_store().get_epoch());
             let signed = transaction.sign(sks.iter().collect::<Vec<&EcdsaPrivateKey>>());
             let receipt = executor
                 .validate_and_execute(&signed)
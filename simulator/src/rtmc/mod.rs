@@ -1,7 +1,11 @@
 use clap::Parser;
+use radix_engine::transaction::TransactionExecutor;
 use scrypto::buffer::scrypto_encode;
 use std::path::PathBuf;
-use transaction_manifest::compile;
+use transaction_manifest::{compile, compile_and_check, render_error};
+
+use crate::ledger::RadixEngineDB;
+use crate::resim::get_data_dir;
 
 /// Radix transaction manifest compiler
 #[derive(Parser, Debug)]
@@ -11,6 +15,14 @@ pub struct Args {
     #[clap(short, long)]
     output: PathBuf,
 
+    /// Paths to the blobs referred to by the manifest, like package code
+    #[clap(short, long)]
+    blob: Vec<PathBuf>,
+
+    /// Check the arguments of calls against the blueprints on the simulator ledger
+    #[clap(short, long)]
+    check: bool,
+
     /// Input file
     #[clap(required = true)]
     input: PathBuf,
@@ -20,13 +32,28 @@ pub struct Args {
 pub enum Error {
     IoError(std::io::Error),
     CompileError(transaction_manifest::CompileError),
+    ResimError(crate::resim::Error),
 }
 
 pub fn run() -> Result<(), Error> {
     let args = Args::parse();
 
     let content = std::fs::read_to_string(args.input).map_err(Error::IoError)?;
-    let transaction = compile(&content).map_err(Error::CompileError)?;
+    let mut blobs = Vec::new();
+    for path in args.blob {
+        blobs.push(std::fs::read(path).map_err(Error::IoError)?);
+    }
+    let transaction = if args.check {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir().map_err(Error::ResimError)?);
+        let executor = TransactionExecutor::new(&mut ledger, false);
+        compile_and_check(&content, blobs, &executor)
+    } else {
+        compile(&content, blobs)
+    }
+    .map_err(|e| {
+        eprint!("{}", render_error(&content, &e));
+        Error::CompileError(e)
+    })?;
     std::fs::write(args.output, scrypto_encode(&transaction)).map_err(Error::IoError)?;
 
     Ok(())
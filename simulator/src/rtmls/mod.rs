@@ -0,0 +1,582 @@
+use scrypto::engine::types::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Read, Write};
+use std::str::FromStr;
+use transaction_manifest::compile_without_blobs;
+use transaction_manifest::diagnostic::diagnose;
+use transaction_manifest::lexer::{tokenize, Span, Token, TokenKind};
+
+use crate::ledger::*;
+use crate::resim::get_data_dir;
+
+const INSTRUCTIONS: &[&str] = &[
+    "TAKE_FROM_WORKTOP",
+    "TAKE_FROM_WORKTOP_BY_AMOUNT",
+    "TAKE_FROM_WORKTOP_BY_IDS",
+    "RETURN_TO_WORKTOP",
+    "ASSERT_WORKTOP_CONTAINS",
+    "ASSERT_WORKTOP_CONTAINS_BY_AMOUNT",
+    "ASSERT_WORKTOP_CONTAINS_BY_IDS",
+    "POP_FROM_AUTH_ZONE",
+    "PUSH_TO_AUTH_ZONE",
+    "CLEAR_AUTH_ZONE",
+    "CREATE_PROOF_FROM_AUTH_ZONE",
+    "CREATE_PROOF_FROM_AUTH_ZONE_BY_AMOUNT",
+    "CREATE_PROOF_FROM_AUTH_ZONE_BY_IDS",
+    "CREATE_PROOF_FROM_BUCKET",
+    "CLONE_PROOF",
+    "DROP_PROOF",
+    "CALL_FUNCTION",
+    "CALL_METHOD",
+    "CALL_METHOD_WITH_ALL_RESOURCES",
+    "PUBLISH_PACKAGE",
+    "PUBLISH_PACKAGE_WITH_UPGRADE_RULE",
+    "UPGRADE_PACKAGE",
+    "MIGRATE_COMPONENT",
+    "SET_PACKAGE_ROYALTY_CONFIG",
+    "CLAIM_PACKAGE_ROYALTY",
+    "SET_PACKAGE_METADATA",
+    "SET_COMPONENT_METADATA",
+    "RECALL_VAULT",
+];
+
+const CONSTRUCTORS: &[&str] = &[
+    "Struct",
+    "Enum",
+    "Some",
+    "None",
+    "Ok",
+    "Err",
+    "Array",
+    "Tuple",
+    "Vec",
+    "TreeSet",
+    "TreeMap",
+    "HashSet",
+    "HashMap",
+    "Decimal",
+    "PackageAddress",
+    "ComponentAddress",
+    "ResourceAddress",
+    "Hash",
+    "Bucket",
+    "Proof",
+    "NonFungibleId",
+    "NonFungibleAddress",
+    "Vault",
+    "Blob",
+    "Expression",
+    "Bytes",
+    "true",
+    "false",
+];
+
+// See https://microsoft.github.io/language-server-protocol/specifications/specification-current
+const TEXT_DOCUMENT_SYNC_FULL: u8 = 1;
+const COMPLETION_ITEM_KIND_CONSTRUCTOR: u8 = 4;
+const COMPLETION_ITEM_KIND_KEYWORD: u8 = 14;
+const DIAGNOSTIC_SEVERITY_ERROR: u8 = 1;
+const ERROR_METHOD_NOT_FOUND: i32 = -32601;
+
+#[derive(Debug)]
+pub enum Error {
+    IoError(std::io::Error),
+    JsonError(serde_json::Error),
+    InvalidHeader(String),
+    MissingContentLength,
+}
+
+/// Runs the language server, over stdin and stdout.
+pub fn run() -> Result<(), Error> {
+    // hover shows the ledger dumps as plain text
+    colored::control::set_override(false);
+
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    let mut input = stdin.lock();
+    let mut output = stdout.lock();
+
+    let mut server = Server::new();
+    while let Some(message) = read_message(&mut input)? {
+        for reply in server.handle(&message) {
+            write_message(&mut output, &reply)?;
+        }
+        if server.exited {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a message, which is a JSON-RPC payload preceded by a `Content-Length` header.
+fn read_message<R: BufRead>(input: &mut R) -> Result<Option<Value>, Error> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line).map_err(Error::IoError)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = Some(
+                value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| Error::InvalidHeader(line.to_owned()))?,
+            );
+        }
+    }
+
+    let mut body = vec![0u8; length.ok_or(Error::MissingContentLength)?];
+    input.read_exact(&mut body).map_err(Error::IoError)?;
+    serde_json::from_slice(&body)
+        .map(Option::Some)
+        .map_err(Error::JsonError)
+}
+
+fn write_message<W: Write>(output: &mut W, message: &Value) -> Result<(), Error> {
+    let body = serde_json::to_string(message).map_err(Error::JsonError)?;
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body).map_err(Error::IoError)?;
+    output.flush().map_err(Error::IoError)
+}
+
+/// Keeps the open manifests, and replies to the messages from the editor.
+pub struct Server {
+    documents: HashMap<String, String>,
+    /// The ledger of the simulator, opened on the first hover
+    ledger: Option<RadixEngineDB>,
+    exited: bool,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self {
+            documents: HashMap::new(),
+            ledger: None,
+            exited: false,
+        }
+    }
+
+    /// Handles a request or notification, and returns the messages to send back.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let id = &message["id"];
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        match message["method"].as_str().unwrap_or_default() {
+            "initialize" => vec![response(
+                id,
+                json!({
+                    "capabilities": {
+                        "textDocumentSync": TEXT_DOCUMENT_SYNC_FULL,
+                        "completionProvider": {},
+                        "hoverProvider": true,
+                        "documentFormattingProvider": true,
+                    },
+                    "serverInfo": {
+                        "name": "rtmls",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }),
+            )],
+            "shutdown" => vec![response(id, Value::Null)],
+            "exit" => {
+                self.exited = true;
+                vec![]
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.to_owned(), text.to_owned());
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/didChange" => {
+                // the whole text is sent on every change, see `TEXT_DOCUMENT_SYNC_FULL`
+                if let Some(change) = params["contentChanges"].as_array().and_then(|c| c.last()) {
+                    let text = change["text"].as_str().unwrap_or_default();
+                    self.documents.insert(uri.to_owned(), text.to_owned());
+                }
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/completion" => vec![response(id, completion_items())],
+            "textDocument/hover" => {
+                let line = params["position"]["line"].as_u64().unwrap_or_default() as usize;
+                let column = params["position"]["character"].as_u64().unwrap_or_default() as usize;
+                vec![response(id, self.hover(uri, (line + 1, column + 1)))]
+            }
+            "textDocument/formatting" => vec![response(id, self.format(uri))],
+            method => {
+                if id.is_null() || method.is_empty() {
+                    // notifications and responses need no reply
+                    vec![]
+                } else {
+                    vec![json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": ERROR_METHOD_NOT_FOUND,
+                            "message": format!("Method not found: {}", method),
+                        },
+                    })]
+                }
+            }
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        let diagnostics: Vec<Value> = match self.documents.get(uri) {
+            // blobs are only attached when the manifest is run
+            Some(text) => match compile_without_blobs(text) {
+                Ok(_) => vec![],
+                Err(e) => diagnose(text, &e)
+                    .into_iter()
+                    .map(|d| {
+                        let message = match d.hint {
+                            Some(hint) => format!("{}\nhint: {}", d.message, hint),
+                            None => d.message,
+                        };
+                        json!({
+                            "range": to_range(&d.span),
+                            "severity": DIAGNOSTIC_SEVERITY_ERROR,
+                            "source": "rtmls",
+                            "message": message,
+                        })
+                    })
+                    .collect(),
+            },
+            None => vec![],
+        };
+
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": {
+                "uri": uri,
+                "diagnostics": diagnostics,
+            },
+        })
+    }
+
+    /// Describes the package, component or resource whose address is under the cursor.
+    fn hover(&mut self, uri: &str, position: (usize, usize)) -> Value {
+        let token = self
+            .documents
+            .get(uri)
+            .and_then(|text| tokenize(text).ok())
+            .and_then(|tokens| {
+                tokens
+                    .into_iter()
+                    .find(|t| t.span.start <= position && position <= t.span.end)
+            });
+        let (address, span) = match token {
+            Some(Token {
+                kind: TokenKind::StringLiteral(s),
+                span,
+            }) => (s, span),
+            _ => return Value::Null,
+        };
+
+        if self.ledger.is_none() {
+            self.ledger = get_data_dir()
+                .ok()
+                .and_then(|dir| RadixEngineDB::read_only(dir).ok());
+        }
+
+        match self
+            .ledger
+            .as_ref()
+            .and_then(|ledger| describe_address(&address, ledger))
+        {
+            Some(description) => json!({
+                "contents": {
+                    "kind": "markdown",
+                    "value": format!("```\n{}```", description),
+                },
+                "range": to_range(&span),
+            }),
+            None => Value::Null,
+        }
+    }
+
+    /// Formats a manifest from its tokens, keeping their source text, so that names and literals
+    /// are left as written.
+    fn format(&self, uri: &str) -> Value {
+        let text = match self.documents.get(uri) {
+            Some(text) => text,
+            None => return Value::Null,
+        };
+        // the lexer drops comments
+        if has_comments(text) {
+            return json!([]);
+        }
+
+        match tokenize(text).map(|tokens| format_tokens(text, &tokens)) {
+            Ok(formatted) if formatted != *text => {
+                let last_line = text.split('\n').last().unwrap_or_default();
+                json!([{
+                    "range": {
+                        "start": { "line": 0, "character": 0 },
+                        "end": {
+                            "line": text.split('\n').count() - 1,
+                            "character": last_line.chars().count(),
+                        },
+                    },
+                    "newText": formatted,
+                }])
+            }
+            _ => json!([]),
+        }
+    }
+}
+
+fn response(id: &Value, result: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": result,
+    })
+}
+
+/// Converts a span into an LSP range, which is 0-indexed and excludes the end.
+fn to_range(span: &Span) -> Value {
+    json!({
+        "start": { "line": span.start.0 - 1, "character": span.start.1 - 1 },
+        "end": { "line": span.end.0 - 1, "character": span.end.1 },
+    })
+}
+
+fn completion_items() -> Value {
+    let instructions = INSTRUCTIONS
+        .iter()
+        .map(|label| json!({ "label": label, "kind": COMPLETION_ITEM_KIND_KEYWORD }));
+    let constructors = CONSTRUCTORS
+        .iter()
+        .map(|label| json!({ "label": label, "kind": COMPLETION_ITEM_KIND_CONSTRUCTOR }));
+    Value::Array(instructions.chain(constructors).collect())
+}
+
+fn describe_address(address: &str, ledger: &RadixEngineDB) -> Option<String> {
+    let mut out = Vec::new();
+    if let Ok(package_address) = PackageAddress::from_str(address) {
+        dump_package(package_address, ledger, &mut out).ok()?;
+    } else if let Ok(component_address) = ComponentAddress::from_str(address) {
+        dump_component(component_address, ledger, &mut out).ok()?;
+    } else if let Ok(resource_address) = ResourceAddress::from_str(address) {
+        dump_resource_manager(resource_address, ledger, &mut out).ok()?;
+    } else {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&out).into_owned())
+}
+
+/// Lays out the tokens one instruction per line, with a single space between values.
+fn format_tokens(text: &str, tokens: &[Token]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut line_starts = vec![0];
+    line_starts.extend(
+        (0..chars.len())
+            .filter(|i| chars[*i] == '\n')
+            .map(|i| i + 1),
+    );
+    let index = |(line, column): (usize, usize)| line_starts[line - 1] + column - 1;
+
+    let mut formatted = String::new();
+    let mut previous: Option<&TokenKind> = None;
+    for token in tokens {
+        let glued = match (previous, &token.kind) {
+            (None, _) | (Some(TokenKind::Semicolon), _) => true,
+            (Some(TokenKind::OpenParenthesis | TokenKind::LessThan), _) => true,
+            (
+                _,
+                TokenKind::CloseParenthesis
+                | TokenKind::GreaterThan
+                | TokenKind::Comma
+                | TokenKind::Semicolon,
+            ) => true,
+            // `Decimal("1")` and `Vec<U8>`, but not the unit value `()`
+            (Some(kind), TokenKind::OpenParenthesis | TokenKind::LessThan) => {
+                *kind == TokenKind::GreaterThan || is_type(kind)
+            }
+            _ => false,
+        };
+        if !glued {
+            formatted.push(' ');
+        }
+        formatted.extend(&chars[index(token.span.start)..=index(token.span.end)]);
+        if token.kind == TokenKind::Semicolon {
+            formatted.push('\n');
+        }
+        previous = Some(&token.kind);
+    }
+    formatted
+}
+
+fn is_type(kind: &TokenKind) -> bool {
+    match kind {
+        TokenKind::Unit
+        | TokenKind::Bool
+        | TokenKind::I8
+        | TokenKind::I16
+        | TokenKind::I32
+        | TokenKind::I64
+        | TokenKind::I128
+        | TokenKind::U8
+        | TokenKind::U16
+        | TokenKind::U32
+        | TokenKind::U64
+        | TokenKind::U128
+        | TokenKind::String
+        | TokenKind::Struct
+        | TokenKind::Enum
+        | TokenKind::Option
+        | TokenKind::Box
+        | TokenKind::Array
+        | TokenKind::Tuple
+        | TokenKind::Result
+        | TokenKind::Vec
+        | TokenKind::TreeSet
+        | TokenKind::TreeMap
+        | TokenKind::HashSet
+        | TokenKind::HashMap
+        | TokenKind::Decimal
+        | TokenKind::PackageAddress
+        | TokenKind::ComponentAddress
+        | TokenKind::ResourceAddress
+        | TokenKind::Hash
+        | TokenKind::Bucket
+        | TokenKind::Proof
+        | TokenKind::NonFungibleId
+        | TokenKind::NonFungibleAddress
+        | TokenKind::Vault
+        | TokenKind::Blob
+        | TokenKind::Expression
+        | TokenKind::Some
+        | TokenKind::None
+        | TokenKind::Ok
+        | TokenKind::Err
+        | TokenKind::Bytes => true,
+        _ => false,
+    }
+}
+
+/// Returns whether the manifest has a `#` comment, outside of string literals.
+fn has_comments(text: &str) -> bool {
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in text.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(server: &mut Server, text: &str) -> Value {
+        server
+            .handle(&json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {
+                    "textDocument": { "uri": "file:///test.rtm", "text": text },
+                },
+            }))
+            .remove(0)
+    }
+
+    #[test]
+    fn test_read_write_message() {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" });
+        let mut buf = Vec::new();
+        write_message(&mut buf, &message).unwrap();
+        assert_eq!(
+            read_message(&mut buf.as_slice()).unwrap(),
+            Some(message.clone())
+        );
+        assert_eq!(read_message(&mut "".as_bytes()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut server = Server::new();
+        assert_eq!(
+            open(&mut server, "CLEAR_AUTH_ZONE;")["params"]["diagnostics"],
+            json!([])
+        );
+        assert_eq!(
+            open(&mut server, "CLEAR_AUTH_ZONE;\nDROP_PROOF 1u8;")["params"]["diagnostics"],
+            json!([{
+                "range": {
+                    "start": { "line": 1, "character": 11 },
+                    "end": { "line": 1, "character": 14 },
+                },
+                "severity": DIAGNOSTIC_SEVERITY_ERROR,
+                "source": "rtmls",
+                "message": "invalid value\nhint: expected Proof, found U8",
+            }])
+        );
+        // blobs are not attached, but errors after them are still reported
+        assert_eq!(
+            open(
+                &mut server,
+                "PUBLISH_PACKAGE Blob(\"0577f08f0322aba5d9567821972438561d69510d84e0cf4f85b0081b188b5421\");\nDROP_PROOF 1u8;"
+            )["params"]["diagnostics"][0]["range"],
+            json!({
+                "start": { "line": 1, "character": 11 },
+                "end": { "line": 1, "character": 14 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_formatting() {
+        let mut server = Server::new();
+        open(&mut server, "CLEAR_AUTH_ZONE ;");
+        let reply = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "textDocument/formatting",
+            "params": { "textDocument": { "uri": "file:///test.rtm" } },
+        }));
+        assert_eq!(reply[0]["result"][0]["newText"], "CLEAR_AUTH_ZONE;\n");
+
+        open(
+            &mut server,
+            "TAKE_FROM_WORKTOP  ResourceAddress(\"030000000000000000000000000000000000000000000000000004\")\n  Bucket(\"xrd\") ;",
+        );
+        let reply = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "textDocument/formatting",
+            "params": { "textDocument": { "uri": "file:///test.rtm" } },
+        }));
+        assert_eq!(
+            reply[0]["result"][0]["newText"],
+            "TAKE_FROM_WORKTOP ResourceAddress(\"030000000000000000000000000000000000000000000000000004\") Bucket(\"xrd\");\n"
+        );
+
+        open(&mut server, "CLEAR_AUTH_ZONE ; # clear");
+        let reply = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "textDocument/formatting",
+            "params": { "textDocument": { "uri": "file:///test.rtm" } },
+        }));
+        assert_eq!(reply[0]["result"], json!([]));
+    }
+}
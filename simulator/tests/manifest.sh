@@ -19,4 +19,8 @@ export resource=`echo $output | cut -d " " -f2`
 
 $resim run ./tests/m2.rtm
 
+export validator=`$resim new-validator | awk '/Component:/ {print $NF}'`
+$resim run ./tests/m3.rtm
+$resim next-epoch
+
 $resim show-ledger
\ No newline at end of file
@@ -12,6 +12,7 @@ blueprint! {
     impl Foo {
         pub fn nfts() -> Bucket {
             ResourceBuilder::new_non_fungible()
+                .id_type(NonFungibleIdType::UUID)
                 .metadata("name", "Cars!")
                 .metadata("description", "Fast Cars")
                 .initial_supply(vec![
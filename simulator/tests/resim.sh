@@ -28,6 +28,11 @@ package=`$resim publish ../examples/hello-world | awk '/Package:/ {print $NF}'`
 component=`$resim call-function $package Hello instantiate_hello | awk '/Component:/ {print $NF}'`
 $resim call-method $component free_token
 
+# Test - ledger clock
+$resim set-current-epoch 5
+$resim set-current-time 1660000000
+$resim show-ledger
+
 # Test - export abi
 $resim export-abi $package Hello
 
@@ -41,14 +46,14 @@ $resim show $token_address
 # Test - output manifest
 $resim new-badge-fixed 1 --name 'MintBadge' --manifest ./target/temp.rtm
 cat ./target/temp.rtm
-$resim publish ../examples/hello-world --manifest ./target/temp2.rtm
-$resim run ./target/temp2.rtm
+blob=`$resim publish ../examples/hello-world --manifest ./target/temp2.rtm | awk '/Blob:/ {print $NF}'`
+$resim run ./target/temp2.rtm --blob $blob
 $resim new-account --manifest ./target/temp3.rtm
 $resim run ./target/temp3.rtm
 
 # Test - run manifest with a given set of signing keys
 $resim generate-key-pair
-$resim run ./target/temp2.rtm --signing-keys 4fc0db017bf9b80743b7151fee3f04bad817f2d8d7e34ae96c022fe7451b0ea3,329a27258d7e9496c42a110571e6ba0d47f2bda8bd610e9777d16853ab145b0c
+$resim run ./target/temp2.rtm --blob $blob --signing-keys 4fc0db017bf9b80743b7151fee3f04bad817f2d8d7e34ae96c022fe7451b0ea3,329a27258d7e9496c42a110571e6ba0d47f2bda8bd610e9777d16853ab145b0c
 
 # Test - nft
 package=`$resim publish ./tests/nft | awk '/Package:/ {print $NF}'`
@@ -1,106 +1,152 @@
+use crate::lexer::Span;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Transaction {
     pub instructions: Vec<Instruction>,
+    /// The location of each instruction in the manifest
+    pub spans: Vec<Span>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Instruction {
     TakeFromWorktop {
-        resource_address: Value,
-        new_bucket: Value,
+        resource_address: ValueWithSpan,
+        new_bucket: ValueWithSpan,
     },
 
     TakeFromWorktopByAmount {
-        amount: Value,
-        resource_address: Value,
-        new_bucket: Value,
+        amount: ValueWithSpan,
+        resource_address: ValueWithSpan,
+        new_bucket: ValueWithSpan,
     },
 
     TakeFromWorktopByIds {
-        ids: Value,
-        resource_address: Value,
-        new_bucket: Value,
+        ids: ValueWithSpan,
+        resource_address: ValueWithSpan,
+        new_bucket: ValueWithSpan,
     },
 
     ReturnToWorktop {
-        bucket: Value,
+        bucket: ValueWithSpan,
     },
 
     AssertWorktopContains {
-        resource_address: Value,
+        resource_address: ValueWithSpan,
     },
 
     AssertWorktopContainsByAmount {
-        amount: Value,
-        resource_address: Value,
+        amount: ValueWithSpan,
+        resource_address: ValueWithSpan,
     },
 
     AssertWorktopContainsByIds {
-        ids: Value,
-        resource_address: Value,
+        ids: ValueWithSpan,
+        resource_address: ValueWithSpan,
     },
 
     PopFromAuthZone {
-        new_proof: Value,
+        new_proof: ValueWithSpan,
     },
 
     PushToAuthZone {
-        proof: Value,
+        proof: ValueWithSpan,
     },
 
     ClearAuthZone,
 
     CreateProofFromAuthZone {
-        resource_address: Value,
-        new_proof: Value,
+        resource_address: ValueWithSpan,
+        new_proof: ValueWithSpan,
     },
 
     CreateProofFromAuthZoneByAmount {
-        amount: Value,
-        resource_address: Value,
-        new_proof: Value,
+        amount: ValueWithSpan,
+        resource_address: ValueWithSpan,
+        new_proof: ValueWithSpan,
     },
 
     CreateProofFromAuthZoneByIds {
-        ids: Value,
-        resource_address: Value,
-        new_proof: Value,
+        ids: ValueWithSpan,
+        resource_address: ValueWithSpan,
+        new_proof: ValueWithSpan,
     },
 
     CreateProofFromBucket {
-        bucket: Value,
-        new_proof: Value,
+        bucket: ValueWithSpan,
+        new_proof: ValueWithSpan,
     },
 
     CloneProof {
-        proof: Value,
-        new_proof: Value,
+        proof: ValueWithSpan,
+        new_proof: ValueWithSpan,
     },
 
     DropProof {
-        proof: Value,
+        proof: ValueWithSpan,
     },
 
     CallFunction {
-        package_address: Value,
-        blueprint_name: Value,
-        function: Value,
-        args: Vec<Value>,
+        package_address: ValueWithSpan,
+        blueprint_name: ValueWithSpan,
+        function: ValueWithSpan,
+        args: Vec<ValueWithSpan>,
     },
 
     CallMethod {
-        component_address: Value,
-        method: Value,
-        args: Vec<Value>,
+        component_address: ValueWithSpan,
+        method: ValueWithSpan,
+        args: Vec<ValueWithSpan>,
     },
 
     CallMethodWithAllResources {
-        component_address: Value,
-        method: Value,
+        component_address: ValueWithSpan,
+        method: ValueWithSpan,
     },
 
     PublishPackage {
-        code: Value,
+        code: ValueWithSpan,
+    },
+
+    PublishPackageWithUpgradeRule {
+        code: ValueWithSpan,
+        upgrade_rule: ValueWithSpan,
+    },
+
+    UpgradePackage {
+        package_address: ValueWithSpan,
+        code: ValueWithSpan,
+    },
+
+    MigrateComponent {
+        component_address: ValueWithSpan,
+        package_address: ValueWithSpan,
+        function: ValueWithSpan,
+    },
+
+    SetPackageRoyaltyConfig {
+        package_address: ValueWithSpan,
+        blueprint_name: ValueWithSpan,
+        royalty_config: ValueWithSpan,
+    },
+
+    ClaimPackageRoyalty {
+        package_address: ValueWithSpan,
+    },
+
+    SetPackageMetadata {
+        package_address: ValueWithSpan,
+        metadata: ValueWithSpan,
+    },
+
+    SetComponentMetadata {
+        component_address: ValueWithSpan,
+        metadata: ValueWithSpan,
+    },
+
+    RecallVault {
+        component_address: ValueWithSpan,
+        vault: ValueWithSpan,
+        amount: ValueWithSpan,
     },
 }
 
@@ -144,11 +190,23 @@ pub enum Type {
     Proof,
     NonFungibleId,
     NonFungibleAddress,
+    Vault,
+    Expression,
+
+    /* Blob is a reference to a payload attached to the transaction, by hash */
+    Blob,
 
     /* Bytes is a convenient way of producing `Vec<u8>` */
     Bytes,
 }
 
+/// A value of an instruction, along with its location in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueWithSpan {
+    pub value: Value,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Value {
     Unit,
@@ -186,6 +244,10 @@ pub enum Value {
     Proof(Box<Value>),
     NonFungibleId(Box<Value>),
     NonFungibleAddress(Box<Value>),
+    Vault(Box<Value>),
+    Expression(Box<Value>),
+
+    Blob(Box<Value>),
 
     Bytes(Vec<u8>),
 }
@@ -226,6 +288,9 @@ impl Value {
             Value::Proof(_) => Type::Proof,
             Value::NonFungibleId(_) => Type::NonFungibleId,
             Value::NonFungibleAddress(_) => Type::NonFungibleAddress,
+            Value::Vault(_) => Type::Vault,
+            Value::Expression(_) => Type::Expression,
+            Value::Blob(_) => Type::Blob,
             Value::Bytes(_) => Type::Vec,
         }
     }
@@ -0,0 +1,483 @@
+use crate::ast;
+use crate::lexer::Span;
+use radix_engine::engine::{is_native_component, is_native_package};
+use radix_engine::model::*;
+use radix_engine::transaction::AbiProvider;
+use sbor::any::Value;
+use sbor::describe::{Fields, Type};
+use sbor::type_id::*;
+use scrypto::core::Expression;
+use scrypto::engine::types::*;
+use scrypto::types::*;
+use scrypto::values::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckErrorKind {
+    BlueprintNotFound(PackageAddress, String),
+    ComponentNotFound(ComponentAddress),
+    FunctionNotFound(String),
+    MethodNotFound(String),
+    InvalidNumberOfArguments {
+        expected: usize,
+        actual: usize,
+    },
+    InvalidArgument {
+        index: usize,
+        expected_type: Type,
+        actual: String,
+    },
+}
+
+/// Represents an error found by checking a manifest against the ABIs of the blueprints it calls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckError {
+    pub kind: CheckErrorKind,
+    pub span: Span,
+}
+
+/// Checks the arguments of every `CALL_FUNCTION` and `CALL_METHOD` against the ABIs of the
+/// blueprints being called.
+///
+/// Calls to native packages and components are skipped, as they have no ABI.
+///
+/// The transaction must be the one generated from the given AST.
+pub fn check_transaction<A: AbiProvider>(
+    tx: &ast::Transaction,
+    transaction: &Transaction,
+    abi_provider: &A,
+) -> Vec<CheckError> {
+    let mut errors = Vec::new();
+
+    for ((instruction, ast_instruction), span) in transaction
+        .instructions
+        .iter()
+        .zip(&tx.instructions)
+        .zip(&tx.spans)
+    {
+        let result = match instruction {
+            Instruction::CallFunction {
+                package_address, ..
+            } if is_native_package(package_address) => Ok(()),
+            Instruction::CallMethod {
+                component_address, ..
+            } if is_native_component(component_address) => Ok(()),
+            Instruction::CallFunction {
+                package_address,
+                blueprint_name,
+                function,
+                args,
+            } => abi_provider
+                .export_abi(*package_address, blueprint_name)
+                .map_err(|_| {
+                    CheckErrorKind::BlueprintNotFound(*package_address, blueprint_name.clone())
+                })
+                .and_then(|abi| {
+                    abi.functions
+                        .into_iter()
+                        .find(|f| &f.name == function)
+                        .ok_or(CheckErrorKind::FunctionNotFound(function.clone()))
+                })
+                .and_then(|f| check_args(args, &f.inputs)),
+            Instruction::CallMethod {
+                component_address,
+                method,
+                args,
+            } => abi_provider
+                .export_abi_by_component(*component_address)
+                .map_err(|_| CheckErrorKind::ComponentNotFound(*component_address))
+                .and_then(|abi| {
+                    abi.methods
+                        .into_iter()
+                        .find(|m| &m.name == method)
+                        .ok_or(CheckErrorKind::MethodNotFound(method.clone()))
+                })
+                .and_then(|m| check_args(args, &m.inputs)),
+            _ => Ok(()),
+        };
+
+        if let Err(kind) = result {
+            let span = error_span(&kind, ast_instruction).unwrap_or_else(|| span.clone());
+            errors.push(CheckError { kind, span });
+        }
+    }
+
+    errors
+}
+
+/// Returns the location of the value an error is about, if any.
+fn error_span(kind: &CheckErrorKind, instruction: &ast::Instruction) -> Option<Span> {
+    let value = match (kind, instruction) {
+        (
+            CheckErrorKind::BlueprintNotFound(..),
+            ast::Instruction::CallFunction { blueprint_name, .. },
+        ) => Some(blueprint_name),
+        (
+            CheckErrorKind::ComponentNotFound(_),
+            ast::Instruction::CallMethod {
+                component_address, ..
+            },
+        ) => Some(component_address),
+        (CheckErrorKind::FunctionNotFound(_), ast::Instruction::CallFunction { function, .. }) => {
+            Some(function)
+        }
+        (CheckErrorKind::MethodNotFound(_), ast::Instruction::CallMethod { method, .. }) => {
+            Some(method)
+        }
+        (
+            CheckErrorKind::InvalidArgument { index, .. },
+            ast::Instruction::CallFunction { args, .. },
+        )
+        | (
+            CheckErrorKind::InvalidArgument { index, .. },
+            ast::Instruction::CallMethod { args, .. },
+        ) => args.get(*index),
+        _ => None,
+    };
+    value.map(|value| value.span.clone())
+}
+
+fn check_args(args: &[Vec<u8>], inputs: &[Type]) -> Result<(), CheckErrorKind> {
+    if args.len() != inputs.len() {
+        return Err(CheckErrorKind::InvalidNumberOfArguments {
+            expected: inputs.len(),
+            actual: args.len(),
+        });
+    }
+
+    for (index, (arg, ty)) in args.iter().zip(inputs).enumerate() {
+        // arguments are generated from the manifest, so they always parse
+        let value = ScryptoValue::from_slice(arg).unwrap();
+        if !matches_type(&value.dom, ty) {
+            return Err(CheckErrorKind::InvalidArgument {
+                index,
+                expected_type: ty.clone(),
+                actual: value.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the SBOR type id of a type, or `None` if it's a custom type unknown to Scrypto.
+fn type_id(ty: &Type) -> Option<u8> {
+    Some(match ty {
+        Type::Unit => TYPE_UNIT,
+        Type::Bool => TYPE_BOOL,
+        Type::I8 => TYPE_I8,
+        Type::I16 => TYPE_I16,
+        Type::I32 => TYPE_I32,
+        Type::I64 => TYPE_I64,
+        Type::I128 => TYPE_I128,
+        Type::U8 => TYPE_U8,
+        Type::U16 => TYPE_U16,
+        Type::U32 => TYPE_U32,
+        Type::U64 => TYPE_U64,
+        Type::U128 => TYPE_U128,
+        Type::String => TYPE_STRING,
+        Type::Option { .. } => TYPE_OPTION,
+        Type::Array { .. } => TYPE_ARRAY,
+        Type::Tuple { .. } => TYPE_TUPLE,
+        Type::Struct { .. } => TYPE_STRUCT,
+        Type::Enum { .. } => TYPE_ENUM,
+        Type::Result { .. } => TYPE_RESULT,
+        Type::Vec { .. } => TYPE_VEC,
+        Type::TreeSet { .. } => TYPE_TREE_SET,
+        Type::TreeMap { .. } => TYPE_TREE_MAP,
+        Type::HashSet { .. } => TYPE_HASH_SET,
+        Type::HashMap { .. } => TYPE_HASH_MAP,
+        Type::Custom { name, .. } => ScryptoType::from_name(name)?.id(),
+    })
+}
+
+fn matches_type(value: &Value, ty: &Type) -> bool {
+    match (ty, value) {
+        // custom types unknown to Scrypto can't be checked
+        (Type::Custom { name, .. }, _) if ScryptoType::from_name(name).is_none() => true,
+        (Type::Unit, Value::Unit)
+        | (Type::Bool, Value::Bool { .. })
+        | (Type::I8, Value::I8 { .. })
+        | (Type::I16, Value::I16 { .. })
+        | (Type::I32, Value::I32 { .. })
+        | (Type::I64, Value::I64 { .. })
+        | (Type::I128, Value::I128 { .. })
+        | (Type::U8, Value::U8 { .. })
+        | (Type::U16, Value::U16 { .. })
+        | (Type::U32, Value::U32 { .. })
+        | (Type::U64, Value::U64 { .. })
+        | (Type::U128, Value::U128 { .. })
+        | (Type::String, Value::String { .. }) => true,
+        (Type::Option { value: ty }, Value::Option { value }) => match &**value {
+            Some(v) => matches_type(v, ty),
+            None => true,
+        },
+        (
+            Type::Array { element, length },
+            Value::Array {
+                element_type_id,
+                elements,
+            },
+        ) => {
+            elements.len() == *length as usize
+                && matches_elements(*element_type_id, elements, element)
+        }
+        (Type::Tuple { elements: types }, Value::Tuple { elements }) => {
+            matches_all(elements, types)
+        }
+        (Type::Struct { fields: types, .. }, Value::Struct { fields }) => {
+            matches_fields(fields, types)
+        }
+        (Type::Enum { variants, .. }, Value::Enum { name, fields }) => variants
+            .iter()
+            .find(|v| &v.name == name)
+            .map_or(false, |v| matches_fields(fields, &v.fields)),
+        (Type::Result { okay, error }, Value::Result { value }) => match &**value {
+            Ok(v) => matches_type(v, okay),
+            Err(v) => matches_type(v, error),
+        },
+        (
+            Type::Vec { element },
+            Value::Vec {
+                element_type_id,
+                elements,
+            },
+        )
+        | (
+            Type::TreeSet { element },
+            Value::TreeSet {
+                element_type_id,
+                elements,
+            },
+        )
+        | (
+            Type::HashSet { element },
+            Value::HashSet {
+                element_type_id,
+                elements,
+            },
+        ) => matches_elements(*element_type_id, elements, element),
+        (
+            Type::TreeMap { key, value },
+            Value::TreeMap {
+                key_type_id,
+                value_type_id,
+                elements,
+            },
+        )
+        | (
+            Type::HashMap { key, value },
+            Value::HashMap {
+                key_type_id,
+                value_type_id,
+                elements,
+            },
+        ) => matches_pairs(*key_type_id, *value_type_id, elements, key, value),
+        // expressions are resolved into resources when the transaction runs
+        (Type::Vec { element }, Value::Custom { type_id, bytes })
+            if *type_id == ScryptoType::Expression.id() =>
+        {
+            let resolved = match Expression::try_from(bytes.as_slice()) {
+                Ok(Expression::EntireWorktop) => ScryptoType::Bucket,
+                Ok(Expression::EntireAuthZone) => ScryptoType::Proof,
+                Err(_) => return false,
+            };
+            matches!(&**element, Type::Custom { name, .. } if *name == resolved.name())
+        }
+        (Type::Custom { .. }, Value::Custom { type_id: id, .. }) => type_id(ty) == Some(*id),
+        _ => false,
+    }
+}
+
+fn matches_all(values: &[Value], types: &[Type]) -> bool {
+    values.len() == types.len() && values.iter().zip(types).all(|(v, t)| matches_type(v, t))
+}
+
+fn matches_fields(values: &[Value], fields: &Fields) -> bool {
+    match fields {
+        Fields::Named { named } => {
+            values.len() == named.len()
+                && values
+                    .iter()
+                    .zip(named)
+                    .all(|(v, (_, t))| matches_type(v, t))
+        }
+        Fields::Unnamed { unnamed } => matches_all(values, unnamed),
+        Fields::Unit => values.is_empty(),
+    }
+}
+
+fn matches_elements(element_type_id: u8, elements: &[Value], element: &Type) -> bool {
+    type_id(element).map_or(true, |id| id == element_type_id)
+        && elements.iter().all(|e| matches_type(e, element))
+}
+
+fn matches_pairs(
+    key_type_id: u8,
+    value_type_id: u8,
+    elements: &[Value],
+    key: &Type,
+    value: &Type,
+) -> bool {
+    type_id(key).map_or(true, |id| id == key_type_id)
+        && type_id(value).map_or(true, |id| id == value_type_id)
+        && elements.chunks(2).all(|pair| {
+            pair.len() == 2 && matches_type(&pair[0], key) && matches_type(&pair[1], value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_engine::transaction::StaticAbiProvider;
+    use scrypto::abi;
+    use scrypto::abi::Mutability;
+    use scrypto::constants::*;
+    use std::str::FromStr;
+
+    const PACKAGE: &str = "01d1f50010e4102d88aacc347711491f852c515134a9ecf67ba17c";
+    const COMPONENT: &str = "02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de";
+
+    fn abi_provider() -> StaticAbiProvider {
+        let package_address = PackageAddress::from_str(PACKAGE).unwrap();
+        let mut abi_provider = StaticAbiProvider::new();
+        abi_provider
+            .with_blueprint(
+                package_address,
+                abi::Blueprint {
+                    package_address: PACKAGE.to_owned(),
+                    blueprint_name: "Airdrop".to_owned(),
+                    functions: vec![abi::Function {
+                        name: "new".to_owned(),
+                        inputs: vec![
+                            Type::U32,
+                            Type::Option {
+                                value: Box::new(Type::String),
+                            },
+                        ],
+                        output: Type::Unit,
+                    }],
+                    methods: vec![abi::Method {
+                        name: "deposit_batch".to_owned(),
+                        mutability: Mutability::Mutable,
+                        inputs: vec![Type::Vec {
+                            element: Box::new(Type::Custom {
+                                name: "Bucket".to_owned(),
+                                generics: vec![],
+                            }),
+                        }],
+                        output: Type::Unit,
+                    }],
+                    events: vec![],
+                },
+            )
+            .with_component(
+                ComponentAddress::from_str(COMPONENT).unwrap(),
+                package_address,
+                "Airdrop",
+            );
+        abi_provider
+    }
+
+    fn check_with_spans(s: &str) -> Vec<CheckError> {
+        let tokens = crate::lexer::tokenize(s).unwrap();
+        let ast = crate::parser::Parser::new(tokens)
+            .parse_transaction()
+            .unwrap();
+        let transaction = crate::generator::generate_transaction(&ast, vec![]).unwrap();
+        check_transaction(&ast, &transaction, &abi_provider())
+    }
+
+    fn check(s: &str) -> Vec<CheckErrorKind> {
+        check_with_spans(s).into_iter().map(|e| e.kind).collect()
+    }
+
+    #[test]
+    fn test_valid_calls() {
+        assert_eq!(
+            check(&format!(
+                r#"CALL_FUNCTION PackageAddress("{}") "Airdrop" "new" 5u32 Some("hello");
+                CALL_METHOD ComponentAddress("{}") "deposit_batch" Expression("ENTIRE_WORKTOP");"#,
+                PACKAGE, COMPONENT
+            )),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_invalid_calls() {
+        assert_eq!(
+            check(&format!(
+                r#"CALL_FUNCTION PackageAddress("{}") "Airdrop" "new" 5u32 Some(1u8);"#,
+                PACKAGE
+            )),
+            vec![CheckErrorKind::InvalidArgument {
+                index: 1,
+                expected_type: Type::Option {
+                    value: Box::new(Type::String)
+                },
+                actual: "Some(1u8)".to_owned(),
+            }]
+        );
+        assert_eq!(
+            check(&format!(
+                r#"CALL_FUNCTION PackageAddress("{}") "Airdrop" "new" 5u32;"#,
+                PACKAGE
+            )),
+            vec![CheckErrorKind::InvalidNumberOfArguments {
+                expected: 2,
+                actual: 1
+            }]
+        );
+        assert_eq!(
+            check(&format!(
+                r#"CALL_METHOD ComponentAddress("{}") "withdraw";"#,
+                COMPONENT
+            )),
+            vec![CheckErrorKind::MethodNotFound("withdraw".to_owned())]
+        );
+        assert_eq!(
+            check(&format!(
+                r#"CALL_METHOD ComponentAddress("{}") "deposit_batch" Expression("ENTIRE_AUTH_ZONE");"#,
+                COMPONENT
+            )),
+            vec![CheckErrorKind::InvalidArgument {
+                index: 0,
+                expected_type: Type::Vec {
+                    element: Box::new(Type::Custom {
+                        name: "Bucket".to_owned(),
+                        generics: vec![],
+                    })
+                },
+                actual: "Expression(\"ENTIRE_AUTH_ZONE\")".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_native_calls_are_skipped() {
+        assert_eq!(
+            check(&format!(
+                r#"CALL_FUNCTION PackageAddress("{}") "Validator" "new";
+                CALL_METHOD ComponentAddress("{}") "next_epoch";"#,
+                VALIDATOR_PACKAGE, EPOCH_MANAGER
+            )),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_errors_point_at_values() {
+        assert_eq!(
+            check_with_spans(&format!(
+                r#"CALL_FUNCTION PackageAddress("{}") "Airdrop" "new" 5u32 Some(1u8);"#,
+                PACKAGE
+            ))
+            .into_iter()
+            .map(|e| e.span)
+            .collect::<Vec<Span>>(),
+            vec![Span {
+                start: (1, 109),
+                end: (1, 117)
+            }]
+        );
+    }
+}
@@ -262,9 +262,82 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                 ));
             }
             Instruction::PublishPackage { code } => {
+                buf.push_str(&format!("PUBLISH_PACKAGE Blob(\"{}\");\n", code));
+            }
+            Instruction::PublishPackageWithUpgradeRule { code, upgrade_rule } => {
+                buf.push_str(&format!(
+                    "PUBLISH_PACKAGE_WITH_UPGRADE_RULE Blob(\"{}\") {};\n",
+                    code,
+                    ScryptoValue::from_value(&upgrade_rule)
+                ));
+            }
+            Instruction::UpgradePackage {
+                package_address,
+                code,
+            } => {
+                buf.push_str(&format!(
+                    "UPGRADE_PACKAGE PackageAddress(\"{}\") Blob(\"{}\");\n",
+                    package_address, code
+                ));
+            }
+            Instruction::MigrateComponent {
+                component_address,
+                package_address,
+                function,
+            } => {
+                buf.push_str(&format!(
+                    "MIGRATE_COMPONENT ComponentAddress(\"{}\") PackageAddress(\"{}\") \"{}\";\n",
+                    component_address, package_address, function
+                ));
+            }
+            Instruction::SetPackageRoyaltyConfig {
+                package_address,
+                blueprint_name,
+                royalty_config,
+            } => {
+                buf.push_str(&format!(
+                    "SET_PACKAGE_ROYALTY_CONFIG PackageAddress(\"{}\") \"{}\" {};\n",
+                    package_address,
+                    blueprint_name,
+                    ScryptoValue::from_value(&royalty_config)
+                ));
+            }
+            Instruction::ClaimPackageRoyalty { package_address } => {
+                buf.push_str(&format!(
+                    "CLAIM_PACKAGE_ROYALTY PackageAddress(\"{}\");\n",
+                    package_address
+                ));
+            }
+            Instruction::SetPackageMetadata {
+                package_address,
+                metadata,
+            } => {
+                buf.push_str(&format!(
+                    "SET_PACKAGE_METADATA PackageAddress(\"{}\") {};\n",
+                    package_address,
+                    ScryptoValue::from_value(&metadata)
+                ));
+            }
+            Instruction::SetComponentMetadata {
+                component_address,
+                metadata,
+            } => {
+                buf.push_str(&format!(
+                    "SET_COMPONENT_METADATA ComponentAddress(\"{}\") {};\n",
+                    component_address,
+                    ScryptoValue::from_value(&metadata)
+                ));
+            }
+            Instruction::RecallVault {
+                component_address,
+                vault_id,
+                amount,
+            } => {
                 buf.push_str(&format!(
-                    "PUBLISH_PACKAGE Bytes(\"{}\");\n",
-                    hex::encode(&code)
+                    "RECALL_VAULT ComponentAddress(\"{}\") Vault(\"{}\") Decimal(\"{}\");\n",
+                    component_address,
+                    scrypto::resource::Vault(vault_id),
+                    amount
                 ));
             }
             Instruction::Nonce { .. } => {
@@ -283,11 +356,12 @@ mod tests {
 
     #[test]
     fn test_decompile() {
-        let tx = compile(include_str!("../examples/complex.rtm")).unwrap();
+        let code = include_bytes!("../examples/code.blob").to_vec();
+        let tx = compile(include_str!("../examples/complex.rtm"), vec![code.clone()]).unwrap();
 
         let manifest = &decompile(&tx).unwrap();
         println!("{}", manifest);
 
-        assert_eq!(compile(manifest).unwrap(), tx);
+        assert_eq!(compile(manifest, vec![code]).unwrap(), tx);
     }
 }
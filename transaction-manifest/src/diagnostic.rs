@@ -0,0 +1,260 @@
+use crate::checker::{CheckError, CheckErrorKind};
+use crate::generator::GeneratorError;
+use crate::lexer::{LexerError, Span};
+use crate::parser::ParserError;
+use crate::CompileError;
+
+/// Represents a human-readable error message, pointing at a location in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Span,
+    pub hint: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(message: String, span: Span, hint: Option<String>) -> Self {
+        Self {
+            message,
+            span,
+            hint,
+        }
+    }
+
+    /// Renders this diagnostic with the offending line of the manifest, like
+    ///
+    /// ```text
+    /// error: invalid number of values
+    ///  --> 1:48
+    ///   |
+    /// 1 | CALL_METHOD_WITH_ALL_RESOURCES ComponentAddress("02d4..", "02d4..") "deposit_batch";
+    ///   |                                                ^^^^^^^^^^^^^^^^^^^^
+    ///   = hint: expected 1 value(s), found 2
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let (line, column) = self.span.start;
+        let text = source.lines().nth(line - 1).unwrap_or("");
+        let end = if self.span.end.0 == line {
+            self.span.end.1
+        } else {
+            text.chars().count()
+        };
+        let gutter = " ".repeat(line.to_string().len());
+
+        // keep tabs, so that the caret lines up with the text
+        let padding: String = text
+            .chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let carets = "^".repeat((end + 1).saturating_sub(column).max(1));
+
+        let mut out = format!("error: {}\n", self.message);
+        out.push_str(&format!("{} --> {}:{}\n", gutter, line, column));
+        out.push_str(&format!("{} |\n", gutter));
+        out.push_str(&format!("{} | {}\n", line, text));
+        out.push_str(&format!("{} | {}{}\n", gutter, padding, carets));
+        if let Some(hint) = &self.hint {
+            out.push_str(&format!("{} = hint: {}\n", gutter, hint));
+        }
+        out
+    }
+}
+
+/// Converts a compile error into diagnostics.
+pub fn diagnose(source: &str, error: &CompileError) -> Vec<Diagnostic> {
+    match error {
+        CompileError::LexerError(e) => vec![diagnose_lexer_error(source, e)],
+        CompileError::ParserError(e) => vec![diagnose_parser_error(source, e)],
+        CompileError::GeneratorError(e, span) => vec![diagnose_generator_error(e, span)],
+        CompileError::CheckerError(errors) => errors.iter().map(diagnose_check_error).collect(),
+    }
+}
+
+/// Renders a compile error, with the offending lines of the manifest.
+pub fn render_error(source: &str, error: &CompileError) -> String {
+    diagnose(source, error)
+        .iter()
+        .map(|d| d.render(source))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Returns the span right after the last char of the manifest.
+fn end_of_file(source: &str) -> Span {
+    let line = source.split('\n').count();
+    let column = source.split('\n').last().unwrap_or("").chars().count() + 1;
+    Span {
+        start: (line, column),
+        end: (line, column),
+    }
+}
+
+fn diagnose_lexer_error(source: &str, error: &LexerError) -> Diagnostic {
+    match error {
+        LexerError::UnexpectedEof => Diagnostic::new(
+            "unexpected end of file".to_owned(),
+            end_of_file(source),
+            None,
+        ),
+        LexerError::UnexpectedChar(c, span) => {
+            Diagnostic::new(format!("unexpected character {:?}", c), span.clone(), None)
+        }
+        LexerError::InvalidNumber(n, span) => Diagnostic::new(
+            format!("invalid number `{}`", n),
+            span.clone(),
+            Some("the number is out of the range of its type".to_owned()),
+        ),
+        LexerError::InvalidUnicode(u, span) => Diagnostic::new(
+            format!("invalid unicode code point {:#x}", u),
+            span.clone(),
+            None,
+        ),
+        LexerError::UnknownIdentifier(id, span) => {
+            Diagnostic::new(format!("unknown identifier `{}`", id), span.clone(), None)
+        }
+    }
+}
+
+fn diagnose_parser_error(source: &str, error: &ParserError) -> Diagnostic {
+    match error {
+        ParserError::UnexpectedEof => Diagnostic::new(
+            "unexpected end of file".to_owned(),
+            end_of_file(source),
+            Some("is a `;` missing?".to_owned()),
+        ),
+        ParserError::UnexpectedToken(token) => Diagnostic::new(
+            format!("unexpected token {:?}", token.kind),
+            token.span.clone(),
+            None,
+        ),
+        ParserError::InvalidNumberOfValues {
+            actual,
+            expected,
+            span,
+        } => Diagnostic::new(
+            "invalid number of values".to_owned(),
+            span.clone(),
+            Some(format!("expected {} value(s), found {}", expected, actual)),
+        ),
+        ParserError::InvalidNumberOfTypes {
+            actual,
+            expected,
+            span,
+        } => Diagnostic::new(
+            "invalid number of types".to_owned(),
+            span.clone(),
+            Some(format!("expected {} type(s), found {}", expected, actual)),
+        ),
+        ParserError::InvalidHex(s, span) => {
+            Diagnostic::new(format!("invalid hex string {:?}", s), span.clone(), None)
+        }
+        ParserError::MissingEnumName(span) => Diagnostic::new(
+            "missing enum variant name".to_owned(),
+            span.clone(),
+            Some("the first value of an `Enum` must be the variant name".to_owned()),
+        ),
+    }
+}
+
+fn diagnose_generator_error(error: &GeneratorError, span: &Span) -> Diagnostic {
+    match error {
+        GeneratorError::InvalidType {
+            expected_type,
+            actual,
+        } => Diagnostic::new(
+            "invalid type".to_owned(),
+            span.clone(),
+            Some(format!("expected {:?}, found {:?}", expected_type, actual)),
+        ),
+        GeneratorError::InvalidValue {
+            expected_type,
+            actual,
+        } => Diagnostic::new(
+            "invalid value".to_owned(),
+            span.clone(),
+            Some(format!(
+                "expected {}, found {:?}",
+                expected_type
+                    .iter()
+                    .map(|t| format!("{:?}", t))
+                    .collect::<Vec<String>>()
+                    .join(" or "),
+                actual.kind()
+            )),
+        ),
+        e => Diagnostic::new(format!("{:?}", e), span.clone(), None),
+    }
+}
+
+fn diagnose_check_error(error: &CheckError) -> Diagnostic {
+    match &error.kind {
+        CheckErrorKind::InvalidNumberOfArguments { expected, actual } => Diagnostic::new(
+            "invalid number of arguments".to_owned(),
+            error.span.clone(),
+            Some(format!(
+                "expected {} argument(s), found {}",
+                expected, actual
+            )),
+        ),
+        CheckErrorKind::InvalidArgument {
+            index,
+            expected_type,
+            actual,
+        } => Diagnostic::new(
+            format!("invalid argument #{}", index),
+            error.span.clone(),
+            Some(format!("expected {:?}, found {}", expected_type, actual)),
+        ),
+        e => Diagnostic::new(format!("{:?}", e), error.span.clone(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compile;
+
+    #[test]
+    fn test_render_lexer_error() {
+        let s = "CLEAR_AUTH_ZONE;\nCLEAR_WORKTOP;";
+        assert_eq!(
+            render_error(s, &compile(s, vec![]).unwrap_err()),
+            "error: unknown identifier `CLEAR_WORKTOP`\n  \
+             --> 2:1\n  \
+             |\n\
+             2 | CLEAR_WORKTOP;\n  \
+             | ^^^^^^^^^^^^^\n"
+        );
+    }
+
+    #[test]
+    fn test_render_generator_error() {
+        let s = r#"TAKE_FROM_WORKTOP_BY_AMOUNT "1.0" ResourceAddress("030000000000000000000000000000000000000000000000000004") Bucket("xrd");"#;
+        assert_eq!(
+            diagnose(s, &compile(s, vec![]).unwrap_err()),
+            vec![Diagnostic::new(
+                "invalid value".to_owned(),
+                Span {
+                    start: (1, 29),
+                    end: (1, 33)
+                },
+                Some("expected Decimal, found String".to_owned())
+            )]
+        );
+    }
+
+    #[test]
+    fn test_render_parser_error() {
+        let s = "CLEAR_AUTH_ZONE";
+        assert_eq!(
+            render_error(s, &compile(s, vec![]).unwrap_err()),
+            "error: unexpected end of file\n  \
+             --> 1:16\n  \
+             |\n\
+             1 | CLEAR_AUTH_ZONE\n  \
+             |                ^\n  \
+             = hint: is a `;` missing?\n"
+        );
+    }
+}
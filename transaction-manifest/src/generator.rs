@@ -1,10 +1,16 @@
 use crate::ast;
+use crate::lexer::Span;
 use radix_engine::engine::*;
 use radix_engine::model::*;
 use sbor::any::{encode_any, Value};
 use sbor::type_id::*;
 use sbor::Encoder;
+use scrypto::buffer::scrypto_decode;
+use scrypto::component::RoyaltyConfig;
+use scrypto::core::Expression;
+use scrypto::crypto::hash;
 use scrypto::engine::types::*;
+use scrypto::resource::AccessRule;
 use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::collections::HashMap;
 use scrypto::rust::str::FromStr;
@@ -28,8 +34,14 @@ pub enum GeneratorError {
     InvalidHash(String),
     InvalidLazyMapId(String),
     InvalidVaultId(String),
+    InvalidExpression(String),
+    InvalidBlob(String),
+    BlobNotFound(Hash),
     InvalidNonFungibleId(String),
     InvalidNonFungibleAddress(String),
+    InvalidAccessRule(ast::Value),
+    InvalidRoyaltyConfig(ast::Value),
+    InvalidMetadata(ast::Value),
     OddNumberOfElements(usize),
     NameResolverError(NameResolverError),
     IdValidatorError(IdValidatorError),
@@ -96,7 +108,38 @@ impl NameResolver {
     }
 }
 
-pub fn generate_transaction(tx: &ast::Transaction) -> Result<Transaction, GeneratorError> {
+pub fn generate_transaction(
+    tx: &ast::Transaction,
+    blobs: Vec<Vec<u8>>,
+) -> Result<Transaction, (GeneratorError, Span)> {
+    let blob_hashes: Vec<Hash> = blobs.iter().map(hash).collect();
+    let instructions = generate_instructions(tx, Some(&blob_hashes))?;
+
+    Ok(Transaction {
+        header: TransactionHeader::default(),
+        instructions,
+        blobs,
+    })
+}
+
+/// Generates a transaction whose blobs are not at hand, such as one being edited, trusting that
+/// every blob it refers to will be attached.
+pub fn generate_transaction_without_blobs(
+    tx: &ast::Transaction,
+) -> Result<Transaction, (GeneratorError, Span)> {
+    let instructions = generate_instructions(tx, None)?;
+
+    Ok(Transaction {
+        header: TransactionHeader::default(),
+        instructions,
+        blobs: Vec::new(),
+    })
+}
+
+fn generate_instructions(
+    tx: &ast::Transaction,
+    blob_hashes: Option<&[Hash]>,
+) -> Result<Vec<Instruction>, (GeneratorError, Span)> {
     let mut id_validator = IdValidator::new();
     let mut name_resolver = NameResolver::new();
     let mut instructions = Vec::new();
@@ -106,17 +149,19 @@ pub fn generate_transaction(tx: &ast::Transaction) -> Result<Transaction, Genera
             instruction,
             &mut id_validator,
             &mut name_resolver,
+            blob_hashes,
         )?);
     }
 
-    Ok(Transaction { instructions })
+    Ok(instructions)
 }
 
 pub fn generate_instruction(
     instruction: &ast::Instruction,
     id_validator: &mut IdValidator,
     resolver: &mut NameResolver,
-) -> Result<Instruction, GeneratorError> {
+    blob_hashes: Option<&[Hash]>,
+) -> Result<Instruction, (GeneratorError, Span)> {
     Ok(match instruction {
         ast::Instruction::TakeFromWorktop {
             resource_address,
@@ -124,11 +169,13 @@ pub fn generate_instruction(
         } => {
             let bucket_id = id_validator
                 .new_bucket()
-                .map_err(GeneratorError::IdValidatorError)?;
-            declare_bucket(new_bucket, resolver, bucket_id)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(new_bucket))?;
+            declare_bucket(&new_bucket.value, resolver, bucket_id).map_err(at(new_bucket))?;
 
             Instruction::TakeFromWorktop {
-                resource_address: generate_resource_address(resource_address)?,
+                resource_address: generate_resource_address(&resource_address.value)
+                    .map_err(at(resource_address))?,
             }
         }
         ast::Instruction::TakeFromWorktopByAmount {
@@ -138,12 +185,14 @@ pub fn generate_instruction(
         } => {
             let bucket_id = id_validator
                 .new_bucket()
-                .map_err(GeneratorError::IdValidatorError)?;
-            declare_bucket(new_bucket, resolver, bucket_id)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(new_bucket))?;
+            declare_bucket(&new_bucket.value, resolver, bucket_id).map_err(at(new_bucket))?;
 
             Instruction::TakeFromWorktopByAmount {
-                amount: generate_decimal(amount)?,
-                resource_address: generate_resource_address(resource_address)?,
+                amount: generate_decimal(&amount.value).map_err(at(amount))?,
+                resource_address: generate_resource_address(&resource_address.value)
+                    .map_err(at(resource_address))?,
             }
         }
         ast::Instruction::TakeFromWorktopByIds {
@@ -153,53 +202,61 @@ pub fn generate_instruction(
         } => {
             let bucket_id = id_validator
                 .new_bucket()
-                .map_err(GeneratorError::IdValidatorError)?;
-            declare_bucket(new_bucket, resolver, bucket_id)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(new_bucket))?;
+            declare_bucket(&new_bucket.value, resolver, bucket_id).map_err(at(new_bucket))?;
 
             Instruction::TakeFromWorktopByIds {
-                ids: generate_non_fungible_ids(ids)?,
-                resource_address: generate_resource_address(resource_address)?,
+                ids: generate_non_fungible_ids(&ids.value).map_err(at(ids))?,
+                resource_address: generate_resource_address(&resource_address.value)
+                    .map_err(at(resource_address))?,
             }
         }
         ast::Instruction::ReturnToWorktop { bucket } => {
-            let bucket_id = generate_bucket(bucket, resolver)?;
+            let bucket_id = generate_bucket(&bucket.value, resolver).map_err(at(bucket))?;
             id_validator
                 .drop_bucket(bucket_id)
-                .map_err(GeneratorError::IdValidatorError)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(bucket))?;
             Instruction::ReturnToWorktop { bucket_id }
         }
         ast::Instruction::AssertWorktopContains { resource_address } => {
             Instruction::AssertWorktopContains {
-                resource_address: generate_resource_address(resource_address)?,
+                resource_address: generate_resource_address(&resource_address.value)
+                    .map_err(at(resource_address))?,
             }
         }
         ast::Instruction::AssertWorktopContainsByAmount {
             amount,
             resource_address,
         } => Instruction::AssertWorktopContainsByAmount {
-            amount: generate_decimal(amount)?,
-            resource_address: generate_resource_address(resource_address)?,
+            amount: generate_decimal(&amount.value).map_err(at(amount))?,
+            resource_address: generate_resource_address(&resource_address.value)
+                .map_err(at(resource_address))?,
         },
         ast::Instruction::AssertWorktopContainsByIds {
             ids,
             resource_address,
         } => Instruction::AssertWorktopContainsByIds {
-            ids: generate_non_fungible_ids(ids)?,
-            resource_address: generate_resource_address(resource_address)?,
+            ids: generate_non_fungible_ids(&ids.value).map_err(at(ids))?,
+            resource_address: generate_resource_address(&resource_address.value)
+                .map_err(at(resource_address))?,
         },
         ast::Instruction::PopFromAuthZone { new_proof } => {
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
-                .map_err(GeneratorError::IdValidatorError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(new_proof))?;
+            declare_proof(&new_proof.value, resolver, proof_id).map_err(at(new_proof))?;
 
             Instruction::PopFromAuthZone
         }
         ast::Instruction::PushToAuthZone { proof } => {
-            let proof_id = generate_proof(proof, resolver)?;
+            let proof_id = generate_proof(&proof.value, resolver).map_err(at(proof))?;
             id_validator
                 .drop_proof(proof_id)
-                .map_err(GeneratorError::IdValidatorError)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(proof))?;
             Instruction::PushToAuthZone { proof_id }
         }
         ast::Instruction::ClearAuthZone => Instruction::ClearAuthZone,
@@ -208,11 +265,13 @@ pub fn generate_instruction(
             resource_address,
             new_proof,
         } => {
-            let resource_address = generate_resource_address(resource_address)?;
+            let resource_address =
+                generate_resource_address(&resource_address.value).map_err(at(resource_address))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
-                .map_err(GeneratorError::IdValidatorError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(new_proof))?;
+            declare_proof(&new_proof.value, resolver, proof_id).map_err(at(new_proof))?;
 
             Instruction::CreateProofFromAuthZone { resource_address }
         }
@@ -221,12 +280,14 @@ pub fn generate_instruction(
             resource_address,
             new_proof,
         } => {
-            let amount = generate_decimal(amount)?;
-            let resource_address = generate_resource_address(resource_address)?;
+            let amount = generate_decimal(&amount.value).map_err(at(amount))?;
+            let resource_address =
+                generate_resource_address(&resource_address.value).map_err(at(resource_address))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
-                .map_err(GeneratorError::IdValidatorError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(new_proof))?;
+            declare_proof(&new_proof.value, resolver, proof_id).map_err(at(new_proof))?;
 
             Instruction::CreateProofFromAuthZoneByAmount {
                 amount,
@@ -238,12 +299,14 @@ pub fn generate_instruction(
             resource_address,
             new_proof,
         } => {
-            let ids = generate_non_fungible_ids(ids)?;
-            let resource_address = generate_resource_address(resource_address)?;
+            let ids = generate_non_fungible_ids(&ids.value).map_err(at(ids))?;
+            let resource_address =
+                generate_resource_address(&resource_address.value).map_err(at(resource_address))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
-                .map_err(GeneratorError::IdValidatorError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(new_proof))?;
+            declare_proof(&new_proof.value, resolver, proof_id).map_err(at(new_proof))?;
 
             Instruction::CreateProofFromAuthZoneByIds {
                 ids,
@@ -251,28 +314,31 @@ pub fn generate_instruction(
             }
         }
         ast::Instruction::CreateProofFromBucket { bucket, new_proof } => {
-            let bucket_id = generate_bucket(bucket, resolver)?;
+            let bucket_id = generate_bucket(&bucket.value, resolver).map_err(at(bucket))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::BucketProof(bucket_id))
-                .map_err(GeneratorError::IdValidatorError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(bucket))?;
+            declare_proof(&new_proof.value, resolver, proof_id).map_err(at(new_proof))?;
 
             Instruction::CreateProofFromBucket { bucket_id }
         }
         ast::Instruction::CloneProof { proof, new_proof } => {
-            let proof_id = generate_proof(proof, resolver)?;
+            let proof_id = generate_proof(&proof.value, resolver).map_err(at(proof))?;
             let proof_id2 = id_validator
                 .clone_proof(proof_id)
-                .map_err(GeneratorError::IdValidatorError)?;
-            declare_proof(new_proof, resolver, proof_id2)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(proof))?;
+            declare_proof(&new_proof.value, resolver, proof_id2).map_err(at(new_proof))?;
 
             Instruction::CloneProof { proof_id }
         }
         ast::Instruction::DropProof { proof } => {
-            let proof_id = generate_proof(proof, resolver)?;
+            let proof_id = generate_proof(&proof.value, resolver).map_err(at(proof))?;
             id_validator
                 .drop_proof(proof_id)
-                .map_err(GeneratorError::IdValidatorError)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(proof))?;
             Instruction::DropProof { proof_id }
         }
         ast::Instruction::CallFunction {
@@ -281,18 +347,21 @@ pub fn generate_instruction(
             function,
             args,
         } => {
-            let args = generate_args(args, resolver)?;
-            for arg in &args {
-                let validated_arg = ScryptoValue::from_slice(arg).unwrap();
+            let generated_args = generate_args(args, resolver, blob_hashes)?;
+            for (arg, generated_arg) in args.iter().zip(&generated_args) {
+                let validated_arg = ScryptoValue::from_slice(generated_arg).unwrap();
                 id_validator
                     .move_resources(&validated_arg)
-                    .map_err(GeneratorError::IdValidatorError)?;
+                    .map_err(GeneratorError::IdValidatorError)
+                    .map_err(at(arg))?;
             }
             Instruction::CallFunction {
-                package_address: generate_package_address(package_address)?,
-                blueprint_name: generate_string(blueprint_name)?,
-                function: generate_string(function)?,
-                args,
+                package_address: generate_package_address(&package_address.value)
+                    .map_err(at(package_address))?,
+                blueprint_name: generate_string(&blueprint_name.value)
+                    .map_err(at(blueprint_name))?,
+                function: generate_string(&function.value).map_err(at(function))?,
+                args: generated_args,
             }
         }
         ast::Instruction::CallMethod {
@@ -300,17 +369,19 @@ pub fn generate_instruction(
             method,
             args,
         } => {
-            let args = generate_args(args, resolver)?;
-            for arg in &args {
-                let validated_arg = ScryptoValue::from_slice(arg).unwrap();
+            let generated_args = generate_args(args, resolver, blob_hashes)?;
+            for (arg, generated_arg) in args.iter().zip(&generated_args) {
+                let validated_arg = ScryptoValue::from_slice(generated_arg).unwrap();
                 id_validator
                     .move_resources(&validated_arg)
-                    .map_err(GeneratorError::IdValidatorError)?;
+                    .map_err(GeneratorError::IdValidatorError)
+                    .map_err(at(arg))?;
             }
             Instruction::CallMethod {
-                component_address: generate_component_address(component_address)?,
-                method: generate_string(method)?,
-                args,
+                component_address: generate_component_address(&component_address.value)
+                    .map_err(at(component_address))?,
+                method: generate_string(&method.value).map_err(at(method))?,
+                args: generated_args,
             }
         }
         ast::Instruction::CallMethodWithAllResources {
@@ -319,18 +390,96 @@ pub fn generate_instruction(
         } => {
             id_validator
                 .move_all_resources()
-                .map_err(GeneratorError::IdValidatorError)?;
+                .map_err(GeneratorError::IdValidatorError)
+                .map_err(at(component_address))?;
             Instruction::CallMethodWithAllResources {
-                component_address: generate_component_address(component_address)?,
-                method: generate_string(method)?,
+                component_address: generate_component_address(&component_address.value)
+                    .map_err(at(component_address))?,
+                method: generate_string(&method.value).map_err(at(method))?,
             }
         }
         ast::Instruction::PublishPackage { code } => Instruction::PublishPackage {
-            code: generate_bytes(code)?,
+            code: generate_blob(&code.value, blob_hashes).map_err(at(code))?,
+        },
+        ast::Instruction::PublishPackageWithUpgradeRule { code, upgrade_rule } => {
+            Instruction::PublishPackageWithUpgradeRule {
+                code: generate_blob(&code.value, blob_hashes).map_err(at(code))?,
+                upgrade_rule: generate_access_rule(&upgrade_rule.value, resolver, blob_hashes)
+                    .map_err(at(upgrade_rule))?,
+            }
+        }
+        ast::Instruction::UpgradePackage {
+            package_address,
+            code,
+        } => Instruction::UpgradePackage {
+            package_address: generate_package_address(&package_address.value)
+                .map_err(at(package_address))?,
+            code: generate_blob(&code.value, blob_hashes).map_err(at(code))?,
+        },
+        ast::Instruction::MigrateComponent {
+            component_address,
+            package_address,
+            function,
+        } => Instruction::MigrateComponent {
+            component_address: generate_component_address(&component_address.value)
+                .map_err(at(component_address))?,
+            package_address: generate_package_address(&package_address.value)
+                .map_err(at(package_address))?,
+            function: generate_string(&function.value).map_err(at(function))?,
+        },
+        ast::Instruction::SetPackageRoyaltyConfig {
+            package_address,
+            blueprint_name,
+            royalty_config,
+        } => Instruction::SetPackageRoyaltyConfig {
+            package_address: generate_package_address(&package_address.value)
+                .map_err(at(package_address))?,
+            blueprint_name: generate_string(&blueprint_name.value).map_err(at(blueprint_name))?,
+            royalty_config: generate_royalty_config(&royalty_config.value, resolver, blob_hashes)
+                .map_err(at(royalty_config))?,
+        },
+        ast::Instruction::ClaimPackageRoyalty { package_address } => {
+            Instruction::ClaimPackageRoyalty {
+                package_address: generate_package_address(&package_address.value)
+                    .map_err(at(package_address))?,
+            }
+        }
+        ast::Instruction::SetPackageMetadata {
+            package_address,
+            metadata,
+        } => Instruction::SetPackageMetadata {
+            package_address: generate_package_address(&package_address.value)
+                .map_err(at(package_address))?,
+            metadata: generate_metadata(&metadata.value, resolver, blob_hashes)
+                .map_err(at(metadata))?,
+        },
+        ast::Instruction::SetComponentMetadata {
+            component_address,
+            metadata,
+        } => Instruction::SetComponentMetadata {
+            component_address: generate_component_address(&component_address.value)
+                .map_err(at(component_address))?,
+            metadata: generate_metadata(&metadata.value, resolver, blob_hashes)
+                .map_err(at(metadata))?,
+        },
+        ast::Instruction::RecallVault {
+            component_address,
+            vault,
+            amount,
+        } => Instruction::RecallVault {
+            component_address: generate_component_address(&component_address.value)
+                .map_err(at(component_address))?,
+            vault_id: generate_vault_id(&vault.value).map_err(at(vault))?,
+            amount: generate_decimal(&amount.value).map_err(at(amount))?,
         },
     })
 }
 
+/// Returns a function which points an error at the given value.
+fn at(value: &ast::ValueWithSpan) -> impl Fn(GeneratorError) -> (GeneratorError, Span) + '_ {
+    move |error| (error, value.span.clone())
+}
+
 #[macro_export]
 macro_rules! invalid_type {
     ( $v:expr, $($exp:expr),+ ) => {
@@ -342,12 +491,13 @@ macro_rules! invalid_type {
 }
 
 fn generate_args(
-    values: &Vec<ast::Value>,
+    values: &Vec<ast::ValueWithSpan>,
     resolver: &mut NameResolver,
-) -> Result<Vec<Vec<u8>>, GeneratorError> {
+    blob_hashes: Option<&[Hash]>,
+) -> Result<Vec<Vec<u8>>, (GeneratorError, Span)> {
     let mut result = Vec::new();
     for v in values {
-        let value = generate_value(v, None, resolver)?;
+        let value = generate_value(&v.value, None, resolver, blob_hashes).map_err(at(v))?;
 
         let mut bytes = Vec::new();
         let mut enc = Encoder::with_type(&mut bytes);
@@ -357,6 +507,46 @@ fn generate_args(
     Ok(result)
 }
 
+fn generate_access_rule(
+    value: &ast::Value,
+    resolver: &mut NameResolver,
+    blob_hashes: Option<&[Hash]>,
+) -> Result<AccessRule, GeneratorError> {
+    let bytes = generate_encoded_value(value, ast::Type::Enum, resolver, blob_hashes)?;
+    scrypto_decode(&bytes).map_err(|_| GeneratorError::InvalidAccessRule(value.clone()))
+}
+
+fn generate_royalty_config(
+    value: &ast::Value,
+    resolver: &mut NameResolver,
+    blob_hashes: Option<&[Hash]>,
+) -> Result<RoyaltyConfig, GeneratorError> {
+    let bytes = generate_encoded_value(value, ast::Type::Struct, resolver, blob_hashes)?;
+    scrypto_decode(&bytes).map_err(|_| GeneratorError::InvalidRoyaltyConfig(value.clone()))
+}
+
+fn generate_metadata(
+    value: &ast::Value,
+    resolver: &mut NameResolver,
+    blob_hashes: Option<&[Hash]>,
+) -> Result<HashMap<String, String>, GeneratorError> {
+    let bytes = generate_encoded_value(value, ast::Type::HashMap, resolver, blob_hashes)?;
+    scrypto_decode(&bytes).map_err(|_| GeneratorError::InvalidMetadata(value.clone()))
+}
+
+fn generate_encoded_value(
+    value: &ast::Value,
+    expected: ast::Type,
+    resolver: &mut NameResolver,
+    blob_hashes: Option<&[Hash]>,
+) -> Result<Vec<u8>, GeneratorError> {
+    let generated = generate_value(value, Some(expected), resolver, blob_hashes)?;
+    let mut bytes = Vec::new();
+    let mut enc = Encoder::with_type(&mut bytes);
+    encode_any(None, &generated, &mut enc);
+    Ok(bytes)
+}
+
 fn generate_string(value: &ast::Value) -> Result<String, GeneratorError> {
     match value {
         ast::Value::String(s) => Ok(s.into()),
@@ -364,37 +554,6 @@ fn generate_string(value: &ast::Value) -> Result<String, GeneratorError> {
     }
 }
 
-fn generate_bytes(value: &ast::Value) -> Result<Vec<u8>, GeneratorError> {
-    match value {
-        ast::Value::Bytes(bytes) => Ok(bytes.clone()),
-        ast::Value::Vec(ty, values) => {
-            if ty == &ast::Type::U8 {
-                let mut result = Vec::new();
-                for v in values {
-                    match v {
-                        ast::Value::U8(num) => {
-                            result.push(*num);
-                        }
-                        _ => {
-                            return Err(GeneratorError::InvalidValue {
-                                expected_type: vec![ast::Type::U8],
-                                actual: v.clone(),
-                            })
-                        }
-                    }
-                }
-                Ok(result)
-            } else {
-                Err(GeneratorError::InvalidType {
-                    expected_type: ast::Type::U8,
-                    actual: *ty,
-                })
-            }
-        }
-        v @ _ => invalid_type!(v, ast::Type::Vec, ast::Type::Bytes),
-    }
-}
-
 fn generate_decimal(value: &ast::Value) -> Result<Decimal, GeneratorError> {
     match value {
         ast::Value::Decimal(inner) => match &**inner {
@@ -452,6 +611,52 @@ fn generate_hash(value: &ast::Value) -> Result<Hash, GeneratorError> {
     }
 }
 
+fn generate_vault_id(value: &ast::Value) -> Result<VaultId, GeneratorError> {
+    match value {
+        ast::Value::Vault(inner) => match &**inner {
+            ast::Value::String(s) => scrypto::resource::Vault::from_str(s)
+                .map(|vault| vault.0)
+                .map_err(|_| GeneratorError::InvalidVaultId(s.into())),
+            v @ _ => invalid_type!(v, ast::Type::String),
+        },
+        v @ _ => invalid_type!(v, ast::Type::Vault),
+    }
+}
+
+fn generate_expression(value: &ast::Value) -> Result<Expression, GeneratorError> {
+    match value {
+        ast::Value::Expression(inner) => match &**inner {
+            ast::Value::String(s) => {
+                Expression::from_str(s).map_err(|_| GeneratorError::InvalidExpression(s.into()))
+            }
+            v @ _ => invalid_type!(v, ast::Type::String),
+        },
+        v @ _ => invalid_type!(v, ast::Type::Expression),
+    }
+}
+
+fn generate_blob_hash(value: &ast::Value) -> Result<Hash, GeneratorError> {
+    match value {
+        ast::Value::Blob(inner) => match &**inner {
+            ast::Value::String(s) => {
+                Hash::from_str(s).map_err(|_| GeneratorError::InvalidBlob(s.into()))
+            }
+            v @ _ => invalid_type!(v, ast::Type::String),
+        },
+        v @ _ => invalid_type!(v, ast::Type::Blob),
+    }
+}
+
+fn generate_blob(value: &ast::Value, blob_hashes: Option<&[Hash]>) -> Result<Hash, GeneratorError> {
+    let blob_hash = generate_blob_hash(value)?;
+    match blob_hashes {
+        Some(blob_hashes) if !blob_hashes.contains(&blob_hash) => {
+            Err(GeneratorError::BlobNotFound(blob_hash))
+        }
+        _ => Ok(blob_hash),
+    }
+}
+
 fn declare_bucket(
     value: &ast::Value,
     resolver: &mut NameResolver,
@@ -560,6 +765,7 @@ fn generate_value(
     value: &ast::Value,
     expected: Option<ast::Type>,
     resolver: &mut NameResolver,
+    blob_hashes: Option<&[Hash]>,
 ) -> Result<Value, GeneratorError> {
     if let Some(ty) = expected {
         if ty != value.kind() {
@@ -587,54 +793,54 @@ fn generate_value(
             value: value.clone(),
         }),
         ast::Value::Struct(fields) => Ok(Value::Struct {
-            fields: generate_singletons(fields, None, resolver)?,
+            fields: generate_singletons(fields, None, resolver, blob_hashes)?,
         }),
         ast::Value::Enum(name, fields) => Ok(Value::Enum {
             name: name.clone(),
-            fields: generate_singletons(fields, None, resolver)?,
+            fields: generate_singletons(fields, None, resolver, blob_hashes)?,
         }),
         ast::Value::Option(value) => match &**value {
             Some(inner) => Ok(Value::Option {
-                value: Some(generate_value(inner, None, resolver)?).into(),
+                value: Some(generate_value(inner, None, resolver, blob_hashes)?).into(),
             }),
             None => Ok(Value::Option { value: None.into() }),
         },
         ast::Value::Array(element_type, elements) => Ok(Value::Array {
             element_type_id: generate_type_id(element_type),
-            elements: generate_singletons(elements, Some(*element_type), resolver)?,
+            elements: generate_singletons(elements, Some(*element_type), resolver, blob_hashes)?,
         }),
         ast::Value::Tuple(elements) => Ok(Value::Tuple {
-            elements: generate_singletons(elements, None, resolver)?,
+            elements: generate_singletons(elements, None, resolver, blob_hashes)?,
         }),
         ast::Value::Result(value) => match &**value {
             Ok(inner) => Ok(Value::Result {
-                value: Ok(generate_value(inner, None, resolver)?).into(),
+                value: Ok(generate_value(inner, None, resolver, blob_hashes)?).into(),
             }),
             Err(inner) => Ok(Value::Result {
-                value: Err(generate_value(inner, None, resolver)?).into(),
+                value: Err(generate_value(inner, None, resolver, blob_hashes)?).into(),
             }),
         },
         ast::Value::Vec(element_type, elements) => Ok(Value::Vec {
             element_type_id: generate_type_id(element_type),
-            elements: generate_singletons(elements, Some(*element_type), resolver)?,
+            elements: generate_singletons(elements, Some(*element_type), resolver, blob_hashes)?,
         }),
         ast::Value::TreeSet(element_type, elements) => Ok(Value::TreeSet {
             element_type_id: generate_type_id(element_type),
-            elements: generate_singletons(elements, Some(*element_type), resolver)?,
+            elements: generate_singletons(elements, Some(*element_type), resolver, blob_hashes)?,
         }),
         ast::Value::TreeMap(key_type, value_type, elements) => Ok(Value::TreeMap {
             key_type_id: generate_type_id(key_type),
             value_type_id: generate_type_id(value_type),
-            elements: generate_pairs(elements, *key_type, *value_type, resolver)?,
+            elements: generate_pairs(elements, *key_type, *value_type, resolver, blob_hashes)?,
         }),
         ast::Value::HashSet(element_type, elements) => Ok(Value::HashSet {
             element_type_id: generate_type_id(element_type),
-            elements: generate_singletons(elements, Some(*element_type), resolver)?,
+            elements: generate_singletons(elements, Some(*element_type), resolver, blob_hashes)?,
         }),
         ast::Value::HashMap(key_type, value_type, elements) => Ok(Value::HashMap {
             key_type_id: generate_type_id(key_type),
             value_type_id: generate_type_id(value_type),
-            elements: generate_pairs(elements, *key_type, *value_type, resolver)?,
+            elements: generate_pairs(elements, *key_type, *value_type, resolver, blob_hashes)?,
         }),
         ast::Value::Decimal(_) => generate_decimal(value).map(|v| Value::Custom {
             type_id: ScryptoType::Decimal.id(),
@@ -676,6 +882,18 @@ fn generate_value(
                 bytes: v.to_vec(),
             })
         }
+        ast::Value::Vault(_) => generate_vault_id(value).map(|v| Value::Custom {
+            type_id: ScryptoType::Vault.id(),
+            bytes: scrypto::resource::Vault(v).to_vec(),
+        }),
+        ast::Value::Expression(_) => generate_expression(value).map(|v| Value::Custom {
+            type_id: ScryptoType::Expression.id(),
+            bytes: v.to_vec(),
+        }),
+        ast::Value::Blob(_) => generate_blob(value, blob_hashes).map(|v| Value::Custom {
+            type_id: ScryptoType::Hash.id(),
+            bytes: v.to_vec(),
+        }),
         ast::Value::Bytes(_) => match value {
             ast::Value::Bytes(bytes) => {
                 let mut elements = Vec::new();
@@ -696,10 +914,11 @@ fn generate_singletons(
     elements: &Vec<ast::Value>,
     ty: Option<ast::Type>,
     resolver: &mut NameResolver,
+    blob_hashes: Option<&[Hash]>,
 ) -> Result<Vec<Value>, GeneratorError> {
     let mut result = vec![];
     for element in elements {
-        result.push(generate_value(element, ty, resolver)?);
+        result.push(generate_value(element, ty, resolver, blob_hashes)?);
     }
     Ok(result)
 }
@@ -709,17 +928,24 @@ fn generate_pairs(
     key_type: ast::Type,
     value_type: ast::Type,
     resolver: &mut NameResolver,
+    blob_hashes: Option<&[Hash]>,
 ) -> Result<Vec<Value>, GeneratorError> {
     if elements.len() % 2 != 0 {
         return Err(GeneratorError::OddNumberOfElements(elements.len()));
     }
     let mut result = vec![];
     for i in 0..elements.len() / 2 {
-        result.push(generate_value(&elements[2 * i], Some(key_type), resolver)?);
+        result.push(generate_value(
+            &elements[2 * i],
+            Some(key_type),
+            resolver,
+            blob_hashes,
+        )?);
         result.push(generate_value(
             &elements[2 * i + 1],
             Some(value_type),
             resolver,
+            blob_hashes,
         )?);
     }
     Ok(result)
@@ -760,6 +986,9 @@ fn generate_type_id(ty: &ast::Type) -> u8 {
         ast::Type::Proof => ScryptoType::Proof.id(),
         ast::Type::NonFungibleId => ScryptoType::NonFungibleId.id(),
         ast::Type::NonFungibleAddress => ScryptoType::NonFungibleAddress.id(),
+        ast::Type::Vault => ScryptoType::Vault.id(),
+        ast::Type::Expression => ScryptoType::Expression.id(),
+        ast::Type::Blob => ScryptoType::Hash.id(),
         ast::Type::Bytes => TYPE_VEC,
     }
 }
@@ -776,7 +1005,10 @@ mod tests {
         ( $s:expr, $expected:expr ) => {{
             let value = Parser::new(tokenize($s).unwrap()).parse_value().unwrap();
             let mut resolver = NameResolver::new();
-            assert_eq!(generate_value(&value, None, &mut resolver), Ok($expected));
+            assert_eq!(
+                generate_value(&value, None, &mut resolver, Some(&[])),
+                Ok($expected)
+            );
         }};
     }
 
@@ -789,7 +1021,7 @@ mod tests {
             let mut id_validator = IdValidator::new();
             let mut resolver = NameResolver::new();
             assert_eq!(
-                generate_instruction(&instruction, &mut id_validator, &mut resolver),
+                generate_instruction(&instruction, &mut id_validator, &mut resolver, Some(&[])),
                 Ok($expected)
             );
         }};
@@ -799,7 +1031,7 @@ mod tests {
     macro_rules! generate_value_error {
         ( $s:expr, $expected:expr ) => {{
             let value = Parser::new(tokenize($s).unwrap()).parse_value().unwrap();
-            match generate_value(&value, None, &mut NameResolver::new()) {
+            match generate_value(&value, None, &mut NameResolver::new(), Some(&[])) {
                 Ok(_) => {
                     panic!("Expected {:?} but no error is thrown", $expected);
                 }
@@ -837,6 +1069,22 @@ mod tests {
                 ]
             }
         );
+        generate_value_ok!(
+            r#"Array<NonFungibleId>(NonFungibleId("#42#"), NonFungibleId("<Ticket_1>"))"#,
+            Value::Array {
+                element_type_id: ScryptoType::NonFungibleId.id(),
+                elements: vec![
+                    Value::Custom {
+                        type_id: ScryptoType::NonFungibleId.id(),
+                        bytes: NonFungibleId::from_u64(42).to_vec()
+                    },
+                    Value::Custom {
+                        type_id: ScryptoType::NonFungibleId.id(),
+                        bytes: NonFungibleId::String("Ticket_1".into()).to_vec()
+                    },
+                ]
+            }
+        );
         generate_value_ok!(
             r#"Struct(Decimal("1.0"), Hash("aa37f5a71083a9aa044fb936678bfd74f848e930d2de482a49a73540ea72aa5c"))"#,
             Value::Struct {
@@ -932,6 +1180,13 @@ mod tests {
                 ]
             }
         );
+        generate_value_ok!(
+            r#"Expression("ENTIRE_WORKTOP")"#,
+            Value::Custom {
+                type_id: ScryptoType::Expression.id(),
+                bytes: Expression::EntireWorktop.to_vec(),
+            }
+        );
     }
 
     #[test]
@@ -955,6 +1210,18 @@ mod tests {
             r#"HashMap<String, String>("abc")"#,
             GeneratorError::OddNumberOfElements(1)
         );
+        generate_value_error!(
+            r#"NonFungibleId("42")"#,
+            GeneratorError::InvalidNonFungibleId("42".into())
+        );
+        generate_value_error!(
+            r#"NonFungibleId("<Ticket 1>")"#,
+            GeneratorError::InvalidNonFungibleId("<Ticket 1>".into())
+        );
+        generate_value_error!(
+            r#"Expression("ENTIRE_LEDGER")"#,
+            GeneratorError::InvalidExpression("ENTIRE_LEDGER".into())
+        );
     }
 
     #[test]
@@ -1024,31 +1291,107 @@ mod tests {
                 method: "deposit_batch".into(),
             }
         );
+        generate_instruction_ok!(
+            r#"SET_COMPONENT_METADATA  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  HashMap<String, String>("name", "Radiswap");"#,
+            Instruction::SetComponentMetadata {
+                component_address: ComponentAddress::from_str(
+                    "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                )
+                .unwrap(),
+                metadata: HashMap::from([("name".to_owned(), "Radiswap".to_owned())]),
+            }
+        );
+        generate_instruction_ok!(
+            r#"RECALL_VAULT  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  Vault("aa37f5a71083a9aa044fb936678bfd74f848e930d2de482a49a73540ea72aa5c02000000")  Decimal("10");"#,
+            Instruction::RecallVault {
+                component_address: ComponentAddress::from_str(
+                    "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                )
+                .unwrap(),
+                vault_id: (
+                    Hash::from_str(
+                        "aa37f5a71083a9aa044fb936678bfd74f848e930d2de482a49a73540ea72aa5c"
+                    )
+                    .unwrap(),
+                    2
+                ),
+                amount: Decimal::from(10),
+            }
+        );
+    }
+
+    #[test]
+    fn test_blob_failures() {
+        let code = include_bytes!("../examples/code.blob").to_vec();
+        assert_eq!(
+            crate::compile(
+                r#"PUBLISH_PACKAGE Blob("0577f08f0322aba5d9567821972438561d69510d84e0cf4f85b0081b188b5421");"#,
+                vec![]
+            ),
+            Err(crate::CompileError::GeneratorError(
+                GeneratorError::BlobNotFound(hash(&code)),
+                Span {
+                    start: (1, 17),
+                    end: (1, 88)
+                }
+            ))
+        );
+        assert_eq!(
+            crate::compile(
+                r#"CALL_METHOD ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "store" Blob("0577f08f0322aba5d9567821972438561d69510d84e0cf4f85b0081b188b5421");"#,
+                vec![]
+            ),
+            Err(crate::CompileError::GeneratorError(
+                GeneratorError::BlobNotFound(hash(&code)),
+                Span {
+                    start: (1, 96),
+                    end: (1, 167)
+                }
+            ))
+        );
+        assert_eq!(
+            crate::compile(r#"PUBLISH_PACKAGE Blob("c0ffee");"#, vec![code]),
+            Err(crate::CompileError::GeneratorError(
+                GeneratorError::InvalidBlob("c0ffee".into()),
+                Span {
+                    start: (1, 17),
+                    end: (1, 30)
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_blobs_not_at_hand() {
+        let code = include_bytes!("../examples/code.blob").to_vec();
+        assert_eq!(
+            crate::compile_without_blobs(
+                r#"PUBLISH_PACKAGE Blob("0577f08f0322aba5d9567821972438561d69510d84e0cf4f85b0081b188b5421");"#
+            )
+            .map(|tx| tx.instructions),
+            Ok(vec![Instruction::PublishPackage { code: hash(&code) }])
+        );
+        assert_eq!(
+            crate::compile_without_blobs(r#"PUBLISH_PACKAGE Blob("c0ffee");"#),
+            Err(crate::CompileError::GeneratorError(
+                GeneratorError::InvalidBlob("c0ffee".into()),
+                Span {
+                    start: (1, 17),
+                    end: (1, 30)
+                }
+            ))
+        );
     }
 
     #[test]
     fn test_transaction() {
         let tx = include_str!("../examples/complex.rtm");
-        let code = vec![
-            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x04, 0x05, 0x01, 0x70, 0x01, 0x01,
-            0x01, 0x05, 0x03, 0x01, 0x00, 0x10, 0x06, 0x19, 0x03, 0x7f, 0x01, 0x41, 0x80, 0x80,
-            0xc0, 0x00, 0x0b, 0x7f, 0x00, 0x41, 0x80, 0x80, 0xc0, 0x00, 0x0b, 0x7f, 0x00, 0x41,
-            0x80, 0x80, 0xc0, 0x00, 0x0b, 0x07, 0x25, 0x03, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72,
-            0x79, 0x02, 0x00, 0x0a, 0x5f, 0x5f, 0x64, 0x61, 0x74, 0x61, 0x5f, 0x65, 0x6e, 0x64,
-            0x03, 0x01, 0x0b, 0x5f, 0x5f, 0x68, 0x65, 0x61, 0x70, 0x5f, 0x62, 0x61, 0x73, 0x65,
-            0x03, 0x02, 0x00, 0x19, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x07, 0x12, 0x01, 0x00, 0x0f,
-            0x5f, 0x5f, 0x73, 0x74, 0x61, 0x63, 0x6b, 0x5f, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x65,
-            0x72, 0x00, 0x4d, 0x09, 0x70, 0x72, 0x6f, 0x64, 0x75, 0x63, 0x65, 0x72, 0x73, 0x02,
-            0x08, 0x6c, 0x61, 0x6e, 0x67, 0x75, 0x61, 0x67, 0x65, 0x01, 0x04, 0x52, 0x75, 0x73,
-            0x74, 0x00, 0x0c, 0x70, 0x72, 0x6f, 0x63, 0x65, 0x73, 0x73, 0x65, 0x64, 0x2d, 0x62,
-            0x79, 0x01, 0x05, 0x72, 0x75, 0x73, 0x74, 0x63, 0x1d, 0x31, 0x2e, 0x35, 0x39, 0x2e,
-            0x30, 0x20, 0x28, 0x39, 0x64, 0x31, 0x62, 0x32, 0x31, 0x30, 0x36, 0x65, 0x20, 0x32,
-            0x30, 0x32, 0x32, 0x2d, 0x30, 0x32, 0x2d, 0x32, 0x33, 0x29,
-        ];
+        let code = include_bytes!("../examples/code.blob").to_vec();
 
         assert_eq!(
-            crate::compile(tx).unwrap(),
+            crate::compile(tx, vec![code.clone()]).unwrap(),
             Transaction {
+                header: TransactionHeader::default(),
                 instructions: vec![
                     Instruction::CallMethod {
                         component_address: ComponentAddress::from_str(
@@ -1125,8 +1468,8 @@ mod tests {
                     Instruction::ReturnToWorktop { bucket_id: 513 },
                     Instruction::TakeFromWorktopByIds {
                         ids: BTreeSet::from([
-                            NonFungibleId::from_str("11").unwrap(),
-                            NonFungibleId::from_str("22").unwrap(),
+                            NonFungibleId::from_str("#11#").unwrap(),
+                            NonFungibleId::from_str("#22#").unwrap(),
                         ]),
                         resource_address: ResourceAddress::from_str(
                             "030000000000000000000000000000000000000000000000000004"
@@ -1140,9 +1483,17 @@ mod tests {
                         .unwrap(),
                         method: "deposit_batch".into(),
                     },
-                    Instruction::PublishPackage { code: code.clone() },
-                    Instruction::PublishPackage { code: code.clone() }
-                ]
+                    Instruction::CallMethod {
+                        component_address: ComponentAddress::from_str(
+                            "02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de".into()
+                        )
+                        .unwrap(),
+                        method: "deposit_batch".into(),
+                        args: vec![scrypto_encode(&Expression::EntireWorktop)]
+                    },
+                    Instruction::PublishPackage { code: hash(&code) },
+                ],
+                blobs: vec![code],
             }
         );
     }
@@ -1,5 +1,6 @@
 use scrypto::rust::str::FromStr;
 
+/// Represents a range of chars in the manifest, as 1-indexed (line, column) pairs.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Span {
     /// The start of the span, inclusive
@@ -59,6 +60,9 @@ pub enum TokenKind {
     Proof,
     NonFungibleId,
     NonFungibleAddress,
+    Vault,
+    Blob,
+    Expression,
 
     /* Sub-types */
     Some,
@@ -98,6 +102,14 @@ pub enum TokenKind {
     CallMethod,
     CallMethodWithAllResources,
     PublishPackage,
+    PublishPackageWithUpgradeRule,
+    UpgradePackage,
+    MigrateComponent,
+    SetPackageRoyaltyConfig,
+    ClaimPackageRoyalty,
+    SetPackageMetadata,
+    SetComponentMetadata,
+    RecallVault,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -109,10 +121,10 @@ pub struct Token {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LexerError {
     UnexpectedEof,
-    UnexpectedChar(char, usize),
-    InvalidNumber(String),
-    InvalidUnicode(u32),
-    UnknownIdentifier(String),
+    UnexpectedChar(char, Span),
+    InvalidNumber(String, Span),
+    InvalidUnicode(u32, Span),
+    UnknownIdentifier(String, Span),
 }
 
 #[derive(Debug, Clone)]
@@ -195,10 +207,7 @@ impl Lexer {
             '"' => self.tokenize_string(),
             'a'..='z' | 'A'..='Z' => self.tokenize_identifier(),
             '{' | '}' | '(' | ')' | '<' | '>' | ',' | ';' => self.tokenize_punctuation(),
-            _ => Err(LexerError::UnexpectedChar(
-                self.text[self.current],
-                self.current,
-            )),
+            c => Err(LexerError::UnexpectedChar(c, self.span(self.current))),
         }
         .map(Option::from)
     }
@@ -232,41 +241,41 @@ impl Lexer {
             'i' => match self.advance()? {
                 '1' => match self.advance()? {
                     '2' => match self.advance()? {
-                        '8' => Self::parse_int(&s, "i128", TokenKind::I128Literal),
+                        '8' => self.parse_int(&s, start, "i128", TokenKind::I128Literal),
                         _ => Err(self.unexpected_char()),
                     },
-                    '6' => Self::parse_int(&s, "i16", TokenKind::I16Literal),
+                    '6' => self.parse_int(&s, start, "i16", TokenKind::I16Literal),
                     _ => Err(self.unexpected_char()),
                 },
                 '3' => match self.advance()? {
-                    '2' => Self::parse_int(&s, "i32", TokenKind::I32Literal),
+                    '2' => self.parse_int(&s, start, "i32", TokenKind::I32Literal),
                     _ => Err(self.unexpected_char()),
                 },
                 '6' => match self.advance()? {
-                    '4' => Self::parse_int(&s, "i64", TokenKind::I64Literal),
+                    '4' => self.parse_int(&s, start, "i64", TokenKind::I64Literal),
                     _ => Err(self.unexpected_char()),
                 },
-                '8' => Self::parse_int(&s, "i8", TokenKind::I8Literal),
+                '8' => self.parse_int(&s, start, "i8", TokenKind::I8Literal),
                 _ => Err(self.unexpected_char()),
             },
             'u' => match self.advance()? {
                 '1' => match self.advance()? {
                     '2' => match self.advance()? {
-                        '8' => Self::parse_int(&s, "u128", TokenKind::U128Literal),
+                        '8' => self.parse_int(&s, start, "u128", TokenKind::U128Literal),
                         _ => Err(self.unexpected_char()),
                     },
-                    '6' => Self::parse_int(&s, "u16", TokenKind::U16Literal),
+                    '6' => self.parse_int(&s, start, "u16", TokenKind::U16Literal),
                     _ => Err(self.unexpected_char()),
                 },
                 '3' => match self.advance()? {
-                    '2' => Self::parse_int(&s, "u32", TokenKind::U32Literal),
+                    '2' => self.parse_int(&s, start, "u32", TokenKind::U32Literal),
                     _ => Err(self.unexpected_char()),
                 },
                 '6' => match self.advance()? {
-                    '4' => Self::parse_int(&s, "u64", TokenKind::U64Literal),
+                    '4' => self.parse_int(&s, start, "u64", TokenKind::U64Literal),
                     _ => Err(self.unexpected_char()),
                 },
-                '8' => Self::parse_int(&s, "u8", TokenKind::U8Literal),
+                '8' => self.parse_int(&s, start, "u8", TokenKind::U8Literal),
                 _ => Err(self.unexpected_char()),
             },
             _ => Err(self.unexpected_char()),
@@ -275,13 +284,15 @@ impl Lexer {
     }
 
     fn parse_int<T: FromStr>(
+        &self,
         int: &str,
+        start: usize,
         ty: &str,
         map: fn(T) -> TokenKind,
     ) -> Result<TokenKind, LexerError> {
         int.parse::<T>()
             .map(map)
-            .map_err(|_| LexerError::InvalidNumber(format!("{}{}", int, ty)))
+            .map_err(|_| LexerError::InvalidNumber(format!("{}{}", int, ty), self.span(start)))
     }
 
     fn tokenize_string(&mut self) -> Result<Token, LexerError> {
@@ -313,7 +324,10 @@ impl Lexer {
                                 return Err(self.unexpected_char());
                             }
                         }
-                        s.push(char::from_u32(unicode).ok_or(LexerError::InvalidUnicode(unicode))?);
+                        s.push(
+                            char::from_u32(unicode)
+                                .ok_or(LexerError::InvalidUnicode(unicode, self.span(start)))?,
+                        );
                     }
                     _ => {
                         return Err(self.unexpected_char());
@@ -389,6 +403,9 @@ impl Lexer {
             "Proof" => Ok(TokenKind::Proof),
             "NonFungibleId" => Ok(TokenKind::NonFungibleId),
             "NonFungibleAddress" => Ok(TokenKind::NonFungibleAddress),
+            "Vault" => Ok(TokenKind::Vault),
+            "Blob" => Ok(TokenKind::Blob),
+            "Expression" => Ok(TokenKind::Expression),
 
             "Some" => Ok(TokenKind::Some),
             "None" => Ok(TokenKind::None),
@@ -419,8 +436,16 @@ impl Lexer {
             "CALL_METHOD" => Ok(TokenKind::CallMethod),
             "CALL_METHOD_WITH_ALL_RESOURCES" => Ok(TokenKind::CallMethodWithAllResources),
             "PUBLISH_PACKAGE" => Ok(TokenKind::PublishPackage),
-
-            s @ _ => Err(LexerError::UnknownIdentifier(s.into())),
+            "PUBLISH_PACKAGE_WITH_UPGRADE_RULE" => Ok(TokenKind::PublishPackageWithUpgradeRule),
+            "UPGRADE_PACKAGE" => Ok(TokenKind::UpgradePackage),
+            "MIGRATE_COMPONENT" => Ok(TokenKind::MigrateComponent),
+            "SET_PACKAGE_ROYALTY_CONFIG" => Ok(TokenKind::SetPackageRoyaltyConfig),
+            "CLAIM_PACKAGE_ROYALTY" => Ok(TokenKind::ClaimPackageRoyalty),
+            "SET_PACKAGE_METADATA" => Ok(TokenKind::SetPackageMetadata),
+            "SET_COMPONENT_METADATA" => Ok(TokenKind::SetComponentMetadata),
+            "RECALL_VAULT" => Ok(TokenKind::RecallVault),
+
+            s @ _ => Err(LexerError::UnknownIdentifier(s.into(), self.span(start))),
         }
         .map(|kind| self.new_token(kind, start))
     }
@@ -443,11 +468,11 @@ impl Lexer {
         Ok(self.new_token(token_kind, start))
     }
 
-    fn index_to_coordinate(&self, index_inclusive: usize) -> (usize, usize) {
+    fn index_to_coordinate(&self, index: usize) -> (usize, usize) {
         // better to track this dynamically, instead of computing for each token
         let mut row = 1;
         let mut col = 1;
-        for i in 0..index_inclusive + 1 {
+        for i in 0..index {
             if self.text[i] == '\n' {
                 row += 1;
                 col = 1;
@@ -458,18 +483,23 @@ impl Lexer {
         (row, col)
     }
 
+    /// Returns the span from the char at `start` to the last consumed char.
+    fn span(&self, start: usize) -> Span {
+        Span {
+            start: self.index_to_coordinate(start),
+            end: self.index_to_coordinate(self.current.max(start + 1) - 1),
+        }
+    }
+
     fn new_token(&self, kind: TokenKind, start: usize) -> Token {
         Token {
             kind,
-            span: Span {
-                start: self.index_to_coordinate(start),
-                end: self.index_to_coordinate(self.current - 1),
-            },
+            span: self.span(start),
         }
     }
 
     fn unexpected_char(&self) -> LexerError {
-        LexerError::UnexpectedChar(self.text[self.current - 1], self.current - 1)
+        LexerError::UnexpectedChar(self.text[self.current - 1], self.span(self.current - 1))
     }
 }
 
@@ -523,7 +553,13 @@ mod tests {
         lex_ok!("false", vec![TokenKind::BoolLiteral(false)]);
         lex_error!(
             "false123u8",
-            LexerError::UnknownIdentifier("false123u8".into())
+            LexerError::UnknownIdentifier(
+                "false123u8".into(),
+                Span {
+                    start: (1, 1),
+                    end: (1, 10)
+                }
+            )
         );
     }
 
@@ -568,6 +604,36 @@ mod tests {
         lex_error!("\"", LexerError::UnexpectedEof);
     }
 
+    #[test]
+    fn test_spans() {
+        let mut lexer = Lexer::new("CLEAR_AUTH_ZONE;\n  \"abc\"");
+        assert_eq!(
+            lexer.next_token().map(|opt| opt.map(|t| t.span)),
+            Ok(Some(Span {
+                start: (1, 1),
+                end: (1, 15)
+            }))
+        );
+        lexer.next_token().unwrap();
+        assert_eq!(
+            lexer.next_token().map(|opt| opt.map(|t| t.span)),
+            Ok(Some(Span {
+                start: (2, 3),
+                end: (2, 7)
+            }))
+        );
+        lex_error!(
+            "1u8 $",
+            LexerError::UnexpectedChar(
+                '$',
+                Span {
+                    start: (1, 5),
+                    end: (1, 5)
+                }
+            )
+        );
+    }
+
     #[test]
     fn test_mixed() {
         lex_ok!(
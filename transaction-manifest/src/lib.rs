@@ -1,24 +1,65 @@
 pub mod ast;
+pub mod checker;
 pub mod decompiler;
+pub mod diagnostic;
 pub mod generator;
 pub mod lexer;
 pub mod parser;
 
 pub use decompiler::{decompile, DecompileError};
+pub use diagnostic::render_error;
 
 use radix_engine::model::Transaction;
+use radix_engine::transaction::AbiProvider;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CompileError {
     LexerError(lexer::LexerError),
     ParserError(parser::ParserError),
-    GeneratorError(generator::GeneratorError),
+    GeneratorError(generator::GeneratorError, lexer::Span),
+    CheckerError(Vec<checker::CheckError>),
 }
 
-pub fn compile(s: &str) -> Result<Transaction, CompileError> {
+/// Compiles a manifest into a transaction, attaching the given blobs.
+///
+/// Blobs are referred to from the manifest by their hash, like `Blob("<hash>")`.
+pub fn compile(s: &str, blobs: Vec<Vec<u8>>) -> Result<Transaction, CompileError> {
     let tokens = lexer::tokenize(s).map_err(CompileError::LexerError)?;
     let ast = parser::Parser::new(tokens)
         .parse_transaction()
         .map_err(CompileError::ParserError)?;
-    generator::generate_transaction(&ast).map_err(CompileError::GeneratorError)
+    generator::generate_transaction(&ast, blobs)
+        .map_err(|(e, span)| CompileError::GeneratorError(e, span))
+}
+
+/// Compiles a manifest whose blobs are not at hand, such as one open in an editor, assuming that
+/// every blob it refers to will be attached.
+pub fn compile_without_blobs(s: &str) -> Result<Transaction, CompileError> {
+    let tokens = lexer::tokenize(s).map_err(CompileError::LexerError)?;
+    let ast = parser::Parser::new(tokens)
+        .parse_transaction()
+        .map_err(CompileError::ParserError)?;
+    generator::generate_transaction_without_blobs(&ast)
+        .map_err(|(e, span)| CompileError::GeneratorError(e, span))
+}
+
+/// Compiles a manifest into a transaction, and checks the arguments of every call against the
+/// ABIs of the blueprints being called.
+pub fn compile_and_check<A: AbiProvider>(
+    s: &str,
+    blobs: Vec<Vec<u8>>,
+    abi_provider: &A,
+) -> Result<Transaction, CompileError> {
+    let tokens = lexer::tokenize(s).map_err(CompileError::LexerError)?;
+    let ast = parser::Parser::new(tokens)
+        .parse_transaction()
+        .map_err(CompileError::ParserError)?;
+    let transaction = generator::generate_transaction(&ast, blobs)
+        .map_err(|(e, span)| CompileError::GeneratorError(e, span))?;
+    let errors = checker::check_transaction(&ast, &transaction, abi_provider);
+    if errors.is_empty() {
+        Ok(transaction)
+    } else {
+        Err(CompileError::CheckerError(errors))
+    }
 }
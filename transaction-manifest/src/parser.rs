@@ -1,14 +1,22 @@
-use crate::ast::{Instruction, Transaction, Type, Value};
-use crate::lexer::{Token, TokenKind};
+use crate::ast::{Instruction, Transaction, Type, Value, ValueWithSpan};
+use crate::lexer::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParserError {
     UnexpectedEof,
     UnexpectedToken(Token),
-    InvalidNumberOfValues { actual: usize, expected: usize },
-    InvalidNumberOfTypes { actual: usize, expected: usize },
-    InvalidHex(String),
-    MissingEnumName,
+    InvalidNumberOfValues {
+        actual: usize,
+        expected: usize,
+        span: Span,
+    },
+    InvalidNumberOfTypes {
+        actual: usize,
+        expected: usize,
+        span: Span,
+    },
+    InvalidHex(String, Span),
+    MissingEnumName(Span),
 }
 
 pub struct Parser {
@@ -56,112 +64,163 @@ impl Parser {
         Ok(token)
     }
 
+    /// Returns the span from the token at `start` to the last consumed token.
+    fn span_since(&self, start: usize) -> Span {
+        Span {
+            start: self.tokens[start].span.start,
+            end: self.tokens[self.current - 1].span.end,
+        }
+    }
+
     pub fn parse_transaction(&mut self) -> Result<Transaction, ParserError> {
         let mut instructions = Vec::<Instruction>::new();
+        let mut spans = Vec::<Span>::new();
 
         while !self.is_eof() {
+            let start = self.peek()?.span.start;
             instructions.push(self.parse_instruction()?);
+            let end = self.tokens[self.current - 1].span.end;
+            spans.push(Span { start, end });
         }
 
-        Ok(Transaction { instructions })
+        Ok(Transaction {
+            instructions,
+            spans,
+        })
     }
 
     pub fn parse_instruction(&mut self) -> Result<Instruction, ParserError> {
         let token = self.advance()?;
         let instruction = match token.kind {
             TokenKind::TakeFromWorktop => Instruction::TakeFromWorktop {
-                resource_address: self.parse_value()?,
-                new_bucket: self.parse_value()?,
+                resource_address: self.parse_value_with_span()?,
+                new_bucket: self.parse_value_with_span()?,
             },
             TokenKind::TakeFromWorktopByAmount => Instruction::TakeFromWorktopByAmount {
-                amount: self.parse_value()?,
-                resource_address: self.parse_value()?,
-                new_bucket: self.parse_value()?,
+                amount: self.parse_value_with_span()?,
+                resource_address: self.parse_value_with_span()?,
+                new_bucket: self.parse_value_with_span()?,
             },
             TokenKind::TakeFromWorktopByIds => Instruction::TakeFromWorktopByIds {
-                ids: self.parse_value()?,
-                resource_address: self.parse_value()?,
-                new_bucket: self.parse_value()?,
+                ids: self.parse_value_with_span()?,
+                resource_address: self.parse_value_with_span()?,
+                new_bucket: self.parse_value_with_span()?,
             },
             TokenKind::ReturnToWorktop => Instruction::ReturnToWorktop {
-                bucket: self.parse_value()?,
+                bucket: self.parse_value_with_span()?,
             },
             TokenKind::AssertWorktopContains => Instruction::AssertWorktopContains {
-                resource_address: self.parse_value()?,
+                resource_address: self.parse_value_with_span()?,
             },
             TokenKind::AssertWorktopContainsByAmount => {
                 Instruction::AssertWorktopContainsByAmount {
-                    amount: self.parse_value()?,
-                    resource_address: self.parse_value()?,
+                    amount: self.parse_value_with_span()?,
+                    resource_address: self.parse_value_with_span()?,
                 }
             }
             TokenKind::AssertWorktopContainsByIds => Instruction::AssertWorktopContainsByIds {
-                ids: self.parse_value()?,
-                resource_address: self.parse_value()?,
+                ids: self.parse_value_with_span()?,
+                resource_address: self.parse_value_with_span()?,
             },
             TokenKind::PopFromAuthZone => Instruction::PopFromAuthZone {
-                new_proof: self.parse_value()?,
+                new_proof: self.parse_value_with_span()?,
             },
             TokenKind::PushToAuthZone => Instruction::PushToAuthZone {
-                proof: self.parse_value()?,
+                proof: self.parse_value_with_span()?,
             },
             TokenKind::ClearAuthZone => Instruction::ClearAuthZone,
             TokenKind::CreateProofFromAuthZone => Instruction::CreateProofFromAuthZone {
-                resource_address: self.parse_value()?,
-                new_proof: self.parse_value()?,
+                resource_address: self.parse_value_with_span()?,
+                new_proof: self.parse_value_with_span()?,
             },
             TokenKind::CreateProofFromAuthZoneByAmount => {
                 Instruction::CreateProofFromAuthZoneByAmount {
-                    amount: self.parse_value()?,
-                    resource_address: self.parse_value()?,
-                    new_proof: self.parse_value()?,
+                    amount: self.parse_value_with_span()?,
+                    resource_address: self.parse_value_with_span()?,
+                    new_proof: self.parse_value_with_span()?,
                 }
             }
             TokenKind::CreateProofFromAuthZoneByIds => Instruction::CreateProofFromAuthZoneByIds {
-                ids: self.parse_value()?,
-                resource_address: self.parse_value()?,
-                new_proof: self.parse_value()?,
+                ids: self.parse_value_with_span()?,
+                resource_address: self.parse_value_with_span()?,
+                new_proof: self.parse_value_with_span()?,
             },
             TokenKind::CreateProofFromBucket => Instruction::CreateProofFromBucket {
-                bucket: self.parse_value()?,
-                new_proof: self.parse_value()?,
+                bucket: self.parse_value_with_span()?,
+                new_proof: self.parse_value_with_span()?,
             },
             TokenKind::CloneProof => Instruction::CloneProof {
-                proof: self.parse_value()?,
-                new_proof: self.parse_value()?,
+                proof: self.parse_value_with_span()?,
+                new_proof: self.parse_value_with_span()?,
             },
             TokenKind::DropProof => Instruction::DropProof {
-                proof: self.parse_value()?,
+                proof: self.parse_value_with_span()?,
             },
             TokenKind::CallFunction => Instruction::CallFunction {
-                package_address: self.parse_value()?,
-                blueprint_name: self.parse_value()?,
-                function: self.parse_value()?,
+                package_address: self.parse_value_with_span()?,
+                blueprint_name: self.parse_value_with_span()?,
+                function: self.parse_value_with_span()?,
                 args: {
                     let mut values = vec![];
                     while self.peek()?.kind != TokenKind::Semicolon {
-                        values.push(self.parse_value()?);
+                        values.push(self.parse_value_with_span()?);
                     }
                     values
                 },
             },
             TokenKind::CallMethod => Instruction::CallMethod {
-                component_address: self.parse_value()?,
-                method: self.parse_value()?,
+                component_address: self.parse_value_with_span()?,
+                method: self.parse_value_with_span()?,
                 args: {
                     let mut values = vec![];
                     while self.peek()?.kind != TokenKind::Semicolon {
-                        values.push(self.parse_value()?);
+                        values.push(self.parse_value_with_span()?);
                     }
                     values
                 },
             },
             TokenKind::CallMethodWithAllResources => Instruction::CallMethodWithAllResources {
-                component_address: self.parse_value()?,
-                method: self.parse_value()?,
+                component_address: self.parse_value_with_span()?,
+                method: self.parse_value_with_span()?,
             },
             TokenKind::PublishPackage => Instruction::PublishPackage {
-                code: self.parse_value()?,
+                code: self.parse_value_with_span()?,
+            },
+            TokenKind::PublishPackageWithUpgradeRule => {
+                Instruction::PublishPackageWithUpgradeRule {
+                    code: self.parse_value_with_span()?,
+                    upgrade_rule: self.parse_value_with_span()?,
+                }
+            }
+            TokenKind::UpgradePackage => Instruction::UpgradePackage {
+                package_address: self.parse_value_with_span()?,
+                code: self.parse_value_with_span()?,
+            },
+            TokenKind::MigrateComponent => Instruction::MigrateComponent {
+                component_address: self.parse_value_with_span()?,
+                package_address: self.parse_value_with_span()?,
+                function: self.parse_value_with_span()?,
+            },
+            TokenKind::SetPackageRoyaltyConfig => Instruction::SetPackageRoyaltyConfig {
+                package_address: self.parse_value_with_span()?,
+                blueprint_name: self.parse_value_with_span()?,
+                royalty_config: self.parse_value_with_span()?,
+            },
+            TokenKind::ClaimPackageRoyalty => Instruction::ClaimPackageRoyalty {
+                package_address: self.parse_value_with_span()?,
+            },
+            TokenKind::SetPackageMetadata => Instruction::SetPackageMetadata {
+                package_address: self.parse_value_with_span()?,
+                metadata: self.parse_value_with_span()?,
+            },
+            TokenKind::SetComponentMetadata => Instruction::SetComponentMetadata {
+                component_address: self.parse_value_with_span()?,
+                metadata: self.parse_value_with_span()?,
+            },
+            TokenKind::RecallVault => Instruction::RecallVault {
+                component_address: self.parse_value_with_span()?,
+                vault: self.parse_value_with_span()?,
+                amount: self.parse_value_with_span()?,
             },
             _ => {
                 return Err(ParserError::UnexpectedToken(token));
@@ -171,6 +230,16 @@ impl Parser {
         Ok(instruction)
     }
 
+    /// Parses a value of an instruction, along with its location.
+    pub fn parse_value_with_span(&mut self) -> Result<ValueWithSpan, ParserError> {
+        let start = self.current;
+        let value = self.parse_value()?;
+        Ok(ValueWithSpan {
+            value,
+            span: self.span_since(start),
+        })
+    }
+
     pub fn parse_value(&mut self) -> Result<Value, ParserError> {
         let token = self.peek()?;
         match token.kind {
@@ -210,7 +279,10 @@ impl Parser {
             | TokenKind::Bucket
             | TokenKind::Proof
             | TokenKind::NonFungibleId
-            | TokenKind::NonFungibleAddress => self.parse_scrypto_types(),
+            | TokenKind::NonFungibleAddress
+            | TokenKind::Vault
+            | TokenKind::Blob
+            | TokenKind::Expression => self.parse_scrypto_types(),
             TokenKind::Bytes => self.parse_bytes(),
             _ => Err(ParserError::UnexpectedToken(token)),
         }
@@ -223,13 +295,14 @@ impl Parser {
     }
 
     pub fn parse_enum(&mut self) -> Result<Value, ParserError> {
+        let start = self.current;
         advance_match!(self, TokenKind::Enum);
         let mut name_and_fields =
             self.parse_values_any(TokenKind::OpenParenthesis, TokenKind::CloseParenthesis)?;
         let name = match name_and_fields.get(0) {
             Some(Value::String(name)) => name.clone(),
             _ => {
-                return Err(ParserError::MissingEnumName);
+                return Err(ParserError::MissingEnumName(self.span_since(start)));
             }
         };
         name_and_fields.remove(0);
@@ -286,7 +359,7 @@ impl Parser {
         let token = self.advance()?;
         let bytes = match token.kind {
             TokenKind::StringLiteral(s) => {
-                hex::decode(&s).map_err(|_| ParserError::InvalidHex(s.to_owned()))
+                hex::decode(&s).map_err(|_| ParserError::InvalidHex(s.to_owned(), token.span))
             }
             _ => Err(ParserError::UnexpectedToken(token)),
         };
@@ -352,6 +425,9 @@ impl Parser {
             TokenKind::NonFungibleAddress => {
                 Ok(Value::NonFungibleAddress(self.parse_values_one()?.into()))
             }
+            TokenKind::Vault => Ok(Value::Vault(self.parse_values_one()?.into())),
+            TokenKind::Blob => Ok(Value::Blob(self.parse_values_one()?.into())),
+            TokenKind::Expression => Ok(Value::Expression(self.parse_values_one()?.into())),
             _ => Err(ParserError::UnexpectedToken(token)),
         }
     }
@@ -375,12 +451,14 @@ impl Parser {
     }
 
     fn parse_values_one(&mut self) -> Result<Value, ParserError> {
+        let start = self.current;
         let values =
             self.parse_values_any(TokenKind::OpenParenthesis, TokenKind::CloseParenthesis)?;
         if values.len() != 1 {
             Err(ParserError::InvalidNumberOfValues {
                 actual: values.len(),
                 expected: 1,
+                span: self.span_since(start),
             })
         } else {
             Ok(values[0].clone())
@@ -388,6 +466,7 @@ impl Parser {
     }
 
     fn parse_generics(&mut self, n: usize) -> Result<Vec<Type>, ParserError> {
+        let start = self.current;
         advance_match!(self, TokenKind::LessThan);
         let mut types = Vec::new();
         while self.peek()?.kind != TokenKind::GreaterThan {
@@ -402,6 +481,7 @@ impl Parser {
             Err(ParserError::InvalidNumberOfTypes {
                 expected: n,
                 actual: types.len(),
+                span: self.span_since(start),
             })
         } else {
             Ok(types)
@@ -443,6 +523,9 @@ impl Parser {
             TokenKind::Bucket => Ok(Type::Bucket),
             TokenKind::Proof => Ok(Type::Proof),
             TokenKind::NonFungibleId => Ok(Type::NonFungibleId),
+            TokenKind::Vault => Ok(Type::Vault),
+            TokenKind::Blob => Ok(Type::Blob),
+            TokenKind::Expression => Ok(Type::Expression),
             _ => Err(ParserError::UnexpectedToken(token)),
         }
     }
@@ -618,8 +701,8 @@ mod tests {
             ParserError::UnexpectedToken(Token {
                 kind: TokenKind::GreaterThan,
                 span: Span {
-                    start: (1, 10),
-                    end: (1, 10)
+                    start: (1, 9),
+                    end: (1, 9)
                 }
             })
         );
@@ -627,16 +710,38 @@ mod tests {
             r#"PackageAddress("abc", "def")"#,
             ParserError::InvalidNumberOfValues {
                 actual: 2,
-                expected: 1
+                expected: 1,
+                span: Span {
+                    start: (1, 15),
+                    end: (1, 28)
+                }
             }
         );
         parse_value_error!(
             r#"Vec<String, String>("abc", "def")"#,
             ParserError::InvalidNumberOfTypes {
                 actual: 2,
-                expected: 1
+                expected: 1,
+                span: Span {
+                    start: (1, 4),
+                    end: (1, 19)
+                }
             }
         );
+        parse_value_error!(
+            r#"Enum(1u8)"#,
+            ParserError::MissingEnumName(Span {
+                start: (1, 1),
+                end: (1, 9)
+            })
+        );
+    }
+
+    fn spanned(value: Value, start: (usize, usize), end: (usize, usize)) -> ValueWithSpan {
+        ValueWithSpan {
+            value,
+            span: Span { start, end },
+        }
     }
 
     #[test]
@@ -644,109 +749,211 @@ mod tests {
         parse_instruction_ok!(
             r#"TAKE_FROM_WORKTOP_BY_AMOUNT  Decimal("1.0")  ResourceAddress("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d")  Bucket("xrd_bucket");"#,
             Instruction::TakeFromWorktopByAmount {
-                amount: Value::Decimal(Value::String("1.0".into()).into()),
-                resource_address: Value::ResourceAddress(
-                    Value::String("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into())
+                amount: spanned(
+                    Value::Decimal(Value::String("1.0".into()).into()),
+                    (1, 30),
+                    (1, 43)
+                ),
+                resource_address: spanned(
+                    Value::ResourceAddress(
+                        Value::String(
+                            "03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into()
+                        )
                         .into()
+                    ),
+                    (1, 46),
+                    (1, 118)
+                ),
+                new_bucket: spanned(
+                    Value::Bucket(Value::String("xrd_bucket".into()).into()),
+                    (1, 121),
+                    (1, 140)
                 ),
-                new_bucket: Value::Bucket(Value::String("xrd_bucket".into()).into()),
             }
         );
         parse_instruction_ok!(
             r#"TAKE_FROM_WORKTOP  ResourceAddress("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d")  Bucket("xrd_bucket");"#,
             Instruction::TakeFromWorktop {
-                resource_address: Value::ResourceAddress(
-                    Value::String("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into())
+                resource_address: spanned(
+                    Value::ResourceAddress(
+                        Value::String(
+                            "03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into()
+                        )
                         .into()
+                    ),
+                    (1, 20),
+                    (1, 92)
+                ),
+                new_bucket: spanned(
+                    Value::Bucket(Value::String("xrd_bucket".into()).into()),
+                    (1, 95),
+                    (1, 114)
                 ),
-                new_bucket: Value::Bucket(Value::String("xrd_bucket".into()).into()),
             }
         );
         parse_instruction_ok!(
             r#"ASSERT_WORKTOP_CONTAINS_BY_AMOUNT  Decimal("1.0")  ResourceAddress("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d");"#,
             Instruction::AssertWorktopContainsByAmount {
-                amount: Value::Decimal(Value::String("1.0".into()).into()),
-                resource_address: Value::ResourceAddress(
-                    Value::String("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into())
+                amount: spanned(
+                    Value::Decimal(Value::String("1.0".into()).into()),
+                    (1, 36),
+                    (1, 49)
+                ),
+                resource_address: spanned(
+                    Value::ResourceAddress(
+                        Value::String(
+                            "03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into()
+                        )
                         .into()
+                    ),
+                    (1, 52),
+                    (1, 124)
                 ),
             }
         );
         parse_instruction_ok!(
             r#"CREATE_PROOF_FROM_BUCKET  Bucket("xrd_bucket")  Proof("admin_auth");"#,
             Instruction::CreateProofFromBucket {
-                bucket: Value::Bucket(Value::String("xrd_bucket".into()).into()),
-                new_proof: Value::Proof(Value::String("admin_auth".into()).into()),
+                bucket: spanned(
+                    Value::Bucket(Value::String("xrd_bucket".into()).into()),
+                    (1, 27),
+                    (1, 46)
+                ),
+                new_proof: spanned(
+                    Value::Proof(Value::String("admin_auth".into()).into()),
+                    (1, 49),
+                    (1, 67)
+                ),
             }
         );
         parse_instruction_ok!(
             r#"CLONE_PROOF  Proof("admin_auth")  Proof("admin_auth2");"#,
             Instruction::CloneProof {
-                proof: Value::Proof(Value::String("admin_auth".into()).into()),
-                new_proof: Value::Proof(Value::String("admin_auth2".into()).into()),
+                proof: spanned(
+                    Value::Proof(Value::String("admin_auth".into()).into()),
+                    (1, 14),
+                    (1, 32)
+                ),
+                new_proof: spanned(
+                    Value::Proof(Value::String("admin_auth2".into()).into()),
+                    (1, 35),
+                    (1, 54)
+                ),
             }
         );
         parse_instruction_ok!(
             r#"DROP_PROOF Proof("admin_auth");"#,
             Instruction::DropProof {
-                proof: Value::Proof(Value::String("admin_auth".into()).into()),
+                proof: spanned(
+                    Value::Proof(Value::String("admin_auth".into()).into()),
+                    (1, 12),
+                    (1, 30)
+                ),
             }
         );
         parse_instruction_ok!(
             r#"CALL_FUNCTION  PackageAddress("01d1f50010e4102d88aacc347711491f852c515134a9ecf67ba17c")  "Airdrop"  "new"  500u32  HashMap<String, U8>("key", 1u8);"#,
             Instruction::CallFunction {
-                package_address: Value::PackageAddress(
-                    Value::String("01d1f50010e4102d88aacc347711491f852c515134a9ecf67ba17c".into())
+                package_address: spanned(
+                    Value::PackageAddress(
+                        Value::String(
+                            "01d1f50010e4102d88aacc347711491f852c515134a9ecf67ba17c".into()
+                        )
                         .into()
+                    ),
+                    (1, 16),
+                    (1, 87)
                 ),
-                blueprint_name: Value::String("Airdrop".into()),
-                function: Value::String("new".into()),
+                blueprint_name: spanned(Value::String("Airdrop".into()), (1, 90), (1, 98)),
+                function: spanned(Value::String("new".into()), (1, 101), (1, 105)),
                 args: vec![
-                    Value::U32(500),
-                    Value::HashMap(
-                        Type::String,
-                        Type::U8,
-                        vec![Value::String("key".into()), Value::U8(1)]
-                    )
+                    spanned(Value::U32(500), (1, 108), (1, 113)),
+                    spanned(
+                        Value::HashMap(
+                            Type::String,
+                            Type::U8,
+                            vec![Value::String("key".into()), Value::U8(1)]
+                        ),
+                        (1, 116),
+                        (1, 146)
+                    ),
                 ]
             }
         );
         parse_instruction_ok!(
             r#"CALL_METHOD  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  "refill"  Bucket("xrd_bucket")  Proof("admin_auth");"#,
             Instruction::CallMethod {
-                component_address: Value::ComponentAddress(
-                    Value::String("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into())
+                component_address: spanned(
+                    Value::ComponentAddress(
+                        Value::String(
+                            "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                        )
                         .into()
+                    ),
+                    (1, 14),
+                    (1, 87)
                 ),
-                method: Value::String("refill".into()),
+                method: spanned(Value::String("refill".into()), (1, 90), (1, 97)),
                 args: vec![
-                    Value::Bucket(Value::String("xrd_bucket".into()).into()),
-                    Value::Proof(Value::String("admin_auth".into()).into())
+                    spanned(
+                        Value::Bucket(Value::String("xrd_bucket".into()).into()),
+                        (1, 100),
+                        (1, 119)
+                    ),
+                    spanned(
+                        Value::Proof(Value::String("admin_auth".into()).into()),
+                        (1, 122),
+                        (1, 140)
+                    ),
                 ]
             }
         );
         parse_instruction_ok!(
             r#"CALL_METHOD  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  "withdraw_non_fungible"  NonFungibleId("00")  Proof("admin_auth");"#,
             Instruction::CallMethod {
-                component_address: Value::ComponentAddress(
-                    Value::String("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into())
+                component_address: spanned(
+                    Value::ComponentAddress(
+                        Value::String(
+                            "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                        )
                         .into()
+                    ),
+                    (1, 14),
+                    (1, 87)
+                ),
+                method: spanned(
+                    Value::String("withdraw_non_fungible".into()),
+                    (1, 90),
+                    (1, 112)
                 ),
-                method: Value::String("withdraw_non_fungible".into()),
                 args: vec![
-                    Value::NonFungibleId(Value::String("00".into()).into()),
-                    Value::Proof(Value::String("admin_auth".into()).into())
+                    spanned(
+                        Value::NonFungibleId(Value::String("00".into()).into()),
+                        (1, 115),
+                        (1, 133)
+                    ),
+                    spanned(
+                        Value::Proof(Value::String("admin_auth".into()).into()),
+                        (1, 136),
+                        (1, 154)
+                    ),
                 ]
             }
         );
         parse_instruction_ok!(
             r#"CALL_METHOD_WITH_ALL_RESOURCES  ComponentAddress("02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de") "deposit_batch";"#,
             Instruction::CallMethodWithAllResources {
-                component_address: Value::ComponentAddress(
-                    Value::String("02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de".into())
+                component_address: spanned(
+                    Value::ComponentAddress(
+                        Value::String(
+                            "02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de".into()
+                        )
                         .into()
+                    ),
+                    (1, 33),
+                    (1, 106)
                 ),
-                method: Value::String("deposit_batch".into()),
+                method: spanned(Value::String("deposit_batch".into()), (1, 108), (1, 122)),
             }
         );
     }